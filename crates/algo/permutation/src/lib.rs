@@ -67,6 +67,206 @@ pub fn prev_permutation<T: Ord>(a: &mut [T]) -> bool {
     false
 }
 
+/// 順列の辞書順での順位。
+///
+/// 相異なる要素からなる列 $a$ が、それらの要素の順列のうち辞書順で何番目（0-indexed）かを返す。
+///
+/// # Idea
+/// $c\_i$ を $a\_i$ より後ろにある $a\_i$ 未満の要素の個数とすると、順位は
+/// $$ \\sum\_{i=0}^{n-1} c\_i\\cdot (n-1-i)! $$
+/// で与えられる。これを
+/// $(\\cdots((c\_0\\cdot (n-1) + c\_1)\\cdot (n-2) + c\_2)\\cdots)\\cdot 1 + c\_{n-1}$
+/// の形で計算する。
+///
+/// # Panics
+/// - $a$ に重複する要素があるとき。
+/// - 順位が `u128` に収まらないとき。$n\\le 34$ であれば $n! \\lt 2^{128}$ なので起きない。
+///
+/// # Complexity
+/// $O(n^2)$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::algo::permutation_rank;
+///
+/// assert_eq!(permutation_rank(&[0, 1, 2]), 0);
+/// assert_eq!(permutation_rank(&[1, 0, 2]), 2);
+/// assert_eq!(permutation_rank(&[2, 1, 0]), 5);
+/// assert_eq!(permutation_rank(&['c', 'a', 'b']), 4);
+/// assert_eq!(permutation_rank::<()>(&[]), 0);
+///
+/// // 順位が小さければ、n が大きくてもよい
+/// let a: Vec<_> = (0..100).collect();
+/// assert_eq!(permutation_rank(&a), 0);
+/// ```
+pub fn permutation_rank<T: Ord>(a: &[T]) -> u128 {
+    let mut sorted: Vec<_> = a.iter().collect();
+    sorted.sort_unstable();
+    assert!(
+        sorted.windows(2).all(|w| w[0] < w[1]),
+        "elements must be distinct"
+    );
+
+    let n = a.len();
+    let mut res = 0_u128;
+    for i in 0..n {
+        let c = a[i + 1..].iter().filter(|&x| x < &a[i]).count();
+        res = res
+            .checked_mul((n - i) as u128)
+            .and_then(|r| r.checked_add(c as u128))
+            .expect("rank overflows u128");
+    }
+    res
+}
+
+/// 辞書順で $k$ 番目の順列。
+///
+/// $(0, 1, \\dots, n-1)$ の順列のうち、辞書順で $k$ 番目（0-indexed）のものを返す。
+/// [`permutation_rank`] の逆操作である。
+///
+/// [`permutation_rank`]: fn.permutation_rank.html
+///
+/// # Idea
+/// $k = \\sum\_{i=0}^{n-1} c\_i\\cdot (n-1-i)!$ ($0\\le c\_i\\le n-1-i$)
+/// と表すと（階乗進法）、$i$ 番目の要素は、まだ使っていない要素のうち
+/// $c\_i$ 番目に小さいものである。$c\_i$ は、$k$ を $1, 2, \\dots, n$
+/// で順に割った余りとして後ろから求まる。
+///
+/// # Panics
+/// $k \\ge n!$ のとき。
+///
+/// # Complexity
+/// $O(n^2)$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::algo::nth_permutation;
+///
+/// assert_eq!(nth_permutation(3, 0), [0, 1, 2]);
+/// assert_eq!(nth_permutation(3, 2), [1, 0, 2]);
+/// assert_eq!(nth_permutation(3, 5), [2, 1, 0]);
+/// assert_eq!(nth_permutation(0, 0), []);
+///
+/// // n! が u128 に収まらなくてもよい
+/// let a = nth_permutation(40, 1);
+/// assert_eq!(a[37..], [37, 39, 38]);
+/// ```
+pub fn nth_permutation(n: usize, mut k: u128) -> Vec<usize> {
+    let mut c = vec![0; n];
+    for i in 1..=n {
+        if k == 0 {
+            break;
+        }
+        c[n - i] = (k % i as u128) as usize;
+        k /= i as u128;
+    }
+    assert_eq!(k, 0, "k must be less than n!");
+
+    let mut rest: Vec<_> = (0..n).collect();
+    c.into_iter().map(|ci| rest.remove(ci)).collect()
+}
+
+/// 逆順列。
+///
+/// `res[perm[i]] == i` となる `res` を返す。
+///
+/// # Panics
+/// `perm` が `0..perm.len()` の順列でないとき、すなわち範囲外の要素や重複する要素があるとき。
+///
+/// # Complexity
+/// $O(n)$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::algo::inverse;
+///
+/// assert_eq!(inverse(&[2, 0, 1]), [1, 2, 0]);
+/// assert_eq!(inverse(&[]), []);
+/// ```
+pub fn inverse(perm: &[usize]) -> Vec<usize> {
+    check_permutation(perm);
+    let mut res = vec![0; perm.len()];
+    perm.iter().enumerate().for_each(|(i, &x)| res[x] = i);
+    res
+}
+
+/// 順列の合成。
+///
+/// `res[i] == p[q[i]]` となる `res` を返す。
+/// すなわち、$q$ を適用してから $p$ を適用する順列 $p\\circ q$ である。
+///
+/// # Panics
+/// - `p` と `q` の長さが異なるとき。
+/// - `p` または `q` が順列でないとき。
+///
+/// # Complexity
+/// $O(n)$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::algo::{compose, inverse};
+///
+/// let p = [2, 0, 1, 3];
+/// let q = [1, 0, 3, 2];
+/// assert_eq!(compose(&p, &q), [0, 2, 3, 1]);
+/// assert_eq!(compose(&p, &inverse(&p)), [0, 1, 2, 3]);
+/// ```
+pub fn compose(p: &[usize], q: &[usize]) -> Vec<usize> {
+    assert_eq!(p.len(), q.len(), "lengths must be equal");
+    check_permutation(p);
+    check_permutation(q);
+    q.iter().map(|&i| p[i]).collect()
+}
+
+/// サイクル分解。
+///
+/// 各 $i$ を `perm[i]` に移す順列をサイクルに分解して返す。
+/// 各サイクルは最小の要素から始まり `i`, `perm[i]`, `perm[perm[i]]`, ... の順に並ぶ。
+/// サイクルどうしは最小の要素の昇順に並ぶ。不動点も長さ 1 のサイクルとして含む。
+///
+/// # Panics
+/// `perm` が `0..perm.len()` の順列でないとき。
+///
+/// # Complexity
+/// $O(n)$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::algo::cycles;
+///
+/// assert_eq!(cycles(&[1, 2, 0, 3, 5, 4]), [vec![0, 1, 2], vec![3], vec![4, 5]]);
+/// assert!(cycles(&[]).is_empty());
+/// ```
+pub fn cycles(perm: &[usize]) -> Vec<Vec<usize>> {
+    check_permutation(perm);
+    let n = perm.len();
+    let mut visited = vec![false; n];
+    let mut res = vec![];
+    for i in 0..n {
+        if visited[i] {
+            continue;
+        }
+        let mut cur = vec![];
+        let mut j = i;
+        while !visited[j] {
+            visited[j] = true;
+            cur.push(j);
+            j = perm[j];
+        }
+        res.push(cur);
+    }
+    res
+}
+
+fn check_permutation(perm: &[usize]) {
+    let n = perm.len();
+    let mut seen = vec![false; n];
+    for &x in perm {
+        assert!(x < n && !seen[x], "not a permutation");
+        seen[x] = true;
+    }
+}
+
 fn next_permutation_with_count<T: Ord>(a: &mut [T], k: usize) -> bool {
     // precondition: k <= a.len(), and a[k..] is sorted
     // postcondition: a[k..] is sorted
@@ -317,3 +517,106 @@ fn partial_dup() {
         }
     }
 }
+
+#[test]
+fn rank() {
+    for n in 0..=7 {
+        let mut a: Vec<_> = (0..n).collect();
+        let mut k = 0;
+        loop {
+            assert_eq!(permutation_rank(&a), k);
+            assert_eq!(nth_permutation(n, k), a);
+            k += 1;
+            if !next_permutation(&mut a) {
+                break;
+            }
+        }
+        assert_eq!(k, (1..=n as u128).product());
+    }
+
+    // 順位が u128 に収まる最大のもの
+    let a: Vec<_> = (0..34).rev().collect();
+    let fact_34: u128 = (1..=34).product();
+    assert_eq!(permutation_rank(&a), fact_34 - 1);
+    assert_eq!(nth_permutation(34, fact_34 - 1), a);
+
+    let a = nth_permutation(35, u128::MAX);
+    assert_eq!(permutation_rank(&a), u128::MAX);
+    let mut b = a.clone();
+    assert!(prev_permutation(&mut b));
+    assert_eq!(permutation_rank(&b), u128::MAX - 1);
+}
+
+#[test]
+#[should_panic(expected = "rank overflows u128")]
+fn rank_overflow() {
+    let a: Vec<_> = (0..35).rev().collect();
+    permutation_rank(&a);
+}
+
+#[test]
+#[should_panic(expected = "elements must be distinct")]
+fn rank_dup() { permutation_rank(&[1, 0, 1]); }
+
+#[test]
+#[should_panic(expected = "k must be less than n!")]
+fn nth_out_of_range() { nth_permutation(3, 6); }
+
+#[test]
+fn cycle_inverse_compose() {
+    let mut f =
+        std::iter::successors(Some(296_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = |k: u64| f.next().unwrap() % k;
+    let mut random_perm = |n: usize| {
+        let mut p: Vec<_> = (0..n).collect();
+        for i in 1..n {
+            p.swap(i, rand(i as u64 + 1) as usize);
+        }
+        p
+    };
+
+    for n in 0..=50 {
+        let id: Vec<_> = (0..n).collect();
+        for _ in 0..20 {
+            let p = random_perm(n);
+            let q = random_perm(n);
+            let pi = inverse(&p);
+            assert_eq!(compose(&p, &pi), id);
+            assert_eq!(compose(&pi, &p), id);
+            assert_eq!(compose(&p, &id), p);
+            assert_eq!(inverse(&compose(&p, &q)), compose(&inverse(&q), &pi));
+
+            let cs = cycles(&p);
+            let mut from_cycles = vec![n; n];
+            for c in &cs {
+                assert_eq!(c[0], *c.iter().min().unwrap());
+                for k in 0..c.len() {
+                    from_cycles[c[k]] = c[(k + 1) % c.len()];
+                }
+            }
+            assert_eq!(from_cycles, p);
+            assert!(cs.windows(2).all(|w| w[0][0] < w[1][0]));
+            assert_eq!(cycles(&pi).len(), cs.len());
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "not a permutation")]
+fn inverse_dup() { inverse(&[0, 1, 1]); }
+
+#[test]
+#[should_panic(expected = "not a permutation")]
+fn inverse_out_of_range() { inverse(&[0, 3, 1]); }
+
+#[test]
+#[should_panic(expected = "not a permutation")]
+fn cycles_dup() { cycles(&[1, 1, 2]); }
+
+#[test]
+#[should_panic(expected = "not a permutation")]
+fn compose_not_perm() { compose(&[0, 1, 2], &[2, 2, 0]); }
+
+#[test]
+#[should_panic(expected = "lengths must be equal")]
+fn compose_len() { compose(&[0, 1, 2], &[1, 0]); }
@@ -8,7 +8,7 @@ use std::ops::{
     RangeBounds, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
 };
 
-use buf_range::{bounds_within, check_bounds};
+use buf_range::{bounds_within, check_bounds, check_bounds_range};
 
 type Word = u64;
 const WORD_SIZE: usize = (0 as Word).count_zeros() as usize;
@@ -62,6 +62,95 @@ impl BitSet {
         }
     }
 
+    /// 区間 `range` のビットをすべて立てる。
+    ///
+    /// # Panics
+    /// 区間の終端が `self.capacity()` より大きいとき。
+    ///
+    /// # Complexity
+    /// $O(1+(r-l)/w)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::BitSet;
+    ///
+    /// let mut bs = BitSet::new(200);
+    /// bs.set_range(60..130);
+    /// assert_eq!(bs.len(), 70);
+    /// assert_eq!(bs.find_first(..), Some(60));
+    /// assert_eq!(bs.find_last(..), Some(129));
+    /// ```
+    pub fn set_range(&mut self, range: impl RangeBounds<usize>) {
+        self.update_range(range, |w, mask| w | mask);
+    }
+
+    /// 区間 `range` のビットをすべて落とす。
+    ///
+    /// # Panics
+    /// 区間の終端が `self.capacity()` より大きいとき。
+    ///
+    /// # Complexity
+    /// $O(1+(r-l)/w)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::BitSet;
+    ///
+    /// let mut bs = BitSet::new(200);
+    /// bs.set_range(..);
+    /// bs.clear_range(10..190);
+    /// assert_eq!(bs.len(), 20);
+    /// assert!(bs.indices(..).eq((0..10).chain(190..200)));
+    /// ```
+    pub fn clear_range(&mut self, range: impl RangeBounds<usize>) {
+        self.update_range(range, |w, mask| w & !mask);
+    }
+
+    /// 区間 `range` のビットをすべて反転する。
+    ///
+    /// # Panics
+    /// 区間の終端が `self.capacity()` より大きいとき。
+    ///
+    /// # Complexity
+    /// $O(1+(r-l)/w)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::BitSet;
+    ///
+    /// let mut bs = BitSet::new(10);
+    /// bs.extend([1, 2, 3]);
+    /// bs.flip_range(2..6);
+    /// assert!(bs.indices(..).eq([1, 4, 5]));
+    /// ```
+    pub fn flip_range(&mut self, range: impl RangeBounds<usize>) {
+        self.update_range(range, |w, mask| w ^ mask);
+    }
+
+    fn update_range(
+        &mut self,
+        range: impl RangeBounds<usize>,
+        f: impl Fn(Word, Word) -> Word,
+    ) {
+        let Range { start, end } = bounds_within(range, self.capacity);
+        check_bounds_range(end, 0..=self.capacity);
+        if start >= end {
+            return;
+        }
+
+        let (ws, we) = (start / WORD_SIZE, (end - 1) / WORD_SIZE);
+        for wi in ws..=we {
+            let lo = if wi == ws { start % WORD_SIZE } else { 0 };
+            let hi = if wi == we { end - wi * WORD_SIZE } else { WORD_SIZE };
+            let mask = !0 >> (WORD_SIZE - (hi - lo)) << lo;
+            let w = self.buf[wi];
+            let new = f(w, mask);
+            self.len -= w.count_ones() as usize;
+            self.len += new.count_ones() as usize;
+            self.buf[wi] = new;
+        }
+    }
+
     #[must_use]
     pub fn contains(&self, index: usize) -> bool {
         check_bounds(index, self.capacity);
@@ -78,6 +167,23 @@ impl BitSet {
     pub fn capacity(&self) -> usize { self.capacity }
 
     // simple bit operations (assignment)
+    /// `self` を `other` との共通部分にする。
+    ///
+    /// capacity が異なっても panic せず、`self` の capacity は変わらない。
+    /// `other` の capacity 以上の位置のビットは落とされる。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::BitSet;
+    ///
+    /// let mut bs = BitSet::new(10);
+    /// bs.extend([1, 2, 4, 8]);
+    /// let mut other = BitSet::new(5);
+    /// other.extend([2, 3, 4]);
+    /// bs &= &other;
+    /// assert_eq!(bs.capacity(), 10);
+    /// assert!(bs.indices(..).eq([2, 4]));
+    /// ```
     pub fn and_assign(&mut self, other: &Self) {
         for (lhs, &rhs) in self.buf.iter_mut().zip(&other.buf) {
             *lhs &= rhs;
@@ -89,6 +195,23 @@ impl BitSet {
         }
         self.fixup();
     }
+    /// `self` を `other` との和集合にする。
+    ///
+    /// capacity が異なっても panic せず、`self` の capacity は変わらない。
+    /// `self` の capacity 以上の位置にある `other` のビットは捨てられる。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::BitSet;
+    ///
+    /// let mut bs = BitSet::new(4);
+    /// bs.extend([0, 3]);
+    /// let mut other = BitSet::new(5);
+    /// other.extend([0, 2, 4]);
+    /// bs |= &other;
+    /// assert_eq!(bs.capacity(), 4);
+    /// assert!(bs.indices(..).eq([0, 2, 3]));
+    /// ```
     pub fn or_assign(&mut self, other: &Self) {
         for (lhs, &rhs) in self.buf.iter_mut().zip(&other.buf) {
             *lhs |= rhs;
@@ -96,12 +219,20 @@ impl BitSet {
         self.fixup();
     }
     pub fn ior_assign(&mut self, other: &Self) { self.or_assign(other) }
+    /// `self` を `other` との対称差にする。
+    ///
+    /// capacity が異なっても panic せず、`self` の capacity は変わらない。
+    /// `self` の capacity 以上の位置にある `other` のビットは捨てられる。
     pub fn xor_assign(&mut self, other: &Self) {
         for (lhs, &rhs) in self.buf.iter_mut().zip(&other.buf) {
             *lhs ^= rhs;
         }
         self.fixup();
     }
+    /// `self` から `other` のビットを除く。
+    ///
+    /// capacity が異なっても panic せず、`self` の capacity は変わらない。
+    /// `other` の capacity 以上の位置のビットはそのまま残る。
     pub fn sub_assign(&mut self, other: &Self) {
         for (lhs, &rhs) in self.buf.iter_mut().zip(&other.buf) {
             *lhs &= !rhs;
@@ -114,6 +245,28 @@ impl BitSet {
         }
         self.fixup();
     }
+    /// 各ビットを `shl` だけ上位に移す。
+    ///
+    /// capacity は変わらず、capacity 以上の位置に移ったビットは捨てられる。
+    ///
+    /// # Panics
+    /// `shl >= self.capacity()` のとき。
+    ///
+    /// # Complexity
+    /// $O(n/w)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::BitSet;
+    ///
+    /// // 部分和として 10 以下のどの値を作れるか
+    /// let mut dp = BitSet::new(11);
+    /// dp.insert(0);
+    /// for &a in &[3, 5, 7] {
+    ///     dp |= &dp << a;
+    /// }
+    /// assert!(dp.indices(..).eq([0, 3, 5, 7, 8, 10]));
+    /// ```
     pub fn shl_assign(&mut self, shl: usize) {
         check_bounds(shl, self.capacity);
         let (quot, rem) = (shl / WORD_SIZE, shl % WORD_SIZE);
@@ -130,6 +283,26 @@ impl BitSet {
         }
         self.fixup();
     }
+    /// 各ビットを `shr` だけ下位に移す。
+    ///
+    /// capacity は変わらず、負の位置に移ったビットは捨てられる。
+    ///
+    /// # Panics
+    /// `shr >= self.capacity()` のとき。
+    ///
+    /// # Complexity
+    /// $O(n/w)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::BitSet;
+    ///
+    /// let mut bs = BitSet::new(100);
+    /// bs.extend([1, 3, 64, 99]);
+    /// bs >>= 3;
+    /// assert_eq!(bs.len(), 3);
+    /// assert!(bs.indices(..).eq([0, 61, 96]));
+    /// ```
     pub fn shr_assign(&mut self, shr: usize) {
         check_bounds(shr, self.capacity);
         let (quot, rem) = (shr / WORD_SIZE, shr % WORD_SIZE);
@@ -194,16 +367,43 @@ impl BitSet {
         tmp
     }
 
-    pub fn reserve_exact(&mut self, new_capacity: usize) {
-        let new_buf_len = (new_capacity + WORD_SIZE - 1) / WORD_SIZE;
-        if self.buf.len() > new_buf_len {
-            for x in &self.buf[new_buf_len..] {
-                self.len -= x.count_ones() as usize;
-            }
-        }
+    /// capacity を `new_capacity` に変更する。
+    ///
+    /// 拡大したときは新たな位置のビットは立っておらず、縮小したときは
+    /// `new_capacity` 以上の位置のビットは捨てられる。
+    ///
+    /// # Complexity
+    /// $O(n/w)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::BitSet;
+    ///
+    /// let mut bs = BitSet::new(10);
+    /// bs.extend([1, 4, 9]);
+    ///
+    /// bs.resize(100);
+    /// bs.extend([50, 99]);
+    /// assert!(bs.indices(..).eq([1, 4, 9, 50, 99]));
+    ///
+    /// bs.resize(5);
+    /// assert_eq!(bs.capacity(), 5);
+    /// assert_eq!(bs.len(), 2);
+    /// assert!(bs.indices(..).eq([1, 4]));
+    /// ```
+    pub fn resize(&mut self, new_capacity: usize) {
+        let new_buf_len = new_capacity.div_ceil(WORD_SIZE);
         self.buf.resize(new_buf_len, 0);
         self.capacity = new_capacity;
-        self.fixup_last();
+        let rem = new_capacity % WORD_SIZE;
+        if rem != 0 {
+            *self.buf.last_mut().unwrap() &= !(!0 << rem);
+        }
+        self.fixup_count();
+    }
+
+    pub fn reserve_exact(&mut self, new_capacity: usize) {
+        self.resize(new_capacity);
     }
 
     pub fn reserve(&mut self, at_least: usize) {
@@ -709,6 +909,8 @@ impl FromIterator<usize> for BitSet {
 
 macro_rules! impl_binary_op {
     ( $( ($trait:ident, $method:ident, $op_assign:ident, $op:ident), )* ) => { $(
+        /// 左辺の capacity を持つ `BitSet` を返す。
+        /// capacity が異なっても panic しない。
         impl $trait::<&BitSet> for BitSet {
             type Output = BitSet;
             fn $method(mut self, other: &Self) -> Self {
@@ -716,6 +918,8 @@ macro_rules! impl_binary_op {
                 self
             }
         }
+        /// 左辺の capacity を持つ `BitSet` を返す。
+        /// capacity が異なっても panic しない。
         impl $trait::<BitSet> for BitSet {
             type Output = BitSet;
             fn $method(mut self, other: Self) -> Self {
@@ -723,10 +927,14 @@ macro_rules! impl_binary_op {
                 self
             }
         }
+        /// 左辺の capacity を持つ `BitSet` を返す。
+        /// capacity が異なっても panic しない。
         impl<'a> $trait::<&'a BitSet> for &'a BitSet {
             type Output = BitSet;
             fn $method(self, other: Self) -> BitSet { self.$op(other) }
         }
+        /// 左辺の capacity を持つ `BitSet` を返す。
+        /// capacity が異なっても panic しない。
         impl<'a> $trait::<BitSet> for &'a BitSet {
             type Output = BitSet;
             fn $method(self, other: BitSet) -> BitSet { self.$op(&other) }
@@ -736,11 +944,15 @@ macro_rules! impl_binary_op {
 
 macro_rules! impl_binary_op_assign {
     ( $( ($trait:ident, $method:ident, $op_assign:ident), )* ) => { $(
+        /// 左辺の capacity は変わらない。
+        /// capacity が異なっても panic しない。
         impl $trait::<&BitSet> for BitSet {
             fn $method(&mut self, other: &Self) {
                 self.$op_assign(other);
             }
         }
+        /// 左辺の capacity は変わらない。
+        /// capacity が異なっても panic しない。
         impl $trait::<BitSet> for BitSet {
             fn $method(&mut self, other: Self) {
                 self.$op_assign(&other);
@@ -811,6 +1023,373 @@ impl Not for &'_ BitSet {
     fn not(self) -> BitSet { self.clone().not() }
 }
 
+type ArrayWord = u128;
+const ARRAY_WORD_SIZE: usize = (0 as ArrayWord).count_zeros() as usize;
+
+/// 固定長の bit set。
+///
+/// 容量 `WORDS * 128` の [`BitSet`] に相当するものを、`[u128; WORDS]`
+/// で持つ。容量がコンパイル時にわかっている場合に、heap
+/// へのアロケーションを避けるためのもの。
+///
+/// 集合演算の結果は常に同じ容量を持つので、[`BitSet`] と異なり
+/// `&` `|` `^` は可換である。また、[`Copy`] を実装している。
+///
+/// # Examples
+/// ```
+/// use nekolib::ds::BitArray;
+///
+/// let mut a = BitArray::<2>::new();
+/// a.insert(1);
+/// a.insert(200);
+/// assert_eq!(a.len(), 2);
+/// assert!(a.contains(200));
+///
+/// let b: BitArray<2> = [1, 2, 3].iter().copied().collect();
+/// assert!((a & b).indices(..).eq([1]));
+/// assert!((a | b).indices(..).eq([1, 2, 3, 200]));
+/// assert!((a << 10).indices(..).eq([11, 210]));
+/// ```
+///
+/// # Implementation notes
+/// 要素数は保持せず、[`len`](#method.len) のたびに数える。
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitArray<const WORDS: usize>([ArrayWord; WORDS]);
+
+impl<const WORDS: usize> Default for BitArray<WORDS> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<const WORDS: usize> BitArray<WORDS> {
+    pub const CAPACITY: usize = WORDS * ARRAY_WORD_SIZE;
+
+    #[must_use]
+    pub fn new() -> Self { Self([0; WORDS]) }
+
+    pub fn insert(&mut self, index: usize) {
+        check_bounds(index, Self::CAPACITY);
+        let (wi, bi) = (index / ARRAY_WORD_SIZE, index % ARRAY_WORD_SIZE);
+        self.0[wi] |= 1 << bi;
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        check_bounds(index, Self::CAPACITY);
+        let (wi, bi) = (index / ARRAY_WORD_SIZE, index % ARRAY_WORD_SIZE);
+        self.0[wi] &= !(1 << bi);
+    }
+
+    #[must_use]
+    pub fn contains(&self, index: usize) -> bool {
+        check_bounds(index, Self::CAPACITY);
+        let (wi, bi) = (index / ARRAY_WORD_SIZE, index % ARRAY_WORD_SIZE);
+        self.0[wi] >> bi & 1 != 0
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|x| x.count_ones() as usize).sum()
+    }
+    #[must_use]
+    pub fn is_empty(&self) -> bool { self.0.iter().all(|&x| x == 0) }
+    #[must_use]
+    pub fn capacity(&self) -> usize { Self::CAPACITY }
+
+    // simple bit operations (assignment)
+    pub fn and_assign(&mut self, other: &Self) {
+        for (lhs, &rhs) in self.0.iter_mut().zip(&other.0) {
+            *lhs &= rhs;
+        }
+    }
+    pub fn or_assign(&mut self, other: &Self) {
+        for (lhs, &rhs) in self.0.iter_mut().zip(&other.0) {
+            *lhs |= rhs;
+        }
+    }
+    pub fn ior_assign(&mut self, other: &Self) { self.or_assign(other) }
+    pub fn xor_assign(&mut self, other: &Self) {
+        for (lhs, &rhs) in self.0.iter_mut().zip(&other.0) {
+            *lhs ^= rhs;
+        }
+    }
+    pub fn sub_assign(&mut self, other: &Self) {
+        for (lhs, &rhs) in self.0.iter_mut().zip(&other.0) {
+            *lhs &= !rhs;
+        }
+    }
+    pub fn not_assign(&mut self) {
+        for lhs in &mut self.0 {
+            *lhs = !*lhs;
+        }
+    }
+    pub fn shl_assign(&mut self, shl: usize) {
+        check_bounds(shl, Self::CAPACITY);
+        let (quot, rem) = (shl / ARRAY_WORD_SIZE, shl % ARRAY_WORD_SIZE);
+        let buf = &mut self.0;
+        for i in (quot..WORDS).rev() {
+            let mut tmp = buf[i - quot] << rem;
+            if rem > 0 && i - quot > 0 {
+                tmp |= buf[i - quot - 1] >> (ARRAY_WORD_SIZE - rem);
+            }
+            buf[i] = tmp;
+        }
+        for e in &mut buf[..quot] {
+            *e = 0;
+        }
+    }
+    pub fn shr_assign(&mut self, shr: usize) {
+        check_bounds(shr, Self::CAPACITY);
+        let (quot, rem) = (shr / ARRAY_WORD_SIZE, shr % ARRAY_WORD_SIZE);
+        let buf = &mut self.0;
+        let mid = WORDS - quot;
+        for i in 0..mid {
+            let mut tmp = buf[i + quot] >> rem;
+            if rem > 0 && i + quot + 1 < WORDS {
+                tmp |= buf[i + quot + 1] << (ARRAY_WORD_SIZE - rem);
+            }
+            buf[i] = tmp;
+        }
+        for e in &mut buf[mid..] {
+            *e = 0;
+        }
+    }
+
+    // simple bit operations (non-assignment)
+    #[must_use]
+    pub fn and(&self, other: &Self) -> Self {
+        let mut tmp = *self;
+        tmp.and_assign(other);
+        tmp
+    }
+    #[must_use]
+    pub fn or(&self, other: &Self) -> Self {
+        let mut tmp = *self;
+        tmp.or_assign(other);
+        tmp
+    }
+    #[must_use]
+    pub fn ior(&self, other: &Self) -> Self { self.or(other) }
+    #[must_use]
+    pub fn xor(&self, other: &Self) -> Self {
+        let mut tmp = *self;
+        tmp.xor_assign(other);
+        tmp
+    }
+    #[must_use]
+    pub fn sub(&self, other: &Self) -> Self {
+        let mut tmp = *self;
+        tmp.sub_assign(other);
+        tmp
+    }
+    #[must_use]
+    pub fn not(&self) -> Self {
+        let mut tmp = *self;
+        tmp.not_assign();
+        tmp
+    }
+    #[must_use]
+    pub fn shl(&self, shl: usize) -> Self {
+        let mut tmp = *self;
+        tmp.shl_assign(shl);
+        tmp
+    }
+    #[must_use]
+    pub fn shr(&self, shr: usize) -> Self {
+        let mut tmp = *self;
+        tmp.shr_assign(shr);
+        tmp
+    }
+
+    #[must_use]
+    pub fn indices(
+        &self,
+        range: impl RangeBounds<usize>,
+    ) -> ArrayIndices<'_, WORDS> {
+        let Range { start, end } = bounds_within(range, Self::CAPACITY);
+        ArrayIndices { bit_array: self, range: (start, end) }
+    }
+
+    fn single_word(&self, start: usize, end: usize) -> ArrayWord {
+        // [start..end] の bit からなる word を返す。
+        // start < end <= (start / ARRAY_WORD_SIZE + 1) * ARRAY_WORD_SIZE
+        // は仮定する。
+        let (wi, bs) = (start / ARRAY_WORD_SIZE, start % ARRAY_WORD_SIZE);
+        let len = end - start;
+        let w = self.0[wi] >> bs;
+        if len == ARRAY_WORD_SIZE { w } else { w & !(!0 << len) }
+    }
+
+    #[must_use]
+    pub fn find_first(&self, range: impl RangeBounds<usize>) -> Option<usize> {
+        let Range { mut start, end } = bounds_within(range, Self::CAPACITY);
+        while start < end {
+            let next = end.min((start / ARRAY_WORD_SIZE + 1) * ARRAY_WORD_SIZE);
+            let w = self.single_word(start, next);
+            if w != 0 {
+                return Some(start + w.trailing_zeros() as usize);
+            }
+            start = next;
+        }
+        None
+    }
+
+    #[must_use]
+    pub fn find_last(&self, range: impl RangeBounds<usize>) -> Option<usize> {
+        let Range { start, mut end } = bounds_within(range, Self::CAPACITY);
+        while start < end {
+            let prev = start.max((end - 1) / ARRAY_WORD_SIZE * ARRAY_WORD_SIZE);
+            let w = self.single_word(prev, end);
+            if w != 0 {
+                let bsr = ARRAY_WORD_SIZE - 1 - w.leading_zeros() as usize;
+                return Some(prev + bsr);
+            }
+            end = prev;
+        }
+        None
+    }
+}
+
+pub struct ArrayIndices<'a, const WORDS: usize> {
+    range: (usize, usize),
+    bit_array: &'a BitArray<WORDS>,
+}
+
+impl<const WORDS: usize> Iterator for ArrayIndices<'_, WORDS> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.range;
+        if start >= end {
+            return None;
+        }
+        let res = self.bit_array.find_first(start..end);
+        self.range.0 = res.map(|i| i + 1).unwrap_or(self.range.1);
+        res
+    }
+}
+
+impl<const WORDS: usize> DoubleEndedIterator for ArrayIndices<'_, WORDS> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.range;
+        if start >= end {
+            return None;
+        }
+        let res = self.bit_array.find_last(start..end);
+        self.range.1 = res.unwrap_or(self.range.0);
+        res
+    }
+}
+
+impl<const WORDS: usize> Ord for BitArray<WORDS> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BitSet と同様、indices() の辞書順で比較する。
+        (self.0.iter().map(|x| x.reverse_bits()))
+            .cmp(other.0.iter().map(|x| x.reverse_bits()))
+    }
+}
+
+impl<const WORDS: usize> PartialOrd for BitArray<WORDS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const WORDS: usize> fmt::Binary for BitArray<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &w in &self.0 {
+            write!(f, "{0:01$b}", w.reverse_bits(), ARRAY_WORD_SIZE)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const WORDS: usize> fmt::Debug for BitArray<WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.indices(..)).finish()
+    }
+}
+
+impl<const WORDS: usize> Extend<usize> for BitArray<WORDS> {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = usize>,
+    {
+        for i in iter {
+            self.insert(i);
+        }
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitArray<WORDS> {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = usize>,
+    {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+macro_rules! impl_array_ops {
+    ( $( (
+        $trait:ident, $method:ident,
+        $trait_assign:ident, $method_assign:ident, $op_assign:ident
+    ), )* ) => { $(
+        impl<const WORDS: usize> $trait for BitArray<WORDS> {
+            type Output = Self;
+            fn $method(mut self, other: Self) -> Self {
+                self.$op_assign(&other);
+                self
+            }
+        }
+        impl<const WORDS: usize> $trait_assign for BitArray<WORDS> {
+            fn $method_assign(&mut self, other: Self) {
+                self.$op_assign(&other);
+            }
+        }
+    )* }
+}
+
+macro_rules! impl_array_shift {
+    ( $( (
+        $trait:ident, $method:ident,
+        $trait_assign:ident, $method_assign:ident, $op_assign:ident
+    ), )* ) => { $(
+        impl<const WORDS: usize> $trait<usize> for BitArray<WORDS> {
+            type Output = Self;
+            fn $method(mut self, sh: usize) -> Self {
+                self.$op_assign(sh);
+                self
+            }
+        }
+        impl<const WORDS: usize> $trait_assign<usize> for BitArray<WORDS> {
+            fn $method_assign(&mut self, sh: usize) {
+                self.$op_assign(sh);
+            }
+        }
+    )* }
+}
+
+impl_array_ops! {
+    (BitAnd, bitand, BitAndAssign, bitand_assign, and_assign),
+    (BitOr, bitor, BitOrAssign, bitor_assign, or_assign),
+    (BitXor, bitxor, BitXorAssign, bitxor_assign, xor_assign),
+    (Sub, sub, SubAssign, sub_assign, sub_assign),
+}
+
+impl_array_shift! {
+    (Shl, shl, ShlAssign, shl_assign, shl_assign),
+    (Shr, shr, ShrAssign, shr_assign, shr_assign),
+}
+
+impl<const WORDS: usize> Not for BitArray<WORDS> {
+    type Output = Self;
+    fn not(mut self) -> Self {
+        self.not_assign();
+        self
+    }
+}
+
 fn bsf(w: Word) -> usize { w.trailing_zeros() as usize }
 fn bsr(w: Word) -> usize { WORD_SIZE - 1 - w.leading_zeros() as usize }
 
@@ -818,7 +1397,7 @@ fn bsr(w: Word) -> usize { WORD_SIZE - 1 - w.leading_zeros() as usize }
 mod test {
     use std::collections::BTreeSet;
 
-    use super::{BitSet, WORD_SIZE};
+    use super::{BitArray, BitSet, ARRAY_WORD_SIZE, WORD_SIZE};
 
     #[test]
     fn fmt() {
@@ -909,6 +1488,41 @@ mod test {
         }
     }
 
+    #[test]
+    fn resize() {
+        let set = SET;
+        let n = set.len();
+
+        let mut bs = BitSet::new(0);
+        for i in 0..n {
+            bs.resize(set[i] + 1);
+            bs.extend(set[..=i].iter().copied());
+            assert_eq!(bs.len(), i + 1);
+        }
+
+        for i in 0..=set[n - 1] + 1 {
+            let mut bs = bs.clone();
+            bs.resize(i);
+            let expected: Vec<_> =
+                set.iter().copied().filter(|&x| x < i).collect();
+            assert_eq!(bs.capacity(), i);
+            assert_eq!(bs.len(), expected.len());
+            assert!(bs.indices(..).eq(expected.iter().copied()));
+
+            // 捨てられたビットが拡大後に復活しない
+            bs.resize(set[n - 1] + 1);
+            assert_eq!(bs.len(), expected.len());
+            assert_eq!(bs.find_first(i..), None);
+        }
+
+        // autofix が無効でも len は再計算される
+        let mut bs = BitSet::new(10);
+        bs.autofix(false);
+        bs.extend([1, 3, 5, 7]);
+        bs.resize(4);
+        assert_eq!(bs.len(), 2);
+    }
+
     #[test]
     fn indices() {
         let set = SET;
@@ -1029,6 +1643,56 @@ mod test {
         }
     }
 
+    #[test]
+    fn shift_len() {
+        for m in [1, 2, 63, 64, 65, 127, 128, 129, 200] {
+            let mut bs = BitSet::new(m);
+            bs.extend((0..m).filter(|i| i % 3 != 1));
+            for sh in 0..m {
+                let expected: Vec<_> =
+                    bs.indices(..).map(|i| i + sh).filter(|&i| i < m).collect();
+                let actual = &bs << sh;
+                assert_eq!(actual.capacity(), m);
+                assert_eq!(actual.len(), expected.len());
+                assert!(actual.indices(..).eq(expected));
+
+                let expected: Vec<_> = bs
+                    .indices(..)
+                    .filter(|&i| i >= sh)
+                    .map(|i| i - sh)
+                    .collect();
+                let mut actual = bs.clone();
+                actual >>= sh;
+                assert_eq!(actual.capacity(), m);
+                assert_eq!(actual.len(), expected.len());
+                assert!(actual.indices(..).eq(expected));
+            }
+        }
+
+        // 部分和 DP
+        let a = [3, 5, 7, 11, 13, 50, 70];
+        let m = 130;
+        let mut dp = BitSet::new(m);
+        dp.insert(0);
+        let mut naive = vec![false; m];
+        naive[0] = true;
+        for &ai in &a {
+            dp |= &dp << ai;
+            for i in (ai..m).rev() {
+                naive[i] |= naive[i - ai];
+            }
+        }
+        assert!(dp.indices(..).eq((0..m).filter(|&i| naive[i])));
+        assert_eq!(dp.len(), naive.iter().filter(|&&x| x).count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn shl_capacity() {
+        let bs = BitSet::new(10);
+        let _ = bs << 10;
+    }
+
     #[test]
     fn not() {
         let bs: BitSet = [0, 1, 3, 6].iter().copied().collect();
@@ -1039,6 +1703,127 @@ mod test {
         assert_eq!(not_bs.len(), 3);
     }
 
+    #[test]
+    fn update_range() {
+        use std::ops::Bound::{Excluded, Included};
+
+        for m in [0, 1, 63, 64, 65, 128, 130] {
+            for l in 0..=m {
+                for r in (l..=m).filter(|&r| r - l < 5 || m - r < 3) {
+                    let mut bs = BitSet::new(m);
+                    bs.extend((0..m).filter(|i| i % 3 == 0));
+                    let naive = |f: fn(bool) -> bool| {
+                        let mut tmp = BitSet::new(m);
+                        for i in 0..m {
+                            let x = i % 3 == 0;
+                            if if (l..r).contains(&i) { f(x) } else { x } {
+                                tmp.insert(i);
+                            }
+                        }
+                        tmp
+                    };
+
+                    let mut actual = bs.clone();
+                    actual.set_range(l..r);
+                    let expected = naive(|_| true);
+                    assert_eq!(actual.len(), expected.len());
+                    assert_eq!(actual, expected);
+
+                    let mut actual = bs.clone();
+                    actual.clear_range(l..r);
+                    let expected = naive(|_| false);
+                    assert_eq!(actual.len(), expected.len());
+                    assert_eq!(actual, expected);
+
+                    let mut actual = bs.clone();
+                    actual.flip_range(l..r);
+                    let expected = naive(|x| !x);
+                    assert_eq!(actual.len(), expected.len());
+                    assert_eq!(actual, expected);
+                    actual.flip_range((Included(l), Excluded(r)));
+                    assert_eq!(actual, bs);
+                }
+            }
+        }
+
+        let mut bs = BitSet::new(100);
+        bs.set_range(..);
+        assert_eq!(bs.len(), 100);
+        bs.flip_range(..);
+        assert!(bs.is_empty());
+        bs.set_range(30..30);
+        bs.set_range(100..);
+        assert!(bs.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_range_out_of_bounds() {
+        let mut bs = BitSet::new(100);
+        bs.set_range(50..101);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clear_range_empty_out_of_bounds() {
+        let mut bs = BitSet::new(100);
+        bs.clear_range(101..101);
+    }
+
+    #[test]
+    fn not_len() {
+        // capacity を超えた上位ビットは立たない
+        for m in [0, 1, 63, 64, 65, 127, 128, 129, 200] {
+            let mut bs = BitSet::new(m);
+            bs.extend((0..m).filter(|i| i % 3 == 0));
+            let not_bs = !&bs;
+            assert_eq!(not_bs.capacity(), m);
+            assert_eq!(not_bs.len(), m - bs.len());
+            assert!(not_bs.indices(..).eq((0..m).filter(|i| i % 3 != 0)));
+            assert_eq!(!&not_bs, bs);
+
+            bs.not_assign();
+            assert_eq!(bs, not_bs);
+            assert_eq!((!BitSet::new(m)).len(), m);
+        }
+    }
+
+    #[test]
+    fn binary_ops_len() {
+        let m = 150;
+        let bs1: BitSet = {
+            let mut tmp = BitSet::new(m);
+            tmp.extend((0..m).filter(|i| i % 2 == 0));
+            tmp
+        };
+        let bs2: BitSet = {
+            let mut tmp = BitSet::new(m);
+            tmp.extend((0..m).filter(|i| i % 3 == 0));
+            tmp
+        };
+        let count = |f: fn(bool, bool) -> bool| {
+            (0..m).filter(|i| f(i % 2 == 0, i % 3 == 0)).count()
+        };
+
+        assert_eq!((&bs1 & &bs2).len(), count(|x, y| x & y));
+        assert_eq!((&bs1 | &bs2).len(), count(|x, y| x | y));
+        assert_eq!((&bs1 ^ &bs2).len(), count(|x, y| x ^ y));
+        assert_eq!((!(&bs1 ^ &bs2)).len(), count(|x, y| x == y));
+
+        let mut bs = bs1.clone();
+        bs &= &bs2;
+        assert_eq!(bs, &bs1 & &bs2);
+        let mut bs = bs1.clone();
+        bs |= bs2.clone();
+        assert_eq!(bs, &bs1 | &bs2);
+        let mut bs = bs1.clone();
+        bs ^= &bs2;
+        assert_eq!(bs, &bs1 ^ &bs2);
+        bs ^= &bs2;
+        assert_eq!(bs, bs1);
+        assert_eq!(bs.len(), count(|x, _| x));
+    }
+
     #[test]
     fn binary_ops() {
         let m = 200;
@@ -1269,4 +2054,68 @@ mod test {
         assert_eq!(test_internal(&bs3, &bs2), Ok(()));
         assert_eq!(test_internal(&bs3, &bs3), Ok(()));
     }
+
+    #[test]
+    fn bit_array() {
+        const WORDS: usize = 12;
+        type BA = BitArray<WORDS>;
+        let m = BA::CAPACITY;
+
+        let sets = [SET, CONSEC, FIB, POW2];
+        let bss: Vec<BitSet> = sets
+            .iter()
+            .map(|s| {
+                let mut bs = BitSet::new(m);
+                bs.extend(s.iter().copied());
+                bs
+            })
+            .collect();
+        let bas: Vec<BA> =
+            sets.iter().map(|s| s.iter().copied().collect()).collect();
+
+        fn is_eq<const W: usize>(actual: BitArray<W>, expected: BitSet) -> bool {
+            actual.len() == expected.len()
+                && actual.indices(..).eq(expected.indices(..))
+                && actual.indices(..).rev().eq(expected.indices(..).rev())
+        }
+
+        for (bs, &ba) in bss.iter().zip(&bas) {
+            assert!(is_eq(ba, bs.clone()));
+            assert!(is_eq(!ba, !bs));
+            for i in 0..3 * ARRAY_WORD_SIZE {
+                assert!(is_eq(ba << i, bs << i));
+                assert!(is_eq(ba >> i, bs >> i));
+            }
+            for i in (0..m).step_by(61) {
+                assert_eq!(ba.find_first(i..), bs.find_first(i..));
+                assert_eq!(ba.find_last(..i), bs.find_last(..i));
+                let r = i..m.min(i + 200);
+                assert!(ba.indices(r.clone()).eq(bs.indices(r)));
+            }
+        }
+
+        for (bs1, &ba1) in bss.iter().zip(&bas) {
+            for (bs2, &ba2) in bss.iter().zip(&bas) {
+                assert!(is_eq(ba1 & ba2, bs1 & bs2));
+                assert!(is_eq(ba1 | ba2, bs1 | bs2));
+                assert!(is_eq(ba1 ^ ba2, bs1 ^ bs2));
+                assert!(is_eq(ba1 - ba2, bs1 - bs2));
+                assert_eq!(ba1.cmp(&ba2), bs1.cmp(bs2));
+            }
+        }
+
+        let mut ba = BA::new();
+        let mut bs = BitSet::new(m);
+        for i in (0..m).step_by(7) {
+            ba.insert(i);
+            bs.insert(i);
+        }
+        for i in (0..m).step_by(3) {
+            ba.remove(i);
+            bs.remove(i);
+        }
+        assert!(is_eq(ba, bs.clone()));
+        assert_eq!(format!("{:b}", ba), format!("{:b}", bs));
+        assert_eq!(format!("{:?}", ba), format!("{:?}", bs));
+    }
 }
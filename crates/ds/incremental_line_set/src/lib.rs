@@ -1,7 +1,10 @@
 //! 直線の集合。
 
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::ops::Neg;
 
 use btree_bimap::BTreeBimap;
 
@@ -15,6 +18,14 @@ use btree_bimap::BTreeBimap;
 /// 言い換えると、直線の追加クエリと、特定の $x$ 座標での $y$
 /// 座標の最小値を求めるクエリを捌く。いわゆる CHT。
 ///
+/// 最大値を求めたい場合は、[`new_upper`] で作った
+/// `IncrementalLineSet<_, UpperEnvelope>` の [`max`] を用いる。
+/// 内部では [`Neg`] で符号を反転した直線を管理するので、整数の場合は
+/// 直線の係数に最小値（`i64::MIN` など）を含めないこと。
+///
+/// [`new_upper`]: #method.new_upper
+/// [`max`]: #method.max
+///
 /// # Idea
 /// 次の二つの連想配列を管理する。
 /// - $a$ を与えると、$\\lambda x.\\; ax+b \\in S$ なる $b$ を返す。
@@ -35,12 +46,23 @@ use btree_bimap::BTreeBimap;
 /// &= \\left\\lfloor\\frac{b\_r-b\_l}{a\_l-a\_r}\\right\\rfloor.
 /// \\end{aligned} $$
 ///
+/// この除算は、$b\_r-b\_l$ や $a\_l-a\_r$ がオーバーフローしないように、
+/// 符号と絶対値に分けて `u128` で行う。
+/// 一方、$x=x\_0$ での値は、最小値を与える直線 $\\lambda x.\\; ax+b$ について
+/// $ax\_0+b$ をそのままの型で計算するので、これが表せる範囲にある必要がある。
+/// 係数の型は [`ChtInt`] を実装した `i8`, `i16`, `i32`, `i64`, `i128`, `isize`
+/// から選べるので、値の範囲に応じて小さい型を用いることもできる。
+///
+/// 実数を扱いたい場合は、[`OrdF64`] を用いる。このときは交点の $x$
+/// 座標そのものを用いる。
+///
 /// # Complexity
 /// |演算|時間計算量|
 /// |---|---|
 /// |`new`|$O(1)$|
 /// |`push`|$O(\\log(\|S\'\|))$|
 /// |`min`|$O(\\log(\|S\'\|))$|
+/// |`max`|$O(\\log(\|S\'\|))$|
 ///
 /// ここで、$S\'$ は $S$ から必要のない直線を除いたものからなる集合である。
 ///
@@ -153,17 +175,114 @@ use btree_bimap::BTreeBimap;
 /// assert_eq!(res, 1 * 1 + (-3) * 2 + 7 * 3);
 /// ```
 ///
+/// ```
+/// use nekolib::ds::IncrementalLineSet;
+///
+/// let mut ls = IncrementalLineSet::new_upper();
+/// assert_eq!(ls.max(0), None);
+///
+/// ls.push((2, 2));
+/// ls.push((-1, 3));
+/// ls.push((0, 1)); // 不要
+/// assert_eq!(ls.max(-1), Some(4));
+/// assert_eq!(ls.max(0), Some(3));
+/// assert_eq!(ls.max(1), Some(4));
+/// assert_eq!(ls.stats(), (3, 2, 1));
+///
+/// assert_eq!(
+///     format!("{:?}", ls),
+///     r"{\x. -x+3: ..=0, \x. 2x+2: ..=2147483647}"
+/// );
+/// ```
+///
 /// # References
 /// - <https://noshi91.hatenablog.com/entry/2021/03/23/200810>
 #[derive(Clone, Default)]
-pub struct IncrementalLineSet<I: Ord> {
+pub struct IncrementalLineSet<I: Ord, E = LowerEnvelope> {
     f: BTreeMap<I, I>,
     range: BTreeBimap<I, I>,
+    pushed: usize,
+    _e: PhantomData<E>,
+}
+
+/// [`IncrementalLineSet`] で最小値と最大値のどちらを求めるかを表す。
+pub trait Envelope<I>: Clone + Default {
+    /// 管理する直線の係数に変換する。
+    fn sign(x: I) -> I;
+}
+
+/// 最小値を求める。下側包絡線を管理する。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LowerEnvelope;
+
+/// 最大値を求める。上側包絡線を管理する。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UpperEnvelope;
+
+impl<I: ChtInt> Envelope<I> for LowerEnvelope {
+    fn sign(x: I) -> I { x }
+}
+
+impl<I: ChtInt + Neg<Output = I>> Envelope<I> for UpperEnvelope {
+    fn sign(x: I) -> I { -x }
 }
 
 impl<I: ChtInt> IncrementalLineSet<I> {
     pub fn new() -> Self { Self::default() }
+    pub fn min(&self, x: I) -> Option<I> { self.fold(x) }
+
+    /// $x$ での最小値を与える直線 $(a, b)$ を返す。
+    ///
+    /// 最小値を与える直線が複数あるときは、それらのうち傾きが最大のもの、
+    /// すなわち $x$ の左側で最小となるものを返す。
+    /// そのような直線が最小となる区間は空でないので、不要と判定されて
+    /// 捨てられていることはない。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::IncrementalLineSet;
+    ///
+    /// let mut ls = IncrementalLineSet::new();
+    /// assert_eq!(ls.min_line(0), None);
+    ///
+    /// ls.push((1, 0));
+    /// ls.push((-1, 2));
+    /// assert_eq!(ls.min_line(0), Some((1, 0)));
+    /// assert_eq!(ls.min_line(1), Some((1, 0))); // 同点
+    /// assert_eq!(ls.min_line(2), Some((-1, 2)));
+    /// ```
+    pub fn min_line(&self, x: I) -> Option<(I, I)> { self.fold_line(x) }
+}
+
+impl<I: ChtInt + Neg<Output = I>> IncrementalLineSet<I, UpperEnvelope> {
+    pub fn new_upper() -> Self { Self::default() }
+    pub fn max(&self, x: I) -> Option<I> { self.fold(x).map(I::neg) }
+
+    /// $x$ での最大値を与える直線 $(a, b)$ を返す。
+    ///
+    /// 最大値を与える直線が複数あるときは、それらのうち傾きが最小のもの、
+    /// すなわち $x$ の左側で最大となるものを返す。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::IncrementalLineSet;
+    ///
+    /// let mut ls = IncrementalLineSet::new_upper();
+    /// ls.push((1, 0));
+    /// ls.push((-1, 2));
+    /// assert_eq!(ls.max_line(0), Some((-1, 2)));
+    /// assert_eq!(ls.max_line(1), Some((-1, 2))); // 同点
+    /// assert_eq!(ls.max_line(2), Some((1, 0)));
+    /// ```
+    pub fn max_line(&self, x: I) -> Option<(I, I)> {
+        self.fold_line(x).map(|(a, b)| (-a, -b))
+    }
+}
+
+impl<I: ChtInt, E: Envelope<I>> IncrementalLineSet<I, E> {
     pub fn push(&mut self, (a, b): (I, I)) {
+        let (a, b) = (E::sign(a), E::sign(b));
+        self.pushed += 1;
         if self.f.is_empty() {
             let max = I::oo();
             self.f.insert(a, b);
@@ -176,12 +295,38 @@ impl<I: ChtInt> IncrementalLineSet<I> {
         self.remove_unused((a, b));
         self.insert((a, b));
     }
-    pub fn min(&self, x: I) -> Option<I> {
+    pub fn inner_len(&self) -> usize { self.f.len() }
+
+    /// 追加された直線の本数、保持している直線の本数、捨てた直線の本数を返す。
+    ///
+    /// 保持している直線の本数は [`inner_len`](#method.inner_len)、すなわち
+    /// $\|S\'\|$ に等しい。捨てた直線には、追加時点で不要と判定されたものと、
+    /// 後から追加された直線によって不要になったものの両方を含む。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::IncrementalLineSet;
+    ///
+    /// let mut ls = IncrementalLineSet::new();
+    /// ls.push((1, 0));
+    /// ls.push((-1, 0));
+    /// ls.push((0, 1)); // 追加時点で不要
+    /// assert_eq!(ls.stats(), (3, 2, 1));
+    /// ls.push((1, -1)); // (1, 0) が不要になる
+    /// assert_eq!(ls.stats(), (4, 2, 2));
+    /// ```
+    pub fn stats(&self) -> (usize, usize, usize) {
+        let len = self.f.len();
+        (self.pushed, len, self.pushed - len)
+    }
+
+    fn fold(&self, x: I) -> Option<I> {
+        self.fold_line(x).map(|line| x.on_line(line))
+    }
+    fn fold_line(&self, x: I) -> Option<(I, I)> {
         let a = *self.range.range_right(x..).next()?.1;
-        let b = self.f[&a];
-        Some(x.on_line((a, b)))
+        Some((a, self.f[&a]))
     }
-    pub fn inner_len(&self) -> usize { self.f.len() }
 
     fn unused(&self, (a, b): (I, I)) -> bool {
         let (&al, &bl) = match self.f.range(a..).next() {
@@ -252,15 +397,16 @@ impl<I: ChtInt> Debug for LineDebugHelper<I> {
     }
 }
 
-impl<I: ChtInt> Debug for IncrementalLineSet<I> {
+impl<I: ChtInt, E: Envelope<I>> Debug for IncrementalLineSet<I, E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let line = |a, b| LineDebugHelper(E::sign(a), E::sign(b));
         f.debug_map()
             .entries(
                 self.f
                     .iter()
                     .rev()
                     .zip(self.range.range_left(..).rev())
-                    .map(|((&a, &b), (&_, &r))| (LineDebugHelper(a, b), ..=r)),
+                    .map(|((&a, &b), (&_, &r))| (line(a, b), ..=r)),
             )
             .finish()
     }
@@ -273,6 +419,35 @@ pub trait ChtInt: Copy + Ord + Default + Debug {
     fn simplify(self) -> i8;
 }
 
+/// $x-y$ を符号と絶対値の組で返す。
+fn abs_diff(x: i128, y: i128) -> (bool, u128) {
+    if x >= y {
+        (false, x.wrapping_sub(y) as u128)
+    } else {
+        (true, y.wrapping_sub(x) as u128)
+    }
+}
+
+/// $\\lfloor (n\_1-n\_2) / (d\_1-d\_2)\\rfloor$ を $\[\\mathrm{min}, \\mathrm{max}]$
+/// に丸めて返す。ただし $d\_1\\gt d\_2$ とする。
+///
+/// 差や商を `i128` で表せない場合もオーバーフローしない。
+fn floor_div_diff(
+    (n1, n2): (i128, i128),
+    (d1, d2): (i128, i128),
+    (min, max): (i128, i128),
+) -> i128 {
+    let (neg, num) = abs_diff(n1, n2);
+    let (_, den) = abs_diff(d1, d2);
+    let (q, r) = (num / den, num % den);
+    if !neg {
+        if q > max as u128 { max } else { q as i128 }
+    } else {
+        let q = q + (r > 0) as u128;
+        if q > min.unsigned_abs() { min } else { (q as i128).wrapping_neg() }
+    }
+}
+
 macro_rules! impl_cht_int {
     ( $($tt:tt)* ) => { $(
         impl ChtInt for $tt {
@@ -284,7 +459,11 @@ macro_rules! impl_cht_int {
             fn right(self, b: Self, (ar, br): (Self, Self)) -> Self {
                 // a > ar
                 let a = self;
-                (br - b).div_euclid(a - ar)
+                let oo = Self::oo();
+                let bound = ((!oo) as i128, oo as i128);
+                let n = (br as i128, b as i128);
+                let d = (a as i128, ar as i128);
+                floor_div_diff(n, d, bound) as $tt
             }
             fn on_line(self, (a, b): (Self, Self)) -> Self { a * self + b }
             fn simplify(self) -> i8 {
@@ -301,6 +480,68 @@ macro_rules! impl_cht_int {
 
 impl_cht_int! { i8 i16 i32 i64 i128 isize }
 
+/// [`IncrementalLineSet`] で `f64` を扱うためのもの。
+///
+/// [`f64::total_cmp`] に基づいて全順序を入れる。NaN を含む直線は追加しないこと。
+///
+/// # Examples
+/// ```
+/// use nekolib::ds::{IncrementalLineSet, OrdF64};
+///
+/// let mut ls = IncrementalLineSet::new();
+/// ls.push((OrdF64(1.0), OrdF64(0.0)));
+/// ls.push((OrdF64(-1.0), OrdF64(1.0)));
+/// assert_eq!(ls.min(OrdF64(0.25)), Some(OrdF64(0.25)));
+/// assert_eq!(ls.min(OrdF64(0.75)), Some(OrdF64(0.25)));
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct OrdF64(pub f64);
+
+impl PartialEq for OrdF64 {
+    fn eq(&self, other: &Self) -> bool { self.cmp(other).is_eq() }
+}
+
+impl Eq for OrdF64 {}
+
+impl PartialOrd for OrdF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrdF64 {
+    fn cmp(&self, other: &Self) -> Ordering { self.0.total_cmp(&other.0) }
+}
+
+impl Debug for OrdF64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Neg for OrdF64 {
+    type Output = Self;
+    fn neg(self) -> Self { Self(-self.0) }
+}
+
+impl ChtInt for OrdF64 {
+    fn oo() -> Self { Self(f64::INFINITY) }
+    fn right(self, b: Self, (ar, br): (Self, Self)) -> Self {
+        // 実数の範囲では交点そのものが境界となる。
+        // -0.0 と 0.0 が区別されないように 0.0 を足しておく。
+        Self((br.0 - b.0) / (self.0 - ar.0) + 0.0)
+    }
+    fn on_line(self, (a, b): (Self, Self)) -> Self { Self(a.0 * self.0 + b.0) }
+    fn simplify(self) -> i8 {
+        match self.0 {
+            0.0 => 0,
+            1.0 => 1,
+            -1.0 => -1,
+            _ => 2,
+        }
+    }
+}
+
 #[test]
 fn test_simple() {
     let mut ls = IncrementalLineSet::new();
@@ -325,6 +566,85 @@ fn test_simple() {
     }
 }
 
+#[test]
+fn test_simple_max() {
+    let mut ls = IncrementalLineSet::new_upper();
+    assert_eq!(ls.max(1), None);
+
+    let mut f = std::iter::successors(Some(185_i32), |&x| {
+        Some((x * 291 + 748) % 93739)
+    })
+    .map(|x| x % 300 - 150);
+
+    let mut naive = vec![];
+    for _ in 0..5000 {
+        let a = f.next().unwrap();
+        let b = f.next().unwrap();
+        ls.push((a, b));
+        naive.push((a, b));
+        for x in -100..=100 {
+            let expected = naive.iter().map(|&(a, b)| a * x + b).max();
+            let got = ls.max(x);
+            assert_eq!(got, expected);
+        }
+    }
+}
+
+#[test]
+fn test_min_line() {
+    let mut ls = IncrementalLineSet::new();
+    let mut ls_max = IncrementalLineSet::new_upper();
+    assert_eq!(ls.min_line(0), None);
+    assert_eq!(ls_max.max_line(0), None);
+
+    let mut f = std::iter::successors(Some(185_i32), |&x| {
+        Some((x * 291 + 748) % 93739)
+    })
+    .map(|x| x % 30 - 15);
+
+    let mut naive = vec![];
+    for _ in 0..500 {
+        let a = f.next().unwrap();
+        let b = f.next().unwrap();
+        ls.push((a, b));
+        ls_max.push((a, b));
+        naive.push((a, b));
+        for x in -20..=20 {
+            let key = |&(a, b): &(i32, i32)| (a * x + b, -a);
+            let expected = naive.iter().copied().min_by_key(key);
+            assert_eq!(ls.min_line(x), expected);
+
+            let key = |&(a, b): &(i32, i32)| (a * x + b, -a);
+            let expected = naive.iter().copied().max_by_key(key);
+            assert_eq!(ls_max.max_line(x), expected);
+        }
+    }
+
+    // 多くの直線が (0, 0) で交わる場合は、傾きが最大のものを返す
+    let mut ls = IncrementalLineSet::new();
+    let mut ls_max = IncrementalLineSet::new_upper();
+    for a in -10..=10 {
+        ls.push((a, 0));
+        ls_max.push((a, 0));
+    }
+    assert_eq!(ls.min_line(0), Some((10, 0)));
+    assert_eq!(ls.min_line(-1), Some((10, 0)));
+    assert_eq!(ls.min_line(1), Some((-10, 0)));
+    assert_eq!(ls_max.max_line(0), Some((-10, 0)));
+    assert_eq!(ls_max.max_line(-1), Some((-10, 0)));
+    assert_eq!(ls_max.max_line(1), Some((10, 0)));
+
+    // 最小となる区間が x = 0 のみの直線
+    let mut ls = IncrementalLineSet::new();
+    ls.push((0, 0));
+    ls.push((3, 1));
+    ls.push((1, 0));
+    assert_eq!(ls.inner_len(), 3);
+    assert_eq!(ls.min_line(-1), Some((3, 1)));
+    assert_eq!(ls.min_line(0), Some((1, 0)));
+    assert_eq!(ls.min_line(1), Some((0, 0)));
+}
+
 #[test]
 fn test_cross() {
     // 一点でたくさんの直線が交差する場合のテストを書く
@@ -339,6 +659,7 @@ fn test_cross() {
         ls.push((-a, 0));
         assert_eq!(ls.inner_len(), 2);
     }
+    assert_eq!(ls.stats(), (1999, 2, 1997));
 }
 
 #[test]
@@ -359,6 +680,11 @@ fn test_many() {
         ls.push((-a, -a * x + y - a));
         assert_eq!(ls.inner_len(), (2 * x + 1) as usize);
     }
+    // x = 0 で同じ直線を二回追加したもののみ捨てられる
+    assert_eq!(
+        ls.stats(),
+        (2 * (x_max + 1) as usize, 2 * x_max as usize + 1, 1)
+    );
     for x in -x_max..=x_max {
         let y = -x * (x + 1) / 2;
         assert_eq!(ls.min(x), Some(y));
@@ -373,6 +699,7 @@ fn test_frac() {
     ls.push((-5, 6)); // [..., 11, 6, 1, ...]
     ls.push((0, 3)); // [..., 3, 3, 3, ...]
     assert_eq!(ls.inner_len(), 2);
+    assert_eq!(ls.stats(), (3, 2, 1));
 }
 
 #[test]
@@ -387,6 +714,95 @@ fn test_below() {
     assert_eq!(ls.inner_len(), 1);
 }
 
+#[test]
+fn test_overflow_i8() {
+    // 傾きの差や切片の差が i8 に収まらない場合
+    for seed in 0..100 {
+        let mut ls = IncrementalLineSet::<i8>::new();
+        let mut f = std::iter::successors(Some(seed * 7 + 1_i32), |&x| {
+            Some((x * 291 + 748) % 93739)
+        });
+        let mut naive = vec![];
+        for _ in 0..30 {
+            let a = (f.next().unwrap() % 257 - 128) as i8;
+            let b = (f.next().unwrap() % 256 - 128) as i8;
+            if (a as i32).abs() + (b as i32).abs() > 127 {
+                continue;
+            }
+            ls.push((a, b));
+            naive.push((a as i32, b as i32));
+            for x in -1..=1 {
+                let expected = naive.iter().map(|&(a, b)| a * x + b).min();
+                let got = ls.min(x as i8).map(|y| y as i32);
+                assert_eq!(got, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_overflow_i64() {
+    // 傾き・切片が i64 の境界付近の場合
+    let max = i64::MAX;
+    let mut f = std::iter::successors(Some(296_u64), |&x| {
+        Some(x.wrapping_mul(6364136223846793005).wrapping_add(1))
+    });
+    for _ in 0..100 {
+        let mut ls = IncrementalLineSet::<i64>::new();
+        let mut naive = vec![];
+        for _ in 0..50 {
+            // |a| + |b| < 2^63
+            let a = (f.next().unwrap() >> 3) as i64 - max / 4;
+            let b = (f.next().unwrap() >> 2) as i64 - max / 2;
+            ls.push((a, b));
+            naive.push((a as i128, b as i128));
+            for x in -1..=1 {
+                let expected = naive.iter().map(|&(a, b)| a * x + b).min();
+                let got = ls.min(x as i64).map(|y| y as i128);
+                assert_eq!(got, expected);
+            }
+        }
+    }
+
+    let mut ls = IncrementalLineSet::<i64>::new();
+    ls.push((max, 0));
+    ls.push((-max, 0));
+    ls.push((0, -max));
+    ls.push((1, max - 1));
+    ls.push((-1, max - 1));
+    assert_eq!(ls.inner_len(), 3);
+    assert_eq!(ls.min(0), Some(-max));
+    assert_eq!(ls.min(1), Some(-max));
+    assert_eq!(ls.min(-1), Some(-max));
+}
+
+#[test]
+fn test_f64() {
+    let mut ls = IncrementalLineSet::new();
+    let mut f = std::iter::successors(Some(185_i32), |&x| {
+        Some((x * 291 + 748) % 93739)
+    })
+    .map(|x| (x % 3000 - 1500) as f64 / 10.0);
+
+    let mut naive = vec![];
+    for _ in 0..300 {
+        let a = f.next().unwrap();
+        let b = f.next().unwrap();
+        ls.push((OrdF64(a), OrdF64(b)));
+        naive.push((a, b));
+        for x in -100..=100 {
+            let x = x as f64 / 8.0;
+            let expected = naive
+                .iter()
+                .map(|&(a, b)| a * x + b)
+                .min_by(|x, y| x.total_cmp(y))
+                .unwrap();
+            let got = ls.min(OrdF64(x)).unwrap().0;
+            assert!((got - expected).abs() < 1e-9);
+        }
+    }
+}
+
 #[cfg(test)]
 fn test_cf660_f_internal(a: &[i64], expected: i64) {
     let n = a.len();
@@ -0,0 +1,425 @@
+//! 区間の境界の比較。
+
+use std::cmp::Ordering::{self, Equal, Greater, Less};
+use std::fmt::{self, Debug, Display};
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
+use std::ops::{RangeBounds, Sub};
+
+/// 左端としての境界を比較する。
+///
+/// `Unbounded` が最小で、同じ値に対しては `Included(x) < Excluded(x)` とする。
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering::*;
+/// use std::ops::Bound::*;
+///
+/// use nekolib::ds::interval_core::cmp_start;
+///
+/// assert_eq!(cmp_start(&Unbounded, &Included(0)), Less);
+/// assert_eq!(cmp_start(&Included(3), &Excluded(3)), Less);
+/// assert_eq!(cmp_start(&Excluded(3), &Included(4)), Less);
+/// assert_eq!(cmp_start::<i32>(&Unbounded, &Unbounded), Equal);
+/// ```
+pub fn cmp_start<T: Ord>(x: &Bound<T>, y: &Bound<T>) -> Ordering {
+    match (x, y) {
+        (Unbounded, Unbounded) => Equal,
+        (Unbounded, _) => Less,
+        (_, Unbounded) => Greater,
+        (Included(x), Excluded(y)) if x == y => Less,
+        (Excluded(x), Included(y)) if x == y => Greater,
+        (Included(x) | Excluded(x), Included(y) | Excluded(y)) => x.cmp(y),
+    }
+}
+
+/// 右端としての境界を比較する。
+///
+/// `Unbounded` が最大で、同じ値に対しては `Excluded(x) < Included(x)` とする。
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering::*;
+/// use std::ops::Bound::*;
+///
+/// use nekolib::ds::interval_core::cmp_end;
+///
+/// assert_eq!(cmp_end(&Unbounded, &Included(0)), Greater);
+/// assert_eq!(cmp_end(&Excluded(3), &Included(3)), Less);
+/// assert_eq!(cmp_end(&Included(3), &Excluded(4)), Less);
+/// assert_eq!(cmp_end::<i32>(&Unbounded, &Unbounded), Equal);
+/// ```
+pub fn cmp_end<T: Ord>(x: &Bound<T>, y: &Bound<T>) -> Ordering {
+    match (x, y) {
+        (Unbounded, Unbounded) => Equal,
+        (_, Unbounded) => Less,
+        (Unbounded, _) => Greater,
+        (Excluded(x), Included(y)) if x == y => Less,
+        (Included(x), Excluded(y)) if x == y => Greater,
+        (Included(x) | Excluded(x), Included(y) | Excluded(y)) => x.cmp(y),
+    }
+}
+
+/// 境界の開閉を入れ替える。
+///
+/// 区間の右端 `b` に対して、その直後から始まる区間の左端を返す（逆も同様）。
+pub fn toggle_bound<T>(b: Bound<T>) -> Bound<T> {
+    match b {
+        Included(x) => Excluded(x),
+        Excluded(x) => Included(x),
+        Unbounded => Unbounded,
+    }
+}
+
+/// 区間。
+///
+/// 整数のときも、`Excluded(x)` と `Included(x-1)` などは区別され、
+/// あくまで実数の区間であるかのように扱われる。
+///
+/// 順序は左端を [`cmp_start`]、右端を [`cmp_end`] で比較した辞書順で定める。
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Interval<T> {
+    start: Bound<T>,
+    end: Bound<T>,
+}
+
+impl<T: Debug> Debug for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.start {
+            Unbounded => write!(f, "(-oo")?,
+            Included(x) => write!(f, "[{:?}", x)?,
+            Excluded(x) => write!(f, "({:?}", x)?,
+        }
+        write!(f, ", ")?;
+        match &self.end {
+            Excluded(x) => write!(f, "{:?})", x),
+            Included(x) => write!(f, "{:?}]", x),
+            Unbounded => write!(f, "oo)"),
+        }
+    }
+}
+
+/// 区間を数学記法で表示する。
+///
+/// 閉じた端を `[`/`]`、開いた端を `(`/`)`、無限を `-∞`/`∞` で表す。
+///
+/// # Examples
+/// ```
+/// use std::ops::Bound::*;
+///
+/// use nekolib::ds::interval_core::Interval;
+///
+/// assert_eq!(Interval::new(Included(1), Excluded(5)).to_string(), "[1,5)");
+/// assert_eq!(Interval::new(Unbounded, Included(3)).to_string(), "(-∞,3]");
+/// let all = Interval::<i32>::new(Unbounded, Unbounded);
+/// assert_eq!(all.to_string(), "(-∞,∞)");
+/// ```
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.start {
+            Unbounded => write!(f, "(-∞")?,
+            Included(x) => write!(f, "[{}", x)?,
+            Excluded(x) => write!(f, "({}", x)?,
+        }
+        write!(f, ",")?;
+        match &self.end {
+            Excluded(x) => write!(f, "{})", x),
+            Included(x) => write!(f, "{}]", x),
+            Unbounded => write!(f, "∞)"),
+        }
+    }
+}
+
+impl<T: Ord> Ord for Interval<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_start(&self.start, &other.start)
+            .then_with(|| cmp_end(&self.end, &other.end))
+    }
+}
+
+impl<T: Ord> PartialOrd for Interval<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Clone> Interval<T> {
+    pub fn from_bounds(b: impl RangeBounds<T>) -> Self {
+        Self::new(b.start_bound().cloned(), b.end_bound().cloned())
+    }
+}
+
+impl<T> Interval<T> {
+    pub fn new(start: Bound<T>, end: Bound<T>) -> Self { Self { start, end } }
+
+    pub fn start(&self) -> &Bound<T> { &self.start }
+    pub fn end(&self) -> &Bound<T> { &self.end }
+    pub fn into_bounds(self) -> (Bound<T>, Bound<T>) { (self.start, self.end) }
+
+    pub fn inf(&self) -> Option<&T> {
+        match &self.start {
+            Included(x) | Excluded(x) => Some(x),
+            Unbounded => None,
+        }
+    }
+    pub fn sup(&self) -> Option<&T> {
+        match &self.end {
+            Included(x) | Excluded(x) => Some(x),
+            Unbounded => None,
+        }
+    }
+}
+
+impl<T: Ord> Interval<T> {
+    pub fn is_empty(&self) -> bool {
+        match (&self.start, &self.end) {
+            (Unbounded, _) | (_, Unbounded) => false,
+            (Included(x), Included(y)) => x > y,
+            _ => self.inf().unwrap() >= self.sup().unwrap(),
+        }
+    }
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (left, right) =
+            if self < other { (self, other) } else { (other, self) };
+        match (&right.start, &left.end) {
+            (Unbounded, _) | (_, Unbounded) => true,
+            (Included(x), Included(y)) => x <= y,
+            _ => right.inf().unwrap() < left.sup().unwrap(),
+        }
+    }
+    pub fn is_connected_with(&self, other: &Self) -> bool {
+        let (left, right) =
+            if self < other { (self, other) } else { (other, self) };
+        match (&right.start, &left.end) {
+            (Unbounded, _) | (_, Unbounded) => true,
+            (Excluded(x), Excluded(y)) => x < y,
+            _ => right.inf().unwrap() <= left.sup().unwrap(),
+        }
+    }
+    /// `x` を含むかを返す。
+    ///
+    /// # Examples
+    /// ```
+    /// use std::ops::Bound::*;
+    ///
+    /// use nekolib::ds::interval_core::Interval;
+    ///
+    /// let it = Interval::new(Included(1), Excluded(3));
+    /// assert!(!it.contains(&0));
+    /// assert!(it.contains(&1));
+    /// assert!(it.contains(&2));
+    /// assert!(!it.contains(&3));
+    /// ```
+    pub fn contains(&self, x: &T) -> bool {
+        let left = match &self.start {
+            Included(s) => s <= x,
+            Excluded(s) => s < x,
+            Unbounded => true,
+        };
+        let right = match &self.end {
+            Included(e) => x <= e,
+            Excluded(e) => x < e,
+            Unbounded => true,
+        };
+        left && right
+    }
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        other.is_superset_of(self)
+    }
+    pub fn is_superset_of(&self, other: &Self) -> bool {
+        // [------- self -------]
+        //    [--- other ---]
+        if other.is_empty() {
+            return true;
+        }
+        if self.is_empty() {
+            return false;
+        }
+        cmp_start(&self.start, &other.start) != Greater
+            && cmp_end(&other.end, &self.end) != Greater
+    }
+
+    pub fn intersection(self, other: Self) -> Option<Interval<T>> {
+        let (left, right) =
+            if self < other { (self, other) } else { (other, self) };
+        let end = if cmp_end(&left.end, &right.end) == Greater {
+            right.end
+        } else {
+            left.end
+        };
+        Some(Interval { start: right.start, end }).filter(|it| !it.is_empty())
+    }
+    pub fn connection(self, other: Self) -> Option<Interval<T>> {
+        let (left, right) =
+            if self < other { (self, other) } else { (other, self) };
+        if !left.is_connected_with(&right) {
+            return None;
+        }
+        let end = if cmp_end(&left.end, &right.end) == Less {
+            right.end
+        } else {
+            left.end
+        };
+        Some(Interval { start: left.start, end })
+    }
+}
+
+impl<T: Ord + Clone + Sub<Output = T>> Interval<T> {
+    /// 区間の長さを返す。
+    ///
+    /// 端点の開閉によらず、右端と左端の差を返す。いずれかの端点が無限のときは
+    /// `None` を返す。空区間に対しては、左端が右端より大きい場合も含めて $0$ を返す。
+    ///
+    /// # Examples
+    /// ```
+    /// use std::ops::Bound::*;
+    ///
+    /// use nekolib::ds::interval_core::Interval;
+    ///
+    /// assert_eq!(Interval::new(Included(1), Excluded(4)).measure(), Some(3));
+    /// assert_eq!(Interval::new(Excluded(1), Included(4)).measure(), Some(3));
+    /// assert_eq!(Interval::new(Included(4), Included(1)).measure(), Some(0));
+    /// assert_eq!(Interval::new(Unbounded, Included(1)).measure(), None);
+    /// ```
+    pub fn measure(&self) -> Option<T> {
+        let (inf, sup) = (self.inf()?, self.sup()?);
+        if inf <= sup {
+            Some(sup.clone() - inf.clone())
+        } else {
+            Some(inf.clone() - inf.clone())
+        }
+    }
+}
+
+impl<T: Ord + Clone> Interval<T> {
+    /// 共通部分と、`self` から `other` を除いた部分を返す。
+    pub fn intersection_minus(
+        self,
+        other: Self,
+    ) -> (Option<Interval<T>>, Vec<Interval<T>>) {
+        if !self.intersects(&other) {
+            return (None, vec![self]);
+        }
+        if self.is_subset_of(&other) {
+            return (Some(self), vec![]);
+        }
+        if self.is_superset_of(&other) {
+            // [-----   self   ------]
+            //     [-- other ---]
+            // ======================
+            // [mi][intersection][nus]
+            let isx = other.clone();
+            let Interval { start: ls, end: le } = self;
+            let Interval { start: rs, end: re } = other;
+            let minus_left = match rs {
+                Unbounded => None,
+                rs => Some(Interval { start: ls, end: toggle_bound(rs) }),
+            }
+            .filter(|it| !it.is_empty());
+            let minus_right = match re {
+                Unbounded => None,
+                re => Some(Interval { start: toggle_bound(re), end: le }),
+            }
+            .filter(|it| !it.is_empty());
+
+            let isx = if isx.is_empty() { None } else { Some(isx) };
+            let minus: Vec<_> =
+                minus_left.into_iter().chain(minus_right).collect();
+            return (isx, minus);
+        }
+        let swap = self > other;
+        let (left, right) = if swap { (other, self) } else { (self, other) };
+        // [--- self ---]
+        //          [--- other ---]
+        // ========================
+        // [ minus ][isx]
+        let Interval { start: ls, end: le } = left;
+        let Interval { start: rs, end: re } = right;
+        let minus = if swap {
+            Interval { start: toggle_bound(le.clone()), end: re }
+        } else {
+            Interval { start: ls, end: toggle_bound(rs.clone()) }
+        };
+        let isx = Interval { start: rs, end: le };
+        (Some(isx), vec![minus])
+    }
+}
+
+#[test]
+fn touching_bounds() {
+    let it = |s: Bound<i32>, e: Bound<i32>| Interval::new(s, e);
+
+    // [1, 3] と (3, 5) は交わらないが連結
+    let l = it(Included(1), Included(3));
+    let r = it(Excluded(3), Excluded(5));
+    assert!(l < r);
+    assert!(!l.intersects(&r));
+    assert!(l.is_connected_with(&r));
+    assert_eq!(l.connection(r), Some(it(Included(1), Excluded(5))));
+
+    // [1, 3) と (3, 5) は連結でない
+    let l = it(Included(1), Excluded(3));
+    assert!(!l.is_connected_with(&r));
+    assert_eq!(l.connection(r), None);
+
+    // [1, 3] と [3, 5) は 3 で交わる
+    let r = it(Included(3), Excluded(5));
+    assert!(l < r);
+    assert!(it(Included(1), Included(3)).intersects(&r));
+    assert_eq!(
+        it(Included(1), Included(3)).intersection(r),
+        Some(it(Included(3), Included(3)))
+    );
+
+    // 一方が他方を含む場合
+    let outer = it(Unbounded, Unbounded);
+    let inner = it(Excluded(4), Included(6));
+    assert_eq!(outer.intersection(inner), Some(inner));
+    assert_eq!(inner.intersection(outer), Some(inner));
+
+    assert!(it(Excluded(3), Excluded(3)).is_empty());
+    assert!(it(Included(3), Excluded(3)).is_empty());
+    assert!(!it(Included(3), Included(3)).is_empty());
+    assert!(r.is_superset_of(&it(Excluded(4), Excluded(4))));
+    assert!(!it(Excluded(3), Excluded(5)).is_superset_of(&r));
+}
+
+#[test]
+fn contains_at_bounds() {
+    let it = |s: Bound<i32>, e: Bound<i32>| Interval::new(s, e);
+
+    let closed = it(Included(1), Included(3));
+    let open = it(Excluded(1), Excluded(3));
+    let left_open = it(Excluded(1), Included(3));
+    let right_open = it(Included(1), Excluded(3));
+    for (x, expected) in [
+        (0, [false, false, false, false]),
+        (1, [true, false, false, true]),
+        (2, [true, true, true, true]),
+        (3, [true, false, true, false]),
+        (4, [false, false, false, false]),
+    ] {
+        let actual =
+            [closed, open, left_open, right_open].map(|it| it.contains(&x));
+        assert_eq!(actual, expected, "x = {}", x);
+    }
+
+    // 1 点のみの区間と空区間
+    assert!(it(Included(2), Included(2)).contains(&2));
+    assert!(!it(Included(2), Excluded(2)).contains(&2));
+    assert!(!it(Excluded(2), Excluded(2)).contains(&2));
+    assert!(!it(Included(3), Included(1)).contains(&2));
+
+    // 無限の端点
+    assert!(it(Unbounded, Included(3)).contains(&i32::MIN));
+    assert!(!it(Unbounded, Excluded(3)).contains(&3));
+    assert!(it(Included(3), Unbounded).contains(&i32::MAX));
+    assert!(!it(Excluded(3), Unbounded).contains(&3));
+    assert!(it(Unbounded, Unbounded).contains(&0));
+
+    assert_eq!(closed.measure(), Some(2));
+    assert_eq!(open.measure(), Some(2));
+    assert_eq!(it(Excluded(2), Excluded(2)).measure(), Some(0));
+    assert_eq!(it(Included(3), Included(1)).measure(), Some(0));
+    assert_eq!(it(Included(3), Unbounded).measure(), None);
+    assert_eq!(it(Unbounded, Unbounded).measure(), None);
+}
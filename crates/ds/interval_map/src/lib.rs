@@ -1,272 +1,13 @@
 //! 区間から値への対応づけ。
 
-use std::cmp::Ordering::{self, Equal, Greater, Less};
+use std::cmp::Ordering::Greater;
 use std::collections::BTreeMap;
 use std::fmt::{self, Debug};
-use std::ops::Bound::{Excluded, Included, Unbounded};
-use std::ops::RangeBounds;
-
-#[derive(Clone, Copy, Eq, PartialEq)]
-enum Left<T> {
-    NegInfinity,
-    Closed(T),
-    Open(T),
-}
-
-#[derive(Clone, Copy, Eq, PartialEq)]
-enum Right<T> {
-    Open(T),
-    Closed(T),
-    PosInfinity,
-}
-
-impl<T: Debug> Debug for Left<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Left::NegInfinity => write!(f, "(-oo"),
-            Left::Closed(x) => write!(f, "[{:?}", x),
-            Left::Open(x) => write!(f, "({:?}", x),
-        }
-    }
-}
-
-impl<T: Debug> Debug for Right<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Right::Open(x) => write!(f, "{:?})", x),
-            Right::Closed(x) => write!(f, "{:?}]", x),
-            Right::PosInfinity => write!(f, "oo)"),
-        }
-    }
-}
-
-impl<T> Left<T> {
-    pub fn inner(&self) -> Option<&T> {
-        match self {
-            Left::Open(x) | Left::Closed(x) => Some(x),
-            Left::NegInfinity => None,
-        }
-    }
-}
-
-impl<T> Right<T> {
-    pub fn inner(&self) -> Option<&T> {
-        match self {
-            Right::PosInfinity => None,
-            Right::Closed(x) | Right::Open(x) => Some(x),
-        }
-    }
-}
-
-impl<T: Ord> Ord for Left<T> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        use Left::{Closed, NegInfinity, Open};
-        match (self, other) {
-            (NegInfinity, NegInfinity) => Equal,
-            (NegInfinity, _) => Less,
-            (_, NegInfinity) => Greater,
-            (Closed(x), Open(y)) if x == y => Less,
-            (Open(x), Closed(y)) if x == y => Greater,
-            _ => self.inner().unwrap().cmp(other.inner().unwrap()),
-        }
-    }
-}
-
-impl<T: Ord> PartialOrd for Left<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+use std::ops::Bound::Unbounded;
+use std::ops::{Add, Mul, RangeBounds};
 
-impl<T: Ord> Ord for Right<T> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        use Right::{Closed, Open, PosInfinity};
-        match (self, other) {
-            (PosInfinity, PosInfinity) => Equal,
-            (_, PosInfinity) => Less,
-            (PosInfinity, _) => Greater,
-            (Open(x), Closed(y)) if x == y => Less,
-            (Closed(x), Open(y)) if x == y => Greater,
-            _ => self.inner().unwrap().cmp(other.inner().unwrap()),
-        }
-    }
-}
-
-impl<T: Ord> PartialOrd for Right<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-#[derive(Clone, Copy, Eq, PartialEq)]
-pub struct Interval<T> {
-    left: Left<T>,
-    right: Right<T>,
-}
-
-impl<T: Debug> Debug for Interval<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}, {:?}", self.left, self.right)
-    }
-}
-
-impl<T: Ord> Ord for Interval<T> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.left.cmp(&other.left).then_with(|| self.right.cmp(&other.right))
-    }
-}
-
-impl<T: Ord> PartialOrd for Interval<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl<T: Ord + Clone> Interval<T> {
-    pub fn from_bounds(b: impl RangeBounds<T>) -> Self {
-        let left = match b.start_bound() {
-            Unbounded => Left::NegInfinity,
-            Included(x) => Left::Closed(x.clone()),
-            Excluded(x) => Left::Open(x.clone()),
-        };
-        let right = match b.end_bound() {
-            Excluded(x) => Right::Open(x.clone()),
-            Included(x) => Right::Closed(x.clone()),
-            Unbounded => Right::PosInfinity,
-        };
-        Self { left, right }
-    }
-}
-
-impl<T: Ord> Interval<T> {
-    pub fn inf(&self) -> Option<&T> { self.left.inner() }
-    pub fn sup(&self) -> Option<&T> { self.right.inner() }
-
-    pub fn is_empty(&self) -> bool {
-        match (&self.left, &self.right) {
-            (Left::NegInfinity, _) => false,
-            (_, Right::PosInfinity) => false,
-            (Left::Closed(x), Right::Closed(y)) => x > y,
-            _ => self.inf().unwrap() >= self.sup().unwrap(),
-        }
-    }
-    pub fn intersects(&self, other: &Self) -> bool {
-        let (left, right) =
-            if self < other { (self, other) } else { (other, self) };
-        match (&right.left, &left.right) {
-            (Left::NegInfinity, _) => true,
-            (_, Right::PosInfinity) => true,
-            (Left::Closed(x), Right::Closed(y)) => x <= y,
-            _ => right.inf().unwrap() < left.sup().unwrap(),
-        }
-    }
-    pub fn is_connected_with(&self, other: &Self) -> bool {
-        let (left, right) =
-            if self < other { (self, other) } else { (other, self) };
-        match (&right.left, &left.right) {
-            (Left::NegInfinity, _) => true,
-            (_, Right::PosInfinity) => true,
-            (Left::Open(x), Right::Open(y)) => x < y,
-            _ => right.inf().unwrap() <= left.sup().unwrap(),
-        }
-    }
-    pub fn is_subset_of(&self, other: &Self) -> bool {
-        //      [--- self ---]
-        // [------- other -------]
-        other.left <= self.left && self.right <= other.right
-    }
-    pub fn is_superset_of(&self, other: &Self) -> bool {
-        // [------- self -------]
-        //    [--- other ---]
-        self.left <= other.left && other.right <= self.right
-    }
-
-    pub fn intersection(self, other: Self) -> Option<Interval<T>> {
-        let (left, right) =
-            if self < other { (self, other) } else { (other, self) };
-        Some(Interval { left: right.left, right: left.right })
-            .filter(|it| !it.is_empty())
-    }
-    pub fn connection(self, other: Self) -> Option<Interval<T>> {
-        let (left, right) =
-            if self < other { (self, other) } else { (other, self) };
-        if !left.is_connected_with(&right) {
-            return None;
-        }
-        Some(Interval { left: left.left, right: right.right })
-    }
-}
-
-impl<T: Ord + Clone> Interval<T> {
-    pub fn intersection_minus(
-        self,
-        other: Self,
-    ) -> (Option<Interval<T>>, Vec<Interval<T>>) {
-        if !self.intersects(&other) {
-            return (None, vec![self]);
-        }
-        if self.is_subset_of(&other) {
-            return (Some(self), vec![]);
-        }
-        if self.is_superset_of(&other) {
-            // [-----   self   ------]
-            //     [-- other ---]
-            // ======================
-            // [mi][intersection][nus]
-            let isx = other.clone();
-            let Interval { left: ll, right: lr } = self;
-            let Interval { left: rl, right: rr } = other;
-            let minus_left = {
-                let tmp = match rl {
-                    Left::NegInfinity => None,
-                    Left::Closed(x) => Some(Right::Open(x)),
-                    Left::Open(x) => Some(Right::Closed(x)),
-                };
-                tmp.map(|right| Interval { left: ll, right })
-                    .filter(|it| !it.is_empty())
-            };
-            let minus_right = {
-                let tmp = match rr {
-                    Right::Open(x) => Some(Left::Closed(x)),
-                    Right::Closed(x) => Some(Left::Open(x)),
-                    Right::PosInfinity => None,
-                };
-                tmp.map(|left| Interval { left, right: lr })
-                    .filter(|it| !it.is_empty())
-            };
-
-            let isx = if isx.is_empty() { None } else { Some(isx) };
-            let minus: Vec<_> =
-                minus_left.into_iter().chain(minus_right).collect();
-            return (isx, minus);
-        }
-        let swap = self > other;
-        let (left, right) = if swap { (other, self) } else { (self, other) };
-        // [--- self ---]
-        //          [--- other ---]
-        // ========================
-        // [ minus ][isx]
-        let Interval { left: ll, right: lr } = left;
-        let Interval { left: rl, right: rr } = right;
-        let minus = if swap {
-            let left = match lr.clone() {
-                Right::Open(x) => Left::Closed(x),
-                Right::Closed(x) => Left::Open(x),
-                Right::PosInfinity => unreachable!(),
-            };
-            Interval { left, right: rr }
-        } else {
-            let right = match rl.clone() {
-                Left::NegInfinity => unreachable!(),
-                Left::Closed(x) => Right::Open(x),
-                Left::Open(x) => Right::Closed(x),
-            };
-            Interval { left: ll, right }
-        };
-        let isx = Interval { left: rl, right: lr };
-        (Some(isx), vec![minus])
-    }
-}
+pub use interval_core::Interval;
+use interval_core::{cmp_start, toggle_bound};
 
 /// 区間から値への対応づけ。
 ///
@@ -375,14 +116,14 @@ impl<K: Ord + Clone, V: Eq + Clone> IntervalMap<K, V> {
             if vi == v && it.is_connected_with(ki) {
                 let ki = ki.clone();
                 self.inner.remove(&ki);
-                it.right = ki.right;
+                *it = it.clone().connection(ki).unwrap();
             }
         }
         if let Some((ki, vi)) = self.inner.range(..&*it).next_back() {
             if vi == v && it.is_connected_with(ki) {
                 let ki = ki.clone();
                 self.inner.remove(&ki);
-                it.left = ki.left;
+                *it = it.clone().connection(ki).unwrap();
             }
         }
     }
@@ -402,6 +143,106 @@ impl<K: Ord + Clone, V: Eq + Clone> IntervalMap<K, V> {
             .find(|(ki, _)| ki.is_superset_of(&it))
     }
 
+    /// $k$ を含む $T\\subseteq S$ があれば、その $T$ および対応する値を返す。
+    ///
+    /// `superset_of(k..=k)` と同じであり、開区間の端点は含まれないものとして扱う。
+    ///
+    /// # Complexity
+    /// $O(\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::ops::Bound::{Excluded, Included};
+    ///
+    /// use nekolib::ds::IntervalMap;
+    ///
+    /// let mut map = IntervalMap::new();
+    /// map.insert(0..2, 'a');
+    /// map.insert((Excluded(2), Included(4)), 'b');
+    ///
+    /// assert_eq!(map.get(&1).map(|(_, &v)| v), Some('a'));
+    /// assert_eq!(map.get(&2), None);
+    /// assert_eq!(map.get(&4).map(|(_, &v)| v), Some('b'));
+    /// assert_eq!(map.get(&5), None);
+    /// ```
+    pub fn get(&self, k: &K) -> Option<(&Interval<K>, &V)> {
+        self.superset_of(k.clone()..=k.clone())
+    }
+
+    /// 区間 `b` のうち、どの $T\\subseteq S$ にも含まれない部分を昇順に返す。
+    ///
+    /// 返す区間は極大であり、隣接する区間が連結している箇所は隙間とみなさない。
+    /// たとえば $[0, 2]$ と $(2, 4)$ の間に隙間はないが、$[0, 2)$ と $(2, 4)$
+    /// の間には $[2, 2]$ の隙間がある。
+    ///
+    /// # Complexity
+    /// 返す区間の個数を $k$ として $O(\\log(n)+k)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::ops::Bound::{Excluded, Included};
+    ///
+    /// use nekolib::ds::interval_map::{Interval, IntervalMap};
+    ///
+    /// let mut map = IntervalMap::new();
+    /// map.insert(0..2, 'a');
+    /// map.insert(2..4, 'b');
+    /// map.insert((Excluded(4), Included(6)), 'c');
+    /// map.insert(8..10, 'd');
+    ///
+    /// let gaps: Vec<_> = map.gaps(-1..12).collect();
+    /// assert_eq!(gaps, [
+    ///     Interval::from_bounds(-1..0),
+    ///     Interval::from_bounds(4..=4),
+    ///     Interval::from_bounds((Excluded(6), Excluded(8))),
+    ///     Interval::from_bounds(10..12),
+    /// ]);
+    /// assert_eq!(map.gaps(0..4).next(), None);
+    /// ```
+    pub fn gaps<B: RangeBounds<K>>(
+        &self,
+        b: B,
+    ) -> impl Iterator<Item = Interval<K>> {
+        let it = Interval::from_bounds(b);
+        let mut res = vec![];
+        if it.is_empty() {
+            return res.into_iter();
+        }
+        let (start, end) = it.clone().into_bounds();
+        let mut cur = Some(start);
+        let covering = (self.inner.range(..&it).next_back().into_iter())
+            .filter(|(ki, _)| ki.intersects(&it))
+            .chain(
+                self.inner
+                    .range(&it..)
+                    .take_while(|(ki, _)| ki.intersects(&it)),
+            );
+        for (ki, _) in covering {
+            let s = cur.take().unwrap();
+            if *ki.start() != Unbounded {
+                let gap =
+                    Interval::new(s.clone(), toggle_bound(ki.start().clone()));
+                if !gap.is_empty() {
+                    res.push(gap);
+                }
+            }
+            cur = match ki.end() {
+                Unbounded => break,
+                e => {
+                    let e = toggle_bound(e.clone());
+                    Some(if cmp_start(&s, &e) == Greater { s } else { e })
+                }
+            };
+        }
+        if let Some(s) = cur {
+            let gap = Interval::new(s, end);
+            if !gap.is_empty() {
+                res.push(gap);
+            }
+        }
+        res.into_iter()
+    }
+
     pub fn iter(
         &self,
     ) -> impl Iterator<Item = (&Interval<K>, &V)> + DoubleEndedIterator + '_
@@ -410,6 +251,48 @@ impl<K: Ord + Clone, V: Eq + Clone> IntervalMap<K, V> {
     }
 }
 
+impl<K> IntervalMap<K, (K, K)>
+where
+    K: Ord + Clone + Add<Output = K> + Mul<Output = K>,
+{
+    /// 区間ごとに一次関数 $ax+b$ を $(a, b)$ として持たせたものを区分線形関数とみなし、
+    /// $x$ での値を返す。
+    ///
+    /// $x$ を含む区間 $T\\subseteq S$ があり、その値が $(a, b)$ であれば
+    /// $ax+b$ を返し、なければ `None` を返す。
+    ///
+    /// # Notes
+    /// 区間の境界での連続性は仮定しない。隣接する区間の一次関数が境界で異なる値を取る
+    /// 場合（不連続点）、境界点での値はその点を含む方の区間の一次関数で計算される。
+    /// たとえば $[0, 2)$ に $(1, 0)$、$[2, 4)$ に $(0, 5)$ を持たせると、$x=2$
+    /// での値は $5$ となる。
+    /// 隣接する区間に同じ $(a, b)$ を持たせると一つの区間にまとめられるが、
+    /// 関数としては変わらない。
+    ///
+    /// # Complexity
+    /// $O(\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::IntervalMap;
+    ///
+    /// // f(x) = |x - 3| on [0, 10]
+    /// let mut f = IntervalMap::new();
+    /// f.insert(0..3, (-1, 3));
+    /// f.insert(3..=10, (1, -3));
+    ///
+    /// assert_eq!(f.interpolate_at(&0), Some(3));
+    /// assert_eq!(f.interpolate_at(&3), Some(0));
+    /// assert_eq!(f.interpolate_at(&7), Some(4));
+    /// assert_eq!(f.interpolate_at(&11), None);
+    /// assert_eq!(f.interpolate_at(&-1), None);
+    /// ```
+    pub fn interpolate_at(&self, x: &K) -> Option<K> {
+        let (_, (a, b)) = self.get(x)?;
+        Some(a.clone() * x.clone() + b.clone())
+    }
+}
+
 impl<'a, K: Ord, V: Eq> IntoIterator for &'a IntervalMap<K, V> {
     type Item = (&'a Interval<K>, &'a V);
     type IntoIter = std::collections::btree_map::Iter<'a, Interval<K>, V>;
@@ -427,3 +310,143 @@ impl<K: Ord + Debug, V: Debug> Debug for IntervalMap<K, V> {
         fmt.debug_map().entries(self.inner.iter()).finish()
     }
 }
+
+#[test]
+fn test_interpolate_at() {
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+
+    // 不連続点を含む区分線形関数
+    let mut f = IntervalMap::new();
+    f.insert(0..2, (1, 0));
+    f.insert(2..4, (0, 5));
+    f.insert((Excluded(4), Included(6)), (-2, 20));
+    let expected = [
+        None,
+        Some(0),
+        Some(1),
+        Some(5),
+        Some(5),
+        None,
+        Some(10),
+        Some(8),
+        None,
+    ];
+    for (x, &e) in (-1..=7).zip(&expected) {
+        assert_eq!(f.interpolate_at(&x), e);
+    }
+
+    // 上書きと、同じ一次関数の区間の連結
+    f.insert(1..3, (2, -1));
+    f.insert(..0, (1, 0));
+    f.insert((Excluded(6), Unbounded), (-2, 20));
+    assert_eq!(f.iter().count(), 4);
+    let expected: Vec<_> = (-5..=10)
+        .map(|x: i64| match x {
+            _ if x < 1 => Some(x),
+            1..=2 => Some(2 * x - 1),
+            3 => Some(5),
+            4 => None,
+            _ => Some(20 - 2 * x),
+        })
+        .collect();
+    let actual: Vec<_> = (-5..=10).map(|x| f.interpolate_at(&x)).collect();
+    assert_eq!(actual, expected);
+    assert_eq!(f.interpolate_at(&i64::MIN), Some(i64::MIN));
+
+    let empty = IntervalMap::<i64, (i64, i64)>::new();
+    assert_eq!(empty.interpolate_at(&0), None);
+}
+
+#[test]
+fn test_get() {
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+
+    let mut map = IntervalMap::new();
+    assert_eq!(map.get(&0), None);
+
+    map.insert(..0, 'a');
+    map.insert(0..2, 'b');
+    map.insert((Included(2), Included(4)), 'c');
+    map.insert((Excluded(4), Excluded(6)), 'd');
+    map.insert((Excluded(7), Unbounded), 'e');
+
+    let get = |k| map.get(&k).map(|(it, &v)| (*it, v));
+    let it = |b: (_, _)| Interval::from_bounds(b);
+    let expected = [
+        (-1, Some((it((Unbounded, Excluded(0))), 'a'))),
+        (0, Some((it((Included(0), Excluded(2))), 'b'))),
+        (1, Some((it((Included(0), Excluded(2))), 'b'))),
+        (2, Some((it((Included(2), Included(4))), 'c'))),
+        (4, Some((it((Included(2), Included(4))), 'c'))),
+        (5, Some((it((Excluded(4), Excluded(6))), 'd'))),
+        (6, None),
+        (7, None),
+        (8, Some((it((Excluded(7), Unbounded)), 'e'))),
+    ];
+    for &(k, e) in &expected {
+        assert_eq!(get(k), e);
+    }
+    for k in -5..=10 {
+        let e = map.superset_of(k..=k).map(|(it, &v)| (*it, v));
+        assert_eq!(get(k), e);
+    }
+}
+
+#[test]
+fn test_gaps() {
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+
+    let it = |b: (_, _)| Interval::from_bounds(b);
+
+    // 空マップ
+    let mut map = IntervalMap::new();
+    assert_eq!(
+        map.gaps(0..5).collect::<Vec<_>>(),
+        [it((Included(0), Excluded(5)))]
+    );
+    assert_eq!(map.gaps(..).collect::<Vec<_>>(), [it((Unbounded, Unbounded))]);
+    assert_eq!(map.gaps(3..3).next(), None);
+
+    // 連結している隣接区間は隙間にならない
+    map.insert(0..=2, 'a');
+    map.insert((Excluded(2), Excluded(4)), 'b');
+    assert_eq!(map.gaps(0..4).next(), None);
+    assert_eq!(map.gaps(1..3).next(), None);
+    assert_eq!(
+        map.gaps(-2..6).collect::<Vec<_>>(),
+        [it((Included(-2), Excluded(0))), it((Included(4), Excluded(6)))]
+    );
+
+    // 開区間の端点のみの隙間
+    map.insert((Excluded(4), Excluded(6)), 'c');
+    assert_eq!(
+        map.gaps(0..=6).collect::<Vec<_>>(),
+        [it((Included(4), Included(4))), it((Included(6), Included(6)))]
+    );
+    assert_eq!(map.gaps((Excluded(4), Excluded(6))).next(), None);
+
+    // 非有界な区間による完全被覆
+    map.insert(..0, 'd');
+    map.insert(6.., 'e');
+    assert_eq!(
+        map.gaps(..).collect::<Vec<_>>(),
+        [it((Included(4), Included(4)))]
+    );
+    map.insert(4..=4, 'f');
+    assert_eq!(map.gaps(..).next(), None);
+
+    // 区間外の区間は無視される
+    let mut map = IntervalMap::new();
+    map.insert(0..2, 'a');
+    map.insert(5..7, 'b');
+    map.insert(10..12, 'c');
+    assert_eq!(
+        map.gaps(1..11).collect::<Vec<_>>(),
+        [it((Included(2), Excluded(5))), it((Included(7), Excluded(10)))]
+    );
+    assert_eq!(
+        map.gaps(3..4).collect::<Vec<_>>(),
+        [it((Included(3), Excluded(4)))]
+    );
+    assert_eq!(map.gaps(5..7).next(), None);
+}
@@ -1,147 +1,15 @@
 //! 区間の集合。
 
-use std::cmp::Ordering::{self, *};
+use std::cmp::Ordering::Less;
 use std::collections::BTreeSet;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display};
+use std::iter::Sum;
 use std::ops::{
     Bound::{self, *},
-    RangeBounds,
+    RangeBounds, Sub,
 };
 
-/// 区間の集合。
-///
-/// # Notes
-/// 整数のとき、Excluded(x) と Included(x-1) などの扱いに注意。
-/// あくまで実数の区間であるかのように扱われる。
-#[derive(Clone, Debug, Eq)]
-struct Interval<T: Ord>(Bound<T>, Bound<T>);
-
-impl<T: Clone + Ord> From<(Bound<&T>, Bound<&T>)> for Interval<T> {
-    fn from(r: (Bound<&T>, Bound<&T>)) -> Interval<T> {
-        let s = match r.0 {
-            Included(lo) => Included(lo.clone()),
-            Excluded(lo) => Excluded(lo.clone()),
-            Unbounded => Unbounded,
-        };
-        let e = match r.1 {
-            Included(hi) => Included(hi.clone()),
-            Excluded(hi) => Excluded(hi.clone()),
-            Unbounded => Unbounded,
-        };
-        Interval(s, e)
-    }
-}
-
-impl<T: Ord> Interval<T> {
-    fn is_empty(&self) -> bool {
-        match (&self.0, &self.1) {
-            (Unbounded, _) | (_, Unbounded) => false,
-            (Included(lo), Included(hi)) => !(lo <= hi),
-            (Included(lo), Excluded(hi))
-            | (Excluded(lo), Included(hi))
-            | (Excluded(lo), Excluded(hi)) => !(lo < hi),
-        }
-    }
-    fn is_superset(&self, other: &Self) -> bool {
-        if other.is_empty() {
-            return true;
-        }
-        if self.is_empty() {
-            return false;
-        }
-
-        // self.0 <= other.0
-        match (&self.0, &other.0) {
-            (Unbounded, _) => {}
-            (_, Unbounded) => return false,
-            (Excluded(lhs), Included(rhs)) if lhs == rhs => return false,
-            (Included(lhs), Included(rhs))
-            | (Included(lhs), Excluded(rhs))
-            | (Excluded(lhs), Included(rhs))
-            | (Excluded(lhs), Excluded(rhs))
-                if lhs > rhs =>
-            {
-                return false
-            }
-            _ => {}
-        }
-
-        // other.1 <= self.1
-        match (&self.1, &other.1) {
-            (Unbounded, _) => true,
-            (_, Unbounded) => false,
-            (Excluded(lhs), Included(rhs)) => lhs > rhs,
-            (Included(lhs), Included(rhs))
-            | (Included(lhs), Excluded(rhs))
-            | (Excluded(lhs), Excluded(rhs)) => lhs >= rhs,
-        }
-    }
-    fn touches(&self, other: &Self) -> bool {
-        let (left, right) = match self.cmp(&other) {
-            Less => (&self, &other),
-            Equal => return true,
-            Greater => (&other, &self),
-        };
-        match (&left.1, &right.0) {
-            (Unbounded, _) | (_, Unbounded) => true,
-            (Included(le), Included(rs))
-            | (Included(le), Excluded(rs))
-            | (Excluded(le), Included(rs)) => rs <= le,
-            (Excluded(le), Excluded(rs)) => rs < le,
-        }
-    }
-}
-
-fn toggle_bound<T: Ord>(b: Bound<T>) -> Bound<T> {
-    match b {
-        Included(x) => Excluded(x),
-        Excluded(x) => Included(x),
-        Unbounded => Unbounded,
-    }
-}
-
-impl<T: Ord> Ord for Interval<T> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        if self.is_empty() && other.is_empty() {
-            return Equal;
-        }
-        if self.0 != other.0 {
-            return match (&self.0, &other.0) {
-                (Unbounded, _) => Less,
-                (_, Unbounded) => Greater,
-                (Included(lhs), Excluded(rhs)) if lhs == rhs => Less,
-                (Excluded(lhs), Included(rhs)) if lhs == rhs => Greater,
-                (Included(lhs), Included(rhs))
-                | (Included(lhs), Excluded(rhs))
-                | (Excluded(lhs), Included(rhs))
-                | (Excluded(lhs), Excluded(rhs)) => lhs.cmp(rhs),
-            };
-        }
-        if self.1 != other.1 {
-            return match (&self.1, &other.1) {
-                (_, Unbounded) => Less,
-                (Unbounded, _) => Greater,
-                (Excluded(lhs), Included(rhs)) if lhs == rhs => Less,
-                (Included(lhs), Excluded(rhs)) if lhs == rhs => Greater,
-                (Included(lhs), Included(rhs))
-                | (Included(lhs), Excluded(rhs))
-                | (Excluded(lhs), Included(rhs))
-                | (Excluded(lhs), Excluded(rhs)) => lhs.cmp(rhs),
-            };
-        }
-        Equal
-    }
-}
-
-impl<T: Ord> PartialOrd for Interval<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl<T: Ord> PartialEq for Interval<T> {
-    fn eq(&self, other: &Self) -> bool { self.cmp(other) == Equal }
-}
+use interval_core::{cmp_end, toggle_bound, Interval};
 
 /// 区間の集合。
 ///
@@ -158,25 +26,73 @@ impl<T: Clone + Debug + Ord> IntervalSet<T> {
     /// 集合が空であれば `true` を返す。
     pub fn is_empty(&self) -> bool { self.buf.is_empty() }
 
+    /// 集合を互いに連結でない区間の和で表したときの区間の個数を返す。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::IntervalSet;
+    ///
+    /// let mut s = IntervalSet::new();
+    /// assert_eq!(s.len(), 0);
+    /// s.insert(1..3);
+    /// s.insert(5..7);
+    /// assert_eq!(s.len(), 2);
+    /// s.insert(3..5);
+    /// assert_eq!(s.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize { self.buf.len() }
+
+    /// 各区間の長さ $\\sup-\\inf$ の総和を返す。
+    ///
+    /// 非有界な区間を含む場合は `None` を返す。
+    ///
+    /// # Notes
+    /// 実数の区間として長さを測るので、端点の開閉は関係しない。
+    /// 整数の区間として含まれる整数の個数を数えたい場合は、$[l, r)$
+    /// の形の区間のみを追加するとよい。
+    ///
+    /// # Examples
+    /// ```
+    /// use std::ops::Bound::{Excluded, Included};
+    ///
+    /// use nekolib::ds::IntervalSet;
+    ///
+    /// let mut s = IntervalSet::new();
+    /// assert_eq!(s.measure(), Some(0));
+    /// s.insert(1..3);
+    /// s.insert((Excluded(5), Included(9)));
+    /// assert_eq!(s.measure(), Some(6));
+    /// s.remove(6..7);
+    /// assert_eq!(s.measure(), Some(5));
+    /// s.insert(8..);
+    /// assert_eq!(s.measure(), None);
+    /// ```
+    pub fn measure(&self) -> Option<T>
+    where
+        T: Sub<Output = T> + Sum,
+    {
+        self.buf.iter().map(|x| Some(x.sup()?.clone() - x.inf()?.clone())).sum()
+    }
+
     /// 区間 `r` を追加する。
     pub fn insert<R: RangeBounds<T>>(&mut self, r: R) {
-        let mut r: Interval<T> = (r.start_bound(), r.end_bound()).into();
+        let mut r = Interval::from_bounds(r);
         if r.is_empty() {
             return;
         }
         self.remove_subset(&r);
         match self.buf.range(..&r).cloned().next_back() {
-            Some(x) if x.is_superset(&r) => return,
-            Some(x) if x.touches(&r) => {
+            Some(x) if x.is_superset_of(&r) => return,
+            Some(x) if x.is_connected_with(&r) => {
                 self.buf.remove(&x);
-                r.0 = x.0;
+                r = r.connection(x).unwrap();
             }
             _ => {}
         }
         match self.buf.range(&r..).cloned().next() {
-            Some(x) if x.touches(&r) => {
+            Some(x) if x.is_connected_with(&r) => {
                 self.buf.remove(&x);
-                r.1 = x.1;
+                r = r.connection(x).unwrap();
             }
             _ => {}
         }
@@ -191,31 +107,40 @@ impl<T: Clone + Debug + Ord> IntervalSet<T> {
 
     /// 区間 `r` を削除する。
     pub fn remove<R: RangeBounds<T>>(&mut self, r: R) {
-        let r: Interval<T> = (r.start_bound(), r.end_bound()).into();
+        let r = Interval::from_bounds(r);
         if r.is_empty() {
             return;
         }
         self.remove_subset(&r);
         match self.buf.range(..&r).cloned().next_back() {
-            Some(x) if x.is_superset(&r) => {
+            Some(x) if x.is_superset_of(&r) => {
                 self.buf.remove(&x);
-                let Interval(r0, r1) = r;
-                self.insert_if_nonempty(Interval(x.0, toggle_bound(r0)));
-                self.insert_if_nonempty(Interval(toggle_bound(r1), x.1));
+                let (x0, x1) = x.into_bounds();
+                let (r0, r1) = r.into_bounds();
+                if r0 != Unbounded {
+                    let x = Interval::new(x0, toggle_bound(r0));
+                    self.insert_if_nonempty(x);
+                }
+                if r1 != Unbounded {
+                    let x = Interval::new(toggle_bound(r1), x1);
+                    self.insert_if_nonempty(x);
+                }
                 return;
             }
-            Some(mut x) if x.touches(&r) => {
+            Some(x) if x.is_connected_with(&r) => {
                 self.buf.remove(&x);
-                x.1 = toggle_bound(r.0.clone());
-                self.insert_if_nonempty(x);
+                let x0 = x.into_bounds().0;
+                let r0 = r.start().clone();
+                self.insert_if_nonempty(Interval::new(x0, toggle_bound(r0)));
             }
             _ => {}
         }
         match self.buf.range(&r..).cloned().next() {
-            Some(mut x) if x.touches(&r) => {
+            Some(x) if x.is_connected_with(&r) => {
                 self.buf.remove(&x);
-                x.0 = toggle_bound(r.1);
-                self.insert_if_nonempty(x);
+                let x1 = x.into_bounds().1;
+                let r1 = r.into_bounds().1;
+                self.insert_if_nonempty(Interval::new(toggle_bound(r1), x1));
             }
             _ => {}
         }
@@ -249,14 +174,15 @@ impl<T: Clone + Debug + Ord> IntervalSet<T> {
         }
         match self
             .buf
-            .range(..=Interval(Included(x.clone()), Unbounded))
+            .range(..=Interval::new(Included(x.clone()), Unbounded))
             .next_back()
+            .map(|it| it.end())
         {
-            Some(Interval(_, Included(y))) if y < x => Included(x),
-            Some(Interval(_, Included(y))) => Excluded(y),
-            Some(Interval(_, Excluded(y))) if y <= x => Included(x),
-            Some(Interval(_, Excluded(y))) => Included(y),
-            Some(Interval(_, Unbounded)) => Unbounded,
+            Some(Included(y)) if y < x => Included(x),
+            Some(Included(y)) => Excluded(y),
+            Some(Excluded(y)) if y <= x => Included(x),
+            Some(Excluded(y)) => Included(y),
+            Some(Unbounded) => Unbounded,
             None => Included(x),
         }
     }
@@ -266,7 +192,7 @@ impl<T: Clone + Debug + Ord> IntervalSet<T> {
         &self,
         r: &R,
     ) -> Option<(&Bound<T>, &Bound<T>)> {
-        let r: Interval<T> = (r.start_bound(), r.end_bound()).into();
+        let r = Interval::from_bounds((r.start_bound(), r.end_bound()));
         if self.buf.is_empty() {
             return None;
         }
@@ -275,14 +201,14 @@ impl<T: Clone + Debug + Ord> IntervalSet<T> {
         } else {
             match self
                 .buf
-                .range(..=&Interval(r.0.clone(), Unbounded))
+                .range(..=&Interval::new(r.start().clone(), Unbounded))
                 .next_back()
             {
-                Some(s) if s.is_superset(&r) => Some(s),
+                Some(s) if s.is_superset_of(&r) => Some(s),
                 _ => None,
             }
         })
-        .map(|r| (&r.0, &r.1))
+        .map(|r| (r.start(), r.end()))
     }
 
     /// 区間 `r` を含んでいれば `true` を返す。
@@ -290,33 +216,159 @@ impl<T: Clone + Debug + Ord> IntervalSet<T> {
         self.covering(r).is_some()
     }
 
+    /// 和集合を返す。
+    ///
+    /// # Complexity
+    /// 両者の区間の個数の和を $n$ として $O(n\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::IntervalSet;
+    ///
+    /// let mut s = IntervalSet::new();
+    /// s.insert(1..3);
+    /// s.insert(6..8);
+    /// let mut t = IntervalSet::new();
+    /// t.insert(3..=4);
+    /// t.insert(7..10);
+    ///
+    /// assert_eq!(s.union(&t).to_string(), "[1,4] ∪ [6,10)");
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut res: Vec<Interval<T>> = vec![];
+        let mut it0 = self.buf.iter().peekable();
+        let mut it1 = other.buf.iter().peekable();
+        loop {
+            let x = match (it0.peek(), it1.peek()) {
+                (Some(&x0), Some(&x1)) if x0 < x1 => it0.next().unwrap(),
+                (Some(_), Some(_)) => it1.next().unwrap(),
+                (Some(_), None) => it0.next().unwrap(),
+                (None, Some(_)) => it1.next().unwrap(),
+                (None, None) => break,
+            };
+            match res.pop() {
+                Some(last) if last.is_connected_with(x) => {
+                    res.push(last.connection(x.clone()).unwrap());
+                }
+                Some(last) => {
+                    res.push(last);
+                    res.push(x.clone());
+                }
+                None => res.push(x.clone()),
+            }
+        }
+        Self { buf: res.into_iter().collect() }
+    }
+
+    /// 共通部分を返す。
+    ///
+    /// # Complexity
+    /// 両者の区間の個数の和を $n$ として $O(n\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::IntervalSet;
+    ///
+    /// let mut s = IntervalSet::new();
+    /// s.insert(1..3);
+    /// s.insert(6..8);
+    /// let mut t = IntervalSet::new();
+    /// t.insert(3..=4);
+    /// t.insert(2..=7);
+    ///
+    /// assert_eq!(s.intersection(&t).to_string(), "[2,3) ∪ [6,7]");
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::intersection_internal(self.buf.iter(), other.buf.iter())
+    }
+
+    /// 差集合を返す。
+    ///
+    /// # Complexity
+    /// 両者の区間の個数の和を $n$ として $O(n\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::IntervalSet;
+    ///
+    /// let mut s = IntervalSet::new();
+    /// s.insert(1..5);
+    /// s.insert(7..);
+    /// let mut t = IntervalSet::new();
+    /// t.insert(3..=4);
+    /// t.insert(8..10);
+    ///
+    /// assert_eq!(s.difference(&t).to_string(), "[1,3) ∪ (4,5) ∪ [7,8) ∪ [10,∞)");
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        let comp = other.complement();
+        Self::intersection_internal(self.buf.iter(), comp.iter())
+    }
+
+    fn intersection_internal<'a>(
+        it0: impl Iterator<Item = &'a Interval<T>>,
+        it1: impl Iterator<Item = &'a Interval<T>>,
+    ) -> Self
+    where
+        T: 'a,
+    {
+        // 各集合の区間は互いに連結でないので、共通部分どうしも連結にならない
+        let mut res = vec![];
+        let mut it0 = it0.peekable();
+        let mut it1 = it1.peekable();
+        while let (Some(&x0), Some(&x1)) = (it0.peek(), it1.peek()) {
+            if let Some(x) = x0.clone().intersection(x1.clone()) {
+                res.push(x);
+            }
+            if cmp_end(x0.end(), x1.end()) == Less {
+                it0.next();
+            } else {
+                it1.next();
+            }
+        }
+        Self { buf: res.into_iter().collect() }
+    }
+
+    fn complement(&self) -> Vec<Interval<T>> {
+        let mut res = vec![];
+        let mut cur = Some(Unbounded);
+        for x in &self.buf {
+            let start = cur.take().unwrap();
+            if *x.start() != Unbounded {
+                res.push(Interval::new(start, toggle_bound(x.start().clone())));
+            }
+            match x.end() {
+                Unbounded => break,
+                end => cur = Some(toggle_bound(end.clone())),
+            }
+        }
+        if let Some(start) = cur {
+            res.push(Interval::new(start, Unbounded));
+        }
+        res
+    }
+
     fn remove_subset(&mut self, r: &Interval<T>) {
-        let rem: Vec<Interval<T>> = match r {
-            Interval(Unbounded, Unbounded) => {
+        let point =
+            |x: &T| Interval::new(Included(x.clone()), Included(x.clone()));
+        let rem: Vec<Interval<T>> = match (r.inf(), r.sup()) {
+            (None, None) => {
                 self.buf.clear();
                 return;
             }
-            Interval(Included(lo), Unbounded)
-            | Interval(Excluded(lo), Unbounded) => self.buf.range((
-                Included(Interval(Included(lo.clone()), Included(lo.clone()))),
-                Unbounded,
-            )),
-            Interval(Unbounded, Included(hi))
-            | Interval(Unbounded, Excluded(hi)) => self.buf.range((
-                Unbounded,
-                Included(Interval(Included(hi.clone()), Included(hi.clone()))),
-            )),
-            Interval(Included(lo), Included(hi))
-            | Interval(Included(lo), Excluded(hi))
-            | Interval(Excluded(lo), Included(hi))
-            | Interval(Excluded(lo), Excluded(hi)) => self.buf.range((
-                Included(Interval(Included(lo.clone()), Included(lo.clone()))),
-                Included(Interval(Included(hi.clone()), Included(hi.clone()))),
-            )),
+            (Some(lo), None) => {
+                self.buf.range((Included(point(lo)), Unbounded))
+            }
+            (None, Some(hi)) => {
+                self.buf.range((Unbounded, Included(point(hi))))
+            }
+            (Some(lo), Some(hi)) => {
+                self.buf.range((Included(point(lo)), Included(point(hi))))
+            }
         }
         .cloned()
         .collect();
-        for k in rem.into_iter().filter(|x| r.is_superset(x)) {
+        for k in rem.into_iter().filter(|x| r.is_superset_of(x)) {
             self.buf.remove(&k);
         }
     }
@@ -325,6 +377,193 @@ impl<T: Clone + Debug + Ord> IntervalSet<T> {
         &self,
     ) -> impl Iterator<Item = (&Bound<T>, &Bound<T>)> + DoubleEndedIterator + '_
     {
-        self.buf.iter().map(|x| (&x.0, &x.1))
+        self.buf.iter().map(|x| (x.start(), x.end()))
+    }
+}
+
+/// 区間の和集合として数学記法で表示する。
+///
+/// 各区間は [`Interval`] の `Display` に従って表示し、`∪` で区切る。
+/// 空集合は `∅` と表示する。
+///
+/// [`Interval`]: ../interval_core/struct.Interval.html
+///
+/// # Examples
+/// ```
+/// use nekolib::ds::IntervalSet;
+///
+/// let mut s = IntervalSet::new();
+/// assert_eq!(s.to_string(), "∅");
+///
+/// s.insert(1..5);
+/// s.insert(7..=10);
+/// s.insert(15..);
+/// assert_eq!(s.to_string(), "[1,5) ∪ [7,10] ∪ [15,∞)");
+/// ```
+impl<T: Ord + Display> Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.buf.is_empty() {
+            return write!(f, "∅");
+        }
+        for (i, x) in self.buf.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ∪ ")?;
+            }
+            write!(f, "{}", x)?;
+        }
+        Ok(())
     }
 }
+
+#[test]
+fn display() {
+    let mut s = IntervalSet::new();
+    assert_eq!(s.to_string(), "∅");
+
+    s.insert(1..5);
+    s.insert(7..=10);
+    s.insert(15..);
+    assert_eq!(s.to_string(), "[1,5) ∪ [7,10] ∪ [15,∞)");
+
+    s.insert(..=-3);
+    s.remove(8..9);
+    assert_eq!(s.to_string(), "(-∞,-3] ∪ [1,5) ∪ [7,8) ∪ [9,10] ∪ [15,∞)");
+
+    s.insert(..);
+    assert_eq!(s.to_string(), "(-∞,∞)");
+    s.remove(..);
+    assert_eq!(s.to_string(), "∅");
+
+    let mut s = IntervalSet::<&str>::new();
+    s.insert((Excluded("a"), Included("c")));
+    s.insert((Excluded("x"), Excluded("z")));
+    assert_eq!(s.to_string(), "(a,c] ∪ (x,z)");
+}
+
+#[test]
+fn remove_unbounded() {
+    // 非有界な端点を反転させた区間が残ってはいけない
+    let mut s = IntervalSet::new();
+    s.insert(..);
+    s.remove(3..);
+    assert_eq!(s.to_string(), "(-∞,3)");
+
+    s.insert(..);
+    s.remove(..=3);
+    assert_eq!(s.to_string(), "(3,∞)");
+
+    s.clear();
+    s.insert(..5);
+    s.remove(..3);
+    assert_eq!(s.to_string(), "[3,5)");
+
+    s.clear();
+    s.insert((Excluded(1), Unbounded));
+    s.remove(4..);
+    assert_eq!(s.to_string(), "(1,4)");
+}
+
+#[test]
+fn set_operations() {
+    let mut f = std::iter::successors(Some(8191_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = |n: u64| f.next().unwrap() % n;
+
+    // 端点を偶数に限り、奇数の点で区間の内部を調べる
+    let random_bound = |rand: &mut dyn FnMut(u64) -> u64| {
+        let x = 2 * rand(11) as i32;
+        match rand(5) {
+            0 => Unbounded,
+            1 | 2 => Included(x),
+            _ => Excluded(x),
+        }
+    };
+    let contains = |s: &IntervalSet<i32>, x: i32| s.has_range(&(x..=x));
+    let is_normalized = |s: &IntervalSet<i32>| {
+        let v: Vec<_> = s.buf.iter().collect();
+        v.iter().all(|x| !x.is_empty())
+            && v.windows(2).all(|w| !w[0].is_connected_with(w[1]))
+    };
+
+    for _ in 0..1000 {
+        let mut ss = vec![];
+        let mut naive = vec![];
+        for _ in 0..2 {
+            let mut s = IntervalSet::new();
+            let mut its = vec![];
+            for _ in 0..rand(5) {
+                let start = random_bound(&mut rand);
+                let end = random_bound(&mut rand);
+                s.insert((start, end));
+                its.push(Interval::new(start, end));
+            }
+            ss.push(s);
+            naive.push(its);
+        }
+        let naive_contains = |i: usize, x: i32| {
+            let point = Interval::new(Included(x), Included(x));
+            naive[i].iter().any(|it| it.is_superset_of(&point))
+        };
+
+        let union = ss[0].union(&ss[1]);
+        let intersection = ss[0].intersection(&ss[1]);
+        let difference = ss[0].difference(&ss[1]);
+        assert!(is_normalized(&union));
+        assert!(is_normalized(&intersection));
+        assert!(is_normalized(&difference));
+        for x in -3..=23 {
+            let (c0, c1) = (naive_contains(0, x), naive_contains(1, x));
+            assert_eq!(contains(&union, x), c0 || c1);
+            assert_eq!(contains(&intersection, x), c0 && c1);
+            assert_eq!(contains(&difference, x), c0 && !c1);
+        }
+
+        let mut expected = ss[0].clone();
+        for (start, end) in ss[1].iter() {
+            expected.insert((*start, *end));
+        }
+        assert_eq!(union, expected);
+        assert_eq!(union, ss[1].union(&ss[0]));
+        assert_eq!(intersection, ss[1].intersection(&ss[0]));
+        for (start, end) in ss[1].iter() {
+            expected.remove((*start, *end));
+        }
+        assert_eq!(difference, expected);
+    }
+}
+
+#[test]
+fn size() {
+    let mut s = IntervalSet::new();
+    assert!(s.is_empty());
+    assert_eq!((s.len(), s.measure()), (0, Some(0)));
+
+    s.insert(0..10);
+    s.insert(20..30);
+    assert_eq!((s.len(), s.measure()), (2, Some(20)));
+
+    // 分割
+    s.remove(3..5);
+    s.remove((Excluded(7), Included(8)));
+    assert_eq!((s.len(), s.measure()), (4, Some(17)));
+
+    // 点のみの区間は長さ 0
+    s.insert(15..=15);
+    assert_eq!((s.len(), s.measure()), (5, Some(17)));
+
+    // マージ
+    s.insert(3..=7);
+    assert_eq!((s.len(), s.measure()), (4, Some(19)));
+    s.insert(9..25);
+    assert_eq!((s.len(), s.measure()), (2, Some(29)));
+
+    // 非有界
+    s.insert(..-5);
+    assert_eq!((s.len(), s.measure()), (3, None));
+    s.remove(..0);
+    assert_eq!((s.len(), s.measure()), (2, Some(29)));
+    s.remove(..);
+    assert!(s.is_empty());
+    assert_eq!((s.len(), s.measure()), (0, Some(0)));
+}
@@ -0,0 +1,368 @@
+//! Li Chao tree。
+
+use std::ops::Range;
+
+/// Li Chao tree。
+///
+/// 以下のクエリを処理する。
+/// - 定義域 $X$ を決め、集合 $S \\gets \\emptyset$ で初期化する。
+/// - 集合 $S$ に 1 次関数 $\\lambda x.\\; ax+b$ を追加する。
+/// - 集合 $S$ に、区間 $\[l, r)$ のみで定義された線分 $\\lambda x.\\; ax+b$
+///   を追加する。
+/// - $x\_0\\in X$ に対し、$x\_0$ で定義された $S$ 中の関数における、$x=x\_0$
+///   での最小値を返す。
+///
+/// 定義域は、座標を列挙して与える（`From<Vec<I>>`）か、整数の区間として与える
+/// （[`new`]）。後者ではノードを必要になったときに作るので、$\|X\|$
+/// が大きくてもよい。
+///
+/// [`new`]: #method.new
+///
+/// # Idea
+/// セグ木の各ノードに直線を高々一つ持たせる。ノードの区間 $\[l, r)$
+/// の中点を $m$ として、ノードに直線を追加するときは、すでにある直線と比べて
+/// $x\_m$ での値が小さい方をノードに残す。残らなかった方の直線は、
+/// $x\_l$ と $x\_{r-1}$ のうち残った直線より値が小さい側の子にのみ
+/// 追加すればよい（二直線の大小は高々一回しか入れ換わらないため）。
+///
+/// $x\_0$ での最小値は、$x\_0$ を含む葉から根までのパス上の直線の値の最小値となる。
+///
+/// 線分は、セグ木の区間クエリと同様に $O(\\log(\|X\|))$ 個のノードに分けて、
+/// 各ノードに直線として追加する。
+///
+/// # Complexity
+/// |演算|時間計算量|
+/// |---|---|
+/// |`new`|$O(1)$|
+/// |`from`|$O(\|X\|\\log(\|X\|))$|
+/// |`add_line`, `min`|$O(\\log(\|X\|))$|
+/// |`add_segment`|$O(\\log(\|X\|)^2)$|
+///
+/// 空間計算量は、追加した直線・線分の個数を $q$ として
+/// $O(\\min\\{\|X\|, q\\log(\|X\|)\\})$。
+///
+/// # Examples
+/// ```
+/// use nekolib::ds::LiChaoTree;
+///
+/// let mut lct = LiChaoTree::new(-10..10);
+/// assert_eq!(lct.min(0), None);
+///
+/// lct.add_line((1, 0));
+/// lct.add_segment(-2..3, (-1, -1));
+/// assert_eq!(lct.min(-3), Some(-3));
+/// assert_eq!(lct.min(-2), Some(-2));
+/// assert_eq!(lct.min(0), Some(-1));
+/// assert_eq!(lct.min(2), Some(-3));
+/// assert_eq!(lct.min(3), Some(3));
+/// ```
+///
+/// ```
+/// use nekolib::ds::LiChaoTree;
+///
+/// let xs = vec![-1_000_000_000, 0, 1_000_000_000];
+/// let mut lct: LiChaoTree<i64> = xs.into();
+/// lct.add_segment(0..1_000_000_001, (2, 0));
+/// lct.add_line((-1, 5));
+/// assert_eq!(lct.min(-1_000_000_000), Some(1_000_000_005));
+/// assert_eq!(lct.min(0), Some(0));
+/// assert_eq!(lct.min(1_000_000_000), Some(-999_999_995));
+/// ```
+///
+/// # References
+/// - <https://judge.yosupo.jp/problem/segment_add_get_min>
+pub struct LiChaoTree<I> {
+    domain: Domain<I>,
+    len: usize,
+    node: Vec<Node<I>>,
+}
+
+enum Domain<I> {
+    Coords(Vec<I>),
+    Range(I),
+}
+
+#[derive(Clone, Copy)]
+struct Node<I> {
+    line: Option<(I, I)>,
+    // 根は子にならないので、0 で子がないことを表す。
+    child: [usize; 2],
+}
+
+impl<I: LiChaoInt> From<Vec<I>> for LiChaoTree<I> {
+    fn from(mut xs: Vec<I>) -> Self {
+        xs.sort_unstable();
+        xs.dedup();
+        let len = xs.len();
+        Self::with_domain(Domain::Coords(xs), len)
+    }
+}
+
+impl<I: LiChaoInt> LiChaoTree<I> {
+    /// 区間 `range` に含まれる整数を定義域として初期化する。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::LiChaoTree;
+    ///
+    /// let mut lct = LiChaoTree::new(0..1_000_000_000_000_000_000_i64);
+    /// lct.add_line((-3, 0));
+    /// assert_eq!(lct.min(999_999_999), Some(-2_999_999_997));
+    /// ```
+    pub fn new(range: Range<I>) -> Self {
+        let Range { start, end } = range;
+        let len = if start < end { end.offset_from(start) } else { 0 };
+        Self::with_domain(Domain::Range(start), len)
+    }
+
+    /// 直線 $\\lambda x.\\; ax+b$ を追加する。
+    pub fn add_line(&mut self, line: (I, I)) {
+        if self.len > 0 {
+            self.insert(0, 0, self.len, line);
+        }
+    }
+
+    /// 区間 `range` で定義された線分 $\\lambda x.\\; ax+b$ を追加する。
+    ///
+    /// 区間の端点は定義域に含まれていなくてもよい。
+    pub fn add_segment(&mut self, range: Range<I>, line: (I, I)) {
+        let Range { start, end } = range;
+        let (l, r) = (self.lower_index(start), self.lower_index(end));
+        if l < r {
+            self.add_segment_rec(0, 0, self.len, l, r, line);
+        }
+    }
+
+    /// $x$ での最小値を返す。
+    ///
+    /// $x$ で定義された直線・線分がないときは `None` を返す。
+    ///
+    /// # Panics
+    /// $x$ が定義域に含まれないとき。
+    pub fn min(&self, x: I) -> Option<I> {
+        let i = self.index(x);
+        let (mut v, mut l, mut r) = (0, 0, self.len);
+        let mut res: Option<I> = None;
+        loop {
+            if let Some(line) = self.node[v].line {
+                let y = x.eval(line);
+                res = Some(res.map_or(y, |res| res.min(y)));
+            }
+            if r - l == 1 {
+                break;
+            }
+            let m = l + (r - l) / 2;
+            if i < m {
+                r = m;
+                v = self.node[v].child[0];
+            } else {
+                l = m;
+                v = self.node[v].child[1];
+            }
+            if v == 0 {
+                break;
+            }
+        }
+        res
+    }
+
+    fn with_domain(domain: Domain<I>, len: usize) -> Self {
+        let root = Node { line: None, child: [0; 2] };
+        Self { domain, len, node: vec![root] }
+    }
+
+    fn coord(&self, i: usize) -> I {
+        match &self.domain {
+            Domain::Coords(xs) => xs[i],
+            Domain::Range(lo) => lo.add_offset(i),
+        }
+    }
+
+    fn index(&self, x: I) -> usize {
+        let i = match &self.domain {
+            Domain::Coords(xs) => xs.binary_search(&x).ok(),
+            Domain::Range(lo) if *lo <= x => Some(x.offset_from(*lo)),
+            Domain::Range(_) => None,
+        };
+        match i {
+            Some(i) if i < self.len => i,
+            _ => panic!("{:?} is not in the domain", x),
+        }
+    }
+
+    fn lower_index(&self, x: I) -> usize {
+        match &self.domain {
+            Domain::Coords(xs) => xs.partition_point(|&xi| xi < x),
+            Domain::Range(lo) if *lo < x => x.offset_from(*lo).min(self.len),
+            Domain::Range(_) => 0,
+        }
+    }
+
+    fn child(&mut self, v: usize, d: usize) -> usize {
+        if self.node[v].child[d] == 0 {
+            self.node[v].child[d] = self.node.len();
+            self.node.push(Node { line: None, child: [0; 2] });
+        }
+        self.node[v].child[d]
+    }
+
+    fn insert(&mut self, v: usize, l: usize, r: usize, line: (I, I)) {
+        let (mut v, mut l, mut r, mut line) = (v, l, r, line);
+        loop {
+            let mut cur = match self.node[v].line {
+                Some(cur) => cur,
+                None => {
+                    self.node[v].line = Some(line);
+                    return;
+                }
+            };
+            let m = l + (r - l) / 2;
+            let xm = self.coord(m);
+            if xm.eval(line) < xm.eval(cur) {
+                self.node[v].line = Some(line);
+                std::mem::swap(&mut line, &mut cur);
+            }
+            if r - l == 1 {
+                return;
+            }
+
+            // line の方が小さくなりうる側の子に降りる。
+            let (xl, xr) = (self.coord(l), self.coord(r - 1));
+            if xl.eval(line) < xl.eval(cur) {
+                v = self.child(v, 0);
+                r = m;
+            } else if xr.eval(line) < xr.eval(cur) {
+                v = self.child(v, 1);
+                l = m;
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn add_segment_rec(
+        &mut self,
+        v: usize,
+        l: usize,
+        r: usize,
+        ql: usize,
+        qr: usize,
+        line: (I, I),
+    ) {
+        if ql <= l && r <= qr {
+            self.insert(v, l, r, line);
+            return;
+        }
+        let m = l + (r - l) / 2;
+        if ql < m {
+            let c = self.child(v, 0);
+            self.add_segment_rec(c, l, m, ql, qr, line);
+        }
+        if m < qr {
+            let c = self.child(v, 1);
+            self.add_segment_rec(c, m, r, ql, qr, line);
+        }
+    }
+}
+
+/// [`LiChaoTree`] で扱う整数型。
+pub trait LiChaoInt: Copy + Ord + std::fmt::Debug {
+    /// $a\\cdot x+b$ を返す。
+    fn eval(self, line: (Self, Self)) -> Self;
+    /// $x-l$ を返す。ただし $l\\le x$ とする。
+    fn offset_from(self, lo: Self) -> usize;
+    /// $l+d$ を返す。
+    fn add_offset(self, d: usize) -> Self;
+}
+
+macro_rules! impl_li_chao_int {
+    ( $( ($i:ty, $u:ty) )* ) => { $(
+        impl LiChaoInt for $i {
+            fn eval(self, (a, b): (Self, Self)) -> Self { a * self + b }
+            fn offset_from(self, lo: Self) -> usize {
+                self.wrapping_sub(lo) as $u as usize
+            }
+            fn add_offset(self, d: usize) -> Self {
+                self.wrapping_add(d as $i)
+            }
+        }
+    )* };
+}
+
+impl_li_chao_int! {
+    (i8, u8) (i16, u16) (i32, u32) (i64, u64) (i128, u128) (isize, usize)
+}
+
+#[test]
+fn test_naive() {
+    let mut f = std::iter::successors(Some(1618_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = |n: u64| f.next().unwrap() % n;
+
+    let (lo, hi) = (-50_i64, 50_i64);
+    let coords: Vec<_> = (lo..hi).filter(|x| x % 3 != 0).collect();
+    let mut lct_range = LiChaoTree::new(lo..hi);
+    let mut lct_coords: LiChaoTree<_> = coords.clone().into();
+    let mut naive = vec![];
+    for _ in 0..300 {
+        let a = rand(41) as i64 - 20;
+        let b = rand(2001) as i64 - 1000;
+        if rand(4) == 0 {
+            lct_range.add_line((a, b));
+            lct_coords.add_line((a, b));
+            naive.push((i64::MIN, i64::MAX, a, b));
+        } else {
+            let l = rand(120) as i64 - 60;
+            let r = l + rand(60) as i64;
+            lct_range.add_segment(l..r, (a, b));
+            lct_coords.add_segment(l..r, (a, b));
+            naive.push((l, r, a, b));
+        }
+
+        let min = |x: i64| {
+            naive
+                .iter()
+                .filter(|&&(l, r, _, _)| l <= x && x < r)
+                .map(|&(_, _, a, b)| a * x + b)
+                .min()
+        };
+        for x in lo..hi {
+            assert_eq!(lct_range.min(x), min(x));
+        }
+        for &x in &coords {
+            assert_eq!(lct_coords.min(x), min(x));
+        }
+    }
+}
+
+#[test]
+fn test_empty() {
+    let lct = LiChaoTree::new(0..10);
+    assert!((0..10).all(|x| lct.min(x).is_none()));
+
+    let mut lct: LiChaoTree<i32> = vec![3, 1, 4, 1, 5].into();
+    lct.add_segment(6..9, (1, 1));
+    lct.add_segment(2..3, (1, 1));
+    assert!([1, 3, 4, 5].iter().all(|&x| lct.min(x).is_none()));
+
+    let mut lct = LiChaoTree::new(0..0);
+    lct.add_line((1, 1));
+    lct.add_segment(-1..1, (1, 1));
+    let mut lct: LiChaoTree<i32> = vec![].into();
+    lct.add_line((1, 1));
+}
+
+#[test]
+#[should_panic(expected = "10 is not in the domain")]
+fn test_out_of_domain_range() {
+    let lct = LiChaoTree::new(0..10);
+    lct.min(10);
+}
+
+#[test]
+#[should_panic(expected = "2 is not in the domain")]
+fn test_out_of_domain_coords() {
+    let lct: LiChaoTree<i32> = vec![1, 3].into();
+    lct.min(2);
+}
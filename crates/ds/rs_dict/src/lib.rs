@@ -3,7 +3,7 @@
 use std::fmt::Debug;
 use std::ops::{Range, RangeBounds};
 
-use buf_range::bounds_within;
+use buf_range::{bounds_within, check_bounds, check_bounds_range};
 use count::Count;
 use find_nth::FindNth;
 
@@ -64,7 +64,37 @@ use SelectPreprocess::{Dense, Sparse};
 impl From<Vec<bool>> for RsDict {
     fn from(buf: Vec<bool>) -> Self {
         let len = buf.len();
-        let buf = Self::compress_vec_bool(buf);
+        Self::from((Self::compress_vec_bool(buf), len))
+    }
+}
+
+/// ワード列とビット長の組から構築する。
+///
+/// $i$ 番目のビットは `buf[i / 64] >> (i % 64) & 1` とする。
+/// $i \\ge n$ に対応するビットは無視するので、末尾のワードの上位ビットは
+/// 何が入っていてもよい。
+///
+/// # Requirements
+/// `buf.len() * 64 >= n`.
+///
+/// # Examples
+/// ```
+/// use nekolib::ds::RsDict;
+///
+/// // [1, 0, 1, 1, 0]
+/// let rs: RsDict = (vec![0b_1111_1111_0_1101_u64], 5).into();
+/// assert_eq!(rs.rank(5, 1), 3);
+/// assert_eq!(rs.select(0, 2), Some(5));
+/// ```
+impl From<(Vec<u64>, usize)> for RsDict {
+    fn from((mut buf, len): (Vec<u64>, usize)) -> Self {
+        assert!(buf.len() * WORD_SIZE >= len, "`buf` is too short");
+        let nc = len.div_ceil(WORD_SIZE);
+        buf.truncate(nc);
+        if len % WORD_SIZE != 0 {
+            buf[nc - 1] &= !(!0_u64 << (len % WORD_SIZE));
+        }
+        buf.push(0);
         let rank = Self::preprocess_rank(&buf);
         let sel0 = Self::preprocess_select(&buf, len, 0);
         let sel1 = Self::preprocess_select(&buf, len, 1);
@@ -74,12 +104,8 @@ impl From<Vec<bool>> for RsDict {
 
 impl RsDict {
     fn compress_vec_bool(buf: Vec<bool>) -> Vec<u64> {
-        if buf.is_empty() {
-            return vec![];
-        }
         let n = buf.len();
-        let nc = 1 + (n - 1) / WORD_SIZE;
-        let mut res = vec![0; nc + 1];
+        let mut res = vec![0; n.div_ceil(WORD_SIZE)];
         for i in 0..n {
             if buf[i] {
                 res[i / WORD_SIZE] |= 1_u64 << (i % WORD_SIZE);
@@ -132,6 +158,47 @@ impl RsDict {
         let rank = if x == 0 { end - rank1 } else { rank1 };
         rank
     }
+    /// 区間 `range` 中の $x$ の個数を返す。
+    ///
+    /// $[l, r)$ に対して $\\mathtt{rank}\_x(r) - \\mathtt{rank}\_x(l)$ を返す。
+    /// 空区間に対しては $0$ を返す。
+    ///
+    /// # Panics
+    /// $r \\gt n$ のとき。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::RsDict;
+    ///
+    /// let rs: RsDict = vec![true, false, true, true, false].into();
+    /// assert_eq!(rs.rank_range(.., 1), 3);
+    /// assert_eq!(rs.rank_range(1..4, 0), 1);
+    /// assert_eq!(rs.rank_range(2..=3, 1), 2);
+    /// assert_eq!(rs.rank_range(3..3, 1), 0);
+    /// ```
+    pub fn rank_range(&self, range: impl RangeBounds<usize>, x: u64) -> usize {
+        let Range { start, end } = bounds_within(range, self.len);
+        check_bounds_range(end, 0..=self.len);
+        self.rank(end, x) - self.rank(start, x)
+    }
+    /// $\\mathtt{rank}\_x(i) = k$ なる最小の $i$ を返す。
+    ///
+    /// すなわち、$k \\ge 1$ のときは $k$ 番目（1-indexed）の $x$
+    /// の位置を $j$ として $j+1$ を返す。
+    /// $k = 0$ のときは `Some(0)` を返し、$x$ の個数が $k$ 未満のときは
+    /// `None` を返す。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::RsDict;
+    ///
+    /// let rs: RsDict = vec![true, false, true, true, false].into();
+    /// assert_eq!(rs.select(1, 0), Some(0));
+    /// assert_eq!(rs.select(1, 1), Some(1));
+    /// assert_eq!(rs.select(1, 3), Some(4));
+    /// assert_eq!(rs.select(1, 4), None);
+    /// assert_eq!(rs.select(0, 2), Some(5));
+    /// ```
     pub fn select(&self, x: u64, k: usize) -> Option<usize> {
         if self.rank(self.len, x) < k {
             None
@@ -141,16 +208,209 @@ impl RsDict {
             Some(self.find_nth_internal(x, k - 1) + 1)
         }
     }
+    /// `select(0, k)` を返す。詳細は [`select`] を参照。
+    ///
+    /// [`select`]: #method.select
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::RsDict;
+    ///
+    /// let rs: RsDict = vec![true, false, true, true, false].into();
+    /// assert_eq!(rs.select0(0), Some(0));
+    /// assert_eq!(rs.select0(1), Some(2));
+    /// assert_eq!(rs.select0(2), Some(5));
+    /// assert_eq!(rs.select0(3), None);
+    /// ```
+    pub fn select0(&self, k: usize) -> Option<usize> { self.select(0, k) }
+    /// `select(1, k)` を返す。詳細は [`select`] を参照。
+    ///
+    /// [`select`]: #method.select
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::RsDict;
+    ///
+    /// let rs: RsDict = vec![true, false, true, true, false].into();
+    /// assert_eq!(rs.select1(0), Some(0));
+    /// assert_eq!(rs.select1(2), Some(3));
+    /// assert_eq!(rs.select1(3), Some(4));
+    /// assert_eq!(rs.select1(4), None);
+    /// ```
+    pub fn select1(&self, k: usize) -> Option<usize> { self.select(1, k) }
+
+    fn pred(&self, x: u64, i: usize) -> Option<usize> {
+        let k = self.rank(self.len.min(i.saturating_add(1)), x);
+        if k == 0 { None } else { Some(self.find_nth_internal(x, k - 1)) }
+    }
+    fn succ(&self, x: u64, i: usize) -> Option<usize> {
+        if i >= self.len {
+            return None;
+        }
+        let k = self.rank(i, x);
+        if k == self.rank(self.len, x) {
+            None
+        } else {
+            Some(self.find_nth_internal(x, k))
+        }
+    }
+    /// $i$ 以下で最大の `0` の位置を返す。
+    ///
+    /// $i$ 自身も含む。BitSet の `find_last(..=i)` と同様。
+    /// 該当する位置がなければ `None` を返す。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::RsDict;
+    ///
+    /// let rs: RsDict = vec![true, false, true, true, false].into();
+    /// assert_eq!(rs.pred0(0), None);
+    /// assert_eq!(rs.pred0(1), Some(1));
+    /// assert_eq!(rs.pred0(3), Some(1));
+    /// assert_eq!(rs.pred0(10), Some(4));
+    /// ```
+    pub fn pred0(&self, i: usize) -> Option<usize> { self.pred(0, i) }
+    /// $i$ 以下で最大の `1` の位置を返す。
+    ///
+    /// $i$ 自身も含む。BitSet の `find_last(..=i)` と同様。
+    /// 該当する位置がなければ `None` を返す。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::RsDict;
+    ///
+    /// let rs: RsDict = vec![false, true, false, true, false].into();
+    /// assert_eq!(rs.pred1(0), None);
+    /// assert_eq!(rs.pred1(1), Some(1));
+    /// assert_eq!(rs.pred1(2), Some(1));
+    /// assert_eq!(rs.pred1(4), Some(3));
+    /// ```
+    pub fn pred1(&self, i: usize) -> Option<usize> { self.pred(1, i) }
+    /// $i$ 以上で最小の `0` の位置を返す。
+    ///
+    /// $i$ 自身も含む。BitSet の `find_first(i..)` と同様。
+    /// 該当する位置がなければ `None` を返す。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::RsDict;
+    ///
+    /// let rs: RsDict = vec![true, false, true, true, false].into();
+    /// assert_eq!(rs.succ0(0), Some(1));
+    /// assert_eq!(rs.succ0(2), Some(4));
+    /// assert_eq!(rs.succ0(4), Some(4));
+    /// assert_eq!(rs.succ0(5), None);
+    /// ```
+    pub fn succ0(&self, i: usize) -> Option<usize> { self.succ(0, i) }
+    /// $i$ 以上で最小の `1` の位置を返す。
+    ///
+    /// $i$ 自身も含む。BitSet の `find_first(i..)` と同様。
+    /// 該当する位置がなければ `None` を返す。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::RsDict;
+    ///
+    /// let rs: RsDict = vec![false, true, false, true, false].into();
+    /// assert_eq!(rs.succ1(0), Some(1));
+    /// assert_eq!(rs.succ1(3), Some(3));
+    /// assert_eq!(rs.succ1(4), None);
+    /// ```
+    pub fn succ1(&self, i: usize) -> Option<usize> { self.succ(1, i) }
+
+    /// $i$ 番目と $i+1$ 番目のビットを入れ替える。
+    ///
+    /// 入れ替えによって位置が変わる出現は `0` と `1` の一つずつなので、
+    /// それらに関する前計算のみを更新する。
+    /// 密なブロックの幅が $w^2$ に達した場合は、疎なブロックに作り直す。
+    ///
+    /// # Panics
+    /// $i+1 \\ge n$ のとき。
+    ///
+    /// # Complexity
+    /// $O(w)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::RsDict;
+    ///
+    /// let mut rs: RsDict = vec![true, false, true, true, false].into();
+    /// rs.swap(3);
+    /// assert_eq!(rs.rank(4, 1), 2);
+    /// assert_eq!(rs.select0(2), Some(4));
+    /// assert_eq!(rs.select1(3), Some(5));
+    /// ```
+    pub fn swap(&mut self, i: usize) {
+        check_bounds(i + 1, self.len);
+        let bit = |j: usize| self.buf[j / WORD_SIZE] >> (j % WORD_SIZE) & 1;
+        let (x0, x1) = (bit(i), bit(i + 1));
+        if x0 == x1 {
+            return;
+        }
+
+        let k0 = self.rank(i, x0);
+        let k1 = self.rank(i, x1);
+        let (w0, w1) = (i / WORD_SIZE, (i + 1) / WORD_SIZE);
+        self.buf[w0] ^= 1 << (i % WORD_SIZE);
+        self.buf[w1] ^= 1 << ((i + 1) % WORD_SIZE);
+        if w0 != w1 {
+            let r = &mut self.rank[w1];
+            if x1 == 1 {
+                *r += 1;
+            } else {
+                *r -= 1;
+            }
+        }
+        self.move_occurrence(x0, k0, i + 1);
+        self.move_occurrence(x1, k1, i);
+    }
+
+    /// $k$ 番目 (0-indexed) の $x$ の位置が `pos` に変わったときの更新をする。
+    fn move_occurrence(&mut self, x: u64, k: usize, pos: usize) {
+        let (il, is) = (k / WORD_SIZE, k % WORD_SIZE);
+        let buf = &self.buf;
+        let sel = if x == 0 { &mut self.sel0 } else { &mut self.sel1 };
+        match &mut sel[il] {
+            Sparse(dir) => dir[is] = pos,
+            // 先頭のブロックの左端は 0 としている
+            Dense(range) if is == 0 && il > 0 => range.start = pos,
+            Dense(_) => {}
+        }
+        if is != 0 || il == 0 {
+            return;
+        }
+        if let Dense(range) = &mut sel[il - 1] {
+            range.end = pos;
+        }
+        for pre in &mut sel[il - 1..=il] {
+            if let Dense(range) = pre {
+                if range.end - range.start >= WORD_SIZE_2 {
+                    *pre = Sparse(Self::occurrences(buf, x, range.clone()));
+                }
+            }
+        }
+    }
+
+    fn occurrences(buf: &[u64], x: u64, range: Range<usize>) -> Vec<usize> {
+        let mut res = vec![];
+        let (wl, wr) = (range.start / WORD_SIZE, range.end.div_ceil(WORD_SIZE));
+        for (wi, &word) in (wl..wr).zip(&buf[wl..wr]) {
+            let mut word = if x == 0 { !word } else { word };
+            while word != 0 {
+                let j = wi * WORD_SIZE + word.trailing_zeros() as usize;
+                if range.contains(&j) {
+                    res.push(j);
+                }
+                word &= word - 1;
+            }
+        }
+        res
+    }
 }
 
 impl Count<u64> for RsDict {
     fn count(&self, r: impl RangeBounds<usize>, x: u64) -> usize {
-        let Range { start, end } = bounds_within(r, self.len);
-        if start > 0 {
-            self.rank(end, x) - self.rank(start, x)
-        } else {
-            self.rank(end, x)
-        }
+        self.rank_range(r, x)
     }
 }
 
@@ -195,6 +455,69 @@ impl RsDict {
             }
         }
     }
+    /// 内部表現の不変条件を検査する。
+    ///
+    /// 以下が成り立つことを確かめる。
+    /// - `buf` は `len` bits を詰めたもので、末尾に番兵の word を持ち、`len`
+    ///   以降のビットは `0` である。
+    /// - `rank[i]` は `buf[..i]` の `1` の個数である。
+    /// - $\\mathtt{rank}\_0(i) + \\mathtt{rank}\_1(i) = i$ であり、
+    ///   $\\mathtt{rank}\_x(i)$ は $i$ について 1 ずつ広義単調増加する。
+    /// - `sel0`/`sel1` の各ブロックは $w$ 個ずつの出現を管理し、`Dense`
+    ///   の幅は $w^2$ 未満、`Sparse` の出現位置は昇順である。
+    /// - $\\mathtt{select}\_x$ と $\\mathtt{rank}\_x$ は互いに逆関数である。
+    ///   すなわち、$1\\le k\\le \\mathtt{rank}\_x(n)$ に対して
+    ///   $s = \\mathtt{select}\_x(k)$ とおくと、$\\mathtt{rank}\_x(s) = k$
+    ///   かつ $s-1$ 番目のビットは $x$ である。
+    #[cfg(test)]
+    fn check_invariants(&self) {
+        let n = self.len;
+        assert_eq!(self.buf.len(), n.div_ceil(WORD_SIZE) + 1);
+        for i in n..self.buf.len() * WORD_SIZE {
+            assert_eq!(self.buf[i / WORD_SIZE] >> (i % WORD_SIZE) & 1, 0);
+        }
+
+        assert_eq!(self.rank.len(), self.buf.len());
+        let mut acc = 0;
+        for (i, &ri) in self.rank.iter().enumerate() {
+            assert_eq!(ri, acc);
+            acc += self.buf[i].count_ones() as usize;
+        }
+
+        for i in 0..n {
+            assert_eq!(self.rank(i, 0) + self.rank(i, 1), i);
+            let bit = self.buf[i / WORD_SIZE] >> (i % WORD_SIZE) & 1;
+            assert_eq!(self.rank(i + 1, bit), self.rank(i, bit) + 1);
+            assert_eq!(self.rank(i + 1, 1 - bit), self.rank(i, 1 - bit));
+        }
+        assert_eq!(self.rank(n, 0) + self.rank(n, 1), n);
+
+        for (x, sel) in [(0, &self.sel0), (1, &self.sel1)] {
+            let count = self.rank(n, x);
+            assert_eq!(sel.len(), count.div_ceil(WORD_SIZE));
+            for pre in sel {
+                match pre {
+                    Sparse(dir) => {
+                        assert!(!dir.is_empty() && dir.len() <= WORD_SIZE);
+                        assert!(dir.windows(2).all(|w| w[0] < w[1]));
+                    }
+                    Dense(range) => {
+                        assert!(range.end - range.start < WORD_SIZE_2);
+                    }
+                }
+            }
+
+            assert_eq!(self.select(x, 0), Some(0));
+            for k in 1..=count {
+                let s = self.select(x, k).unwrap();
+                assert_eq!(self.rank(s, x), k);
+                let bit =
+                    self.buf[(s - 1) / WORD_SIZE] >> ((s - 1) % WORD_SIZE);
+                assert_eq!(bit & 1, x);
+            }
+            assert_eq!(self.select(x, count + 1), None);
+        }
+    }
     fn rank_rough(&self, n: usize, x: u64) -> usize {
         let rank1 = self.rank[n];
         let rank = if x == 0 { n * WORD_SIZE - rank1 } else { rank1 };
@@ -260,3 +583,232 @@ fn test_rs() {
     assert_eq!(rs.find_nth(.., 0, zeros.len()), None);
     assert_eq!(rs.find_nth(.., 1, ones.len()), None);
 }
+
+#[test]
+fn test_invariants() {
+    let mut f =
+        std::iter::successors(Some(296_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    for &n in &[0, 1, 63, 64, 65, 1000, 4096, 5000, 10000] {
+        // 疎なブロックと密なブロックが両方できるよう偏りを変える
+        for &p in &[1, 2, 32, 63] {
+            let buf: Vec<_> = (0..n).map(|_| rand() % 64 < p).collect();
+            let rs: RsDict = buf.into();
+            rs.check_invariants();
+        }
+    }
+}
+
+#[test]
+fn test_from_words() {
+    let mut f =
+        std::iter::successors(Some(185_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    for &n in &[0, 1, 63, 64, 65, 127, 128, 1000, 4096, 5000, 10000] {
+        for &p in &[1, 2, 32, 63] {
+            let bits: Vec<_> = (0..n).map(|_| rand() % 64 < p).collect();
+            let expected: RsDict = bits.clone().into();
+
+            // 末尾の余分なビットやワードは無視される
+            let mut words = vec![0_u64; n / WORD_SIZE + 2];
+            for (i, w) in words.iter_mut().enumerate() {
+                if i * WORD_SIZE >= n {
+                    *w = (rand() << 33) ^ rand();
+                }
+            }
+            for (i, &b) in bits.iter().enumerate() {
+                if b {
+                    words[i / WORD_SIZE] |= 1 << (i % WORD_SIZE);
+                }
+            }
+            if n % WORD_SIZE != 0 {
+                words[n / WORD_SIZE] |= !0 << (n % WORD_SIZE);
+            }
+
+            for len in [n.div_ceil(WORD_SIZE), words.len()] {
+                let rs: RsDict = (words[..len].to_vec(), n).into();
+                rs.check_invariants();
+                assert_eq!(rs.len, expected.len);
+                assert_eq!(rs.buf, expected.buf);
+                assert_eq!(rs.rank, expected.rank);
+                let sel = |rs: &RsDict| format!("{:?}", (&rs.sel0, &rs.sel1));
+                assert_eq!(sel(&rs), sel(&expected));
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "`buf` is too short")]
+fn test_from_words_short() { let _: RsDict = (vec![0_u64; 2], 129).into(); }
+
+#[test]
+fn test_select01() {
+    let mut f =
+        std::iter::successors(Some(748_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    for &n in &[0, 1, 2, 63, 64, 65, 1000, 5000] {
+        for &p in &[0, 1, 32, 63, 64] {
+            let buf: Vec<_> = (0..n).map(|_| rand() % 64 < p).collect();
+            let rs: RsDict = buf.clone().into();
+            let count0 = buf.iter().filter(|&&b| !b).count();
+            let count1 = n - count0;
+
+            assert_eq!(rs.select0(0), Some(0));
+            assert_eq!(rs.select1(0), Some(0));
+            let mut k0 = 0;
+            let mut k1 = 0;
+            for (i, &b) in buf.iter().enumerate() {
+                if b {
+                    k1 += 1;
+                    assert_eq!(rs.select1(k1), Some(i + 1));
+                } else {
+                    k0 += 1;
+                    assert_eq!(rs.select0(k0), Some(i + 1));
+                }
+            }
+            for k in 0..=n + 1 {
+                assert_eq!(rs.select0(k), rs.select(0, k));
+                assert_eq!(rs.select1(k), rs.select(1, k));
+            }
+            for k in count0 + 1..=n + 2 {
+                assert_eq!(rs.select0(k), None);
+            }
+            for k in count1 + 1..=n + 2 {
+                assert_eq!(rs.select1(k), None);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rank_range() {
+    use std::ops::Bound::{Excluded, Unbounded};
+
+    let n = 300;
+    let buf: Vec<_> = (0..n).map(|i| i * i % 7 < 3).collect();
+    let rs: RsDict = buf.clone().into();
+    let naive = |l: usize, r: usize, x: u64| {
+        buf[l..r].iter().filter(|&&b| b as u64 == x).count()
+    };
+
+    for x in 0..=1 {
+        assert_eq!(rs.rank_range(.., x), naive(0, n, x));
+        for l in 0..=n {
+            assert_eq!(rs.rank_range(l.., x), naive(l, n, x));
+            assert_eq!(rs.rank_range(..l, x), naive(0, l, x));
+            for r in l..=n {
+                assert_eq!(rs.rank_range(l..r, x), naive(l, r, x));
+            }
+            for r in l..n {
+                assert_eq!(rs.rank_range(l..=r, x), naive(l, r + 1, x));
+                assert_eq!(rs.rank_range(..=r, x), naive(0, r + 1, x));
+            }
+        }
+        for l in 0..n {
+            let b = (Excluded(l), Unbounded);
+            assert_eq!(rs.rank_range(b, x), naive(l + 1, n, x));
+        }
+
+        // 空区間
+        assert_eq!(rs.rank_range(3..3, x), 0);
+        assert_eq!(rs.rank_range(n..n, x), 0);
+        let (l, r) = (5, 2);
+        assert_eq!(rs.rank_range(l..r, x), 0);
+        assert_eq!(rs.rank_range(n.., x), 0);
+    }
+
+    let empty: RsDict = vec![].into();
+    assert_eq!(empty.rank_range(.., 0), 0);
+    assert_eq!(empty.rank_range(0..0, 1), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_rank_range_out_of_bounds() {
+    let rs: RsDict = vec![true; 64].into();
+    rs.rank_range(..65, 1);
+}
+
+#[test]
+#[should_panic]
+fn test_rank_range_out_of_bounds_inclusive() {
+    let rs: RsDict = vec![true; 5].into();
+    rs.rank_range(3..=5, 1);
+}
+
+#[test]
+fn test_pred_succ() {
+    let mut f =
+        std::iter::successors(Some(397_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    for &n in &[0, 1, 2, 63, 64, 65, 1000, 5000] {
+        for &p in &[0, 1, 32, 63, 64] {
+            let buf: Vec<_> = (0..n).map(|_| rand() % 64 < p).collect();
+            let rs: RsDict = buf.clone().into();
+            for i in 0..n + 3 {
+                let pred =
+                    |b: bool| (0..n.min(i + 1)).rev().find(|&j| buf[j] == b);
+                let succ = |b: bool| (i..n).find(|&j| buf[j] == b);
+                assert_eq!(rs.pred0(i), pred(false));
+                assert_eq!(rs.pred1(i), pred(true));
+                assert_eq!(rs.succ0(i), succ(false));
+                assert_eq!(rs.succ1(i), succ(true));
+            }
+            assert_eq!(rs.pred0(usize::MAX), buf.iter().rposition(|&b| !b));
+            assert_eq!(rs.pred1(usize::MAX), buf.iter().rposition(|&b| b));
+            assert_eq!(rs.succ0(usize::MAX), None);
+            assert_eq!(rs.succ1(usize::MAX), None);
+        }
+    }
+}
+
+#[test]
+fn test_swap() {
+    let mut f =
+        std::iter::successors(Some(258_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    for &n in &[2, 63, 64, 65, 1000, 5000] {
+        for &p in &[1, 2, 32, 63] {
+            let mut buf: Vec<_> = (0..n).map(|_| rand() % 64 < p).collect();
+            let mut rs: RsDict = buf.clone().into();
+            for _ in 0..200 {
+                let i = (rand() % (n as u64 - 1)) as usize;
+                buf.swap(i, i + 1);
+                rs.swap(i);
+            }
+            rs.check_invariants();
+            for i in 0..=n {
+                let count = buf[..i].iter().filter(|&&b| b).count();
+                assert_eq!(rs.rank(i, 1), count);
+            }
+        }
+    }
+
+    // 密なブロックの幅が w^2 に達する場合
+    let w = WORD_SIZE;
+    let mut buf = vec![false; w * w + 2 * w];
+    for b in &mut buf[..2 * w] {
+        *b = true;
+    }
+    let end = w + w * w - 1;
+    buf[end] = true;
+    let mut rs: RsDict = buf.into();
+    assert!(matches!(rs.sel1[1], Dense(_)));
+    rs.swap(end);
+    assert!(matches!(rs.sel1[1], Sparse(_)));
+    rs.check_invariants();
+    assert_eq!(rs.select1(2 * w + 1), Some(end + 2));
+}
+
+#[test]
+#[should_panic]
+fn test_swap_out_of_bounds() {
+    let mut rs: RsDict = vec![true, false, true].into();
+    rs.swap(2);
+}
@@ -2,15 +2,18 @@
 
 use std::cell::RefCell;
 use std::fmt::{self, Debug};
-use std::ops::{Deref, DerefMut, Range, RangeBounds};
+use std::iter::FromIterator;
+use std::ops::{Add, Deref, DerefMut, Range, RangeBounds};
 
 use act::Act;
 use action::MonoidAction;
+use additive::Zero;
 use binop::{Identity, Magma};
-use buf_range::{bounds_within, check_bounds_range};
+use buf_range::{bounds_within, check_bounds, check_bounds_range};
 use fold::Fold;
 use fold_bisect::{FoldBisect, FoldBisectRev};
 use get_mut::GetMut;
+use op_add_count::OpAddCount;
 
 const WORD_SIZE: u32 = 0_usize.count_zeros();
 
@@ -64,6 +67,61 @@ where
     pub fn is_empty(&self) -> bool { self.len == 0 }
     pub fn len(&self) -> usize { self.len }
 
+    /// $i$ 番目の要素を返す。
+    ///
+    /// # Panics
+    /// `i >= self.len()` のとき。
+    ///
+    /// # Complexity
+    /// $O(\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::VecActSegtree;
+    /// use nekolib::traits::Act;
+    /// use nekolib::utils::OpAffineOnOpAddCount;
+    ///
+    /// let mut st: VecActSegtree<OpAffineOnOpAddCount<i64>> =
+    ///     vec![(1, 1), (2, 1), (3, 1)].into();
+    /// st.act(1.., (2, 1));
+    /// assert_eq!(st.get(0), (1, 1));
+    /// assert_eq!(st.get(2), (7, 1));
+    /// ```
+    pub fn get(&self, i: usize) -> <A::Operand as Magma>::Set {
+        check_bounds(i, self.len);
+        self.force_range(i, i + 1);
+        self.buf.borrow()[self.len + i].clone()
+    }
+
+    /// $i$ 番目の要素を $x$ にする。
+    ///
+    /// # Panics
+    /// `i >= self.len()` のとき。
+    ///
+    /// # Complexity
+    /// $O(\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::VecActSegtree;
+    /// use nekolib::traits::{Act, Fold};
+    /// use nekolib::utils::OpAffineOnOpAddCount;
+    ///
+    /// let mut st: VecActSegtree<OpAffineOnOpAddCount<i64>> =
+    ///     vec![(1, 1), (2, 1), (3, 1)].into();
+    /// st.act(.., (2, 0));
+    /// st.set(1, (10, 1));
+    /// assert_eq!(st.fold(..), (18, 3));
+    /// st.act(..2, (1, 1));
+    /// assert_eq!(st.get(1), (11, 1));
+    /// ```
+    pub fn set(&mut self, i: usize, x: <A::Operand as Magma>::Set) {
+        check_bounds(i, self.len);
+        self.force_range(i, i + 1);
+        self.buf.borrow_mut()[self.len + i] = x;
+        self.build_range(i, i + 1);
+    }
+
     fn arch_pair(&self, l: usize, r: usize) -> (Vec<usize>, Vec<usize>) {
         let mut l = self.len + l;
         let mut r = self.len + r;
@@ -249,6 +307,57 @@ where
     }
 }
 
+impl<A> FromIterator<<A::Operand as Magma>::Set> for VecActSegtree<A>
+where
+    A: MonoidAction + Default,
+    <A::Operator as Magma>::Set: Clone,
+    <A::Operand as Magma>::Set: Clone,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = <A::Operand as Magma>::Set>,
+    {
+        Self::from_iter_with_action(iter, A::default())
+    }
+}
+
+impl<A> VecActSegtree<A>
+where
+    A: MonoidAction,
+    <A::Operator as Magma>::Set: Clone,
+    <A::Operand as Magma>::Set: Clone,
+{
+    /// イテレータの要素と作用 `action` から構築する。
+    ///
+    /// `A: Default` であれば `collect()` でも構築できる。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::VecActSegtree;
+    /// use nekolib::traits::{Act, Fold};
+    /// use nekolib::utils::OpAffineOnOpAddCount;
+    ///
+    /// let action = OpAffineOnOpAddCount::<i64>::default();
+    /// let mut st = VecActSegtree::from_iter_with_action(
+    ///     (1..=4).map(|x| (x, 1)),
+    ///     action,
+    /// );
+    /// st.act(1..3, (2, 0));
+    /// assert_eq!(st.fold(..), (15, 4));
+    ///
+    /// let st: VecActSegtree<OpAffineOnOpAddCount<i64>> =
+    ///     (1..=4).map(|x| (x, 1)).collect();
+    /// assert_eq!(st.fold(..), (10, 4));
+    /// ```
+    pub fn from_iter_with_action<I>(iter: I, action: A) -> Self
+    where
+        I: IntoIterator<Item = <A::Operand as Magma>::Set>,
+    {
+        let v: Vec<_> = iter.into_iter().collect();
+        Self::from((v, action))
+    }
+}
+
 impl<A> Debug for VecActSegtree<A>
 where
     A: MonoidAction,
@@ -416,6 +525,66 @@ where
     }
 }
 
+impl<A> VecActSegtree<A>
+where
+    A: MonoidAction,
+    <A::Operator as Magma>::Set: Clone,
+    <A::Operand as Magma>::Set: Clone,
+{
+    /// 区間 $[l, r)$ の和が `threshold` 以上となる最小の $r$ を返す。
+    ///
+    /// 作用される型が [`OpAddCount`] のとき、和の成分を `threshold` と比較する。
+    /// `threshold` が $0$ 以下のときは `Some(l)` を返す。
+    /// $[l, n)$ の和が `threshold` 未満のときは `None` を返す。
+    ///
+    /// [`fold_bisect`](#method.fold_bisect) に委譲する。
+    ///
+    /// # Requirements
+    /// 各要素の和の成分が非負である。
+    ///
+    /// # Panics
+    /// `l > self.len()` のとき。
+    ///
+    /// # Complexity
+    /// $O(\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::VecActSegtree;
+    /// use nekolib::traits::Act;
+    /// use nekolib::utils::OpAffineOnOpAddCount;
+    ///
+    /// let mut st: VecActSegtree<OpAffineOnOpAddCount<i64>> =
+    ///     vec![(2, 1), (4, 1), (1, 1), (3, 1), (5, 1)].into();
+    ///
+    /// assert_eq!(st.fold_bisect_ge(1, 0), Some(1));
+    /// assert_eq!(st.fold_bisect_ge(1, 4), Some(2));
+    /// assert_eq!(st.fold_bisect_ge(1, 5), Some(3));
+    /// assert_eq!(st.fold_bisect_ge(1, 13), Some(5));
+    /// assert_eq!(st.fold_bisect_ge(1, 14), None);
+    ///
+    /// st.act(2..4, (2, 0));
+    /// assert_eq!(st.fold_bisect_ge(1, 12), Some(4));
+    /// ```
+    ///
+    /// [`OpAddCount`]: ../../utils/op_add_count/enum.OpAddCount.html
+    pub fn fold_bisect_ge<T>(&self, l: usize, threshold: T) -> Option<usize>
+    where
+        A: MonoidAction<Operand = OpAddCount<T>>,
+        T: Ord + Clone + Add<Output = T> + Zero,
+    {
+        check_bounds_range(l, 0..=self.len);
+
+        if threshold <= T::zero() {
+            return Some(l);
+        }
+        match self.fold_bisect(l, |(x, _)| *x < threshold) {
+            (r, _) if r == self.len => None,
+            (r, _) => Some(r + 1),
+        }
+    }
+}
+
 #[doc(hidden)]
 pub struct GetMutIndex<'a, A>
 where
@@ -717,3 +886,150 @@ fn test_get_mut_n(n: usize) {
         assert_eq!(Vec::<_>::from(st), naive);
     }
 }
+
+#[test]
+fn test_get_set() {
+    use op_affine_on_op_add_count::OpAffineOnOpAddCount;
+
+    let mut f = std::iter::successors(Some(4649_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = |n: u64| f.next().unwrap() % n;
+
+    for n in 1..=40 {
+        let mut naive: Vec<i128> = (0..n).map(|_| rand(10) as i128).collect();
+        let a: Vec<_> = naive.iter().map(|&x| (x, 1)).collect();
+        let mut st: VecActSegtree<OpAffineOnOpAddCount<i128>> = a.into();
+        for _ in 0..300 {
+            let l = rand(n as u64 + 1) as usize;
+            let r = l + rand((n - l) as u64 + 1) as usize;
+            let i = rand(n as u64) as usize;
+            match rand(4) {
+                0 => {
+                    let (a, b) = (rand(3) as i128, rand(10) as i128 - 5);
+                    st.act(l..r, (a, b));
+                    for x in &mut naive[l..r] {
+                        *x = a * *x + b;
+                    }
+                }
+                1 => {
+                    let x = rand(100) as i128 - 50;
+                    st.set(i, (x, 1));
+                    naive[i] = x;
+                }
+                2 => assert_eq!(st.get(i), (naive[i], 1)),
+                _ => {
+                    let sum = naive[l..r].iter().sum();
+                    assert_eq!(st.fold(l..r), (sum, (r - l) as i128));
+                }
+            }
+        }
+        let actual: Vec<_> = (0..n).map(|i| st.get(i).0).collect();
+        assert_eq!(actual, naive);
+    }
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_get_out_of_bounds() {
+    use op_affine_on_op_add_count::OpAffineOnOpAddCount;
+
+    let st: VecActSegtree<OpAffineOnOpAddCount<i128>> = vec![(0, 1); 3].into();
+    st.get(3);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_set_empty() {
+    use op_affine_on_op_add_count::OpAffineOnOpAddCount;
+
+    let mut st: VecActSegtree<OpAffineOnOpAddCount<i128>> = vec![].into();
+    st.set(0, (1, 1));
+}
+
+#[test]
+fn test_from_iter() {
+    use op_affine_on_op_add_count::OpAffineOnOpAddCount;
+
+    type Tree = VecActSegtree<OpAffineOnOpAddCount<i128>>;
+
+    let empty: Tree = std::iter::empty().collect();
+    assert!(empty.is_empty());
+    assert_eq!(empty.fold(..), (0, 0));
+    assert!(Vec::<_>::from(empty).is_empty());
+
+    for n in 0..=40 {
+        let a: Vec<_> = (0..n).map(|i| (i * i % 7, 1)).collect();
+        let st: Tree = a.iter().copied().collect();
+        assert_eq!(st.len(), n as usize);
+        assert_eq!(Vec::<_>::from(st), a);
+
+        let action = OpAffineOnOpAddCount::default();
+        let mut st = Tree::from_iter_with_action(a.clone(), action);
+        let mut naive = a.clone();
+        for l in 0..n as usize {
+            st.act(l.., (2, 1));
+            for x in &mut naive[l..] {
+                x.0 = 2 * x.0 + 1;
+            }
+        }
+        let expected: Tree = naive.clone().into();
+        for l in 0..=n as usize {
+            for r in l..=n as usize {
+                assert_eq!(st.fold(l..r), expected.fold(l..r));
+            }
+        }
+        assert_eq!(Vec::<_>::from(st), naive);
+    }
+}
+
+#[test]
+fn test_fold_bisect_ge() {
+    use op_affine_on_op_add_count::OpAffineOnOpAddCount;
+
+    type Tree = VecActSegtree<OpAffineOnOpAddCount<i64>>;
+
+    let empty: Tree = vec![].into();
+    assert_eq!(empty.fold_bisect_ge(0, 0), Some(0));
+    assert_eq!(empty.fold_bisect_ge(0, 1), None);
+
+    let mut f = std::iter::successors(Some(3141_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = || f.next().unwrap();
+    for n in 1..=40 {
+        let mut naive: Vec<_> =
+            (0..n).map(|_| ((rand() % 4) as i64, 1)).collect();
+        let mut st: Tree = naive.clone().into();
+        for _ in 0..10 {
+            let l = rand() as usize % n;
+            let r = l + rand() as usize % (n - l) + 1;
+            let (a, b) = ((rand() % 3) as i64, (rand() % 3) as i64);
+            st.act(l..r, (a, b));
+            for x in &mut naive[l..r] {
+                x.0 = a * x.0 + b;
+            }
+
+            let sum: i64 = naive.iter().map(|x| x.0).sum();
+            for l in 0..=n {
+                assert_eq!(st.fold_bisect_ge(l, -1), Some(l));
+                assert_eq!(st.fold_bisect_ge(l, 0), Some(l));
+                for th in 1..=sum + 1 {
+                    let expected = (l..=n).find(|&r| {
+                        naive[l..r].iter().map(|x| x.0).sum::<i64>() >= th
+                    });
+                    assert_eq!(st.fold_bisect_ge(l, th), expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_fold_bisect_ge_out_of_bounds() {
+    use op_affine_on_op_add_count::OpAffineOnOpAddCount;
+
+    let st: VecActSegtree<OpAffineOnOpAddCount<i64>> = vec![(1, 1)].into();
+    st.fold_bisect_ge(2, 1);
+}
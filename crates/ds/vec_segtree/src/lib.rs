@@ -195,10 +195,10 @@ where
         let mut i = self.tree.len + self.index;
         while i > 1 {
             i >>= 1;
-            self.tree.buf[i] = self.tree.monoid.op(
-                self.tree.buf[i << 1].clone(),
-                self.tree.buf[i << 1 | 1].clone(),
-            );
+            self.tree.buf[i] = self
+                .tree
+                .monoid
+                .op_ref(&self.tree.buf[i << 1], &self.tree.buf[i << 1 | 1]);
         }
     }
 }
@@ -250,7 +250,7 @@ where
         let mut buf = vec![monoid.id(); len];
         buf.append(&mut v);
         for i in (0..len).rev() {
-            buf[i] = monoid.op(buf[i << 1].clone(), buf[i << 1 | 1].clone());
+            buf[i] = monoid.op_ref(&buf[i << 1], &buf[i << 1 | 1]);
         }
         Self { buf, len, monoid }
     }
@@ -301,13 +301,9 @@ where
 
         let mut x = self.monoid.id();
         assert!(pred(&x), "`pred(id)` must hold");
-        match self.fold(l..) {
-            x if pred(&x) => return (self.len, x),
-            _ => {}
-        }
 
         for v in self.nodes(l, self.len) {
-            let tmp = self.monoid.op(x.clone(), self.buf[v].clone());
+            let tmp = self.monoid.op_ref(&x, &self.buf[v]);
             if pred(&tmp) {
                 x = tmp;
                 continue;
@@ -315,7 +311,7 @@ where
             let mut v = v;
             while v < self.len {
                 v <<= 1;
-                let tmp = self.monoid.op(x.clone(), self.buf[v].clone());
+                let tmp = self.monoid.op_ref(&x, &self.buf[v]);
                 if pred(&tmp) {
                     x = tmp;
                     v += 1;
@@ -323,7 +319,7 @@ where
             }
             return (v - self.len, x);
         }
-        unreachable!();
+        (self.len, x)
     }
 }
 
@@ -340,13 +336,9 @@ where
 
         let mut x = self.monoid.id();
         assert!(pred(&x), "`pred(id)` must hold");
-        match self.fold(..r) {
-            x if pred(&x) => return (0, x),
-            _ => {}
-        }
 
         for v in self.nodes_rev(0, r) {
-            let tmp = self.monoid.op(self.buf[v].clone(), x.clone());
+            let tmp = self.monoid.op_ref(&self.buf[v], &x);
             if pred(&tmp) {
                 x = tmp;
                 continue;
@@ -354,7 +346,7 @@ where
             let mut v = v;
             while v < self.len {
                 v = v << 1 | 1;
-                let tmp = self.monoid.op(self.buf[v].clone(), x.clone());
+                let tmp = self.monoid.op_ref(&self.buf[v], &x);
                 if pred(&tmp) {
                     x = tmp;
                     v -= 1;
@@ -362,6 +354,132 @@ where
             }
             return (v - self.len + 1, x);
         }
-        unreachable!();
+        (0, x)
+    }
+}
+
+#[test]
+fn test_op_ref() {
+    use binop::{Associative, Identity, Magma};
+
+    #[derive(Default)]
+    struct Concat;
+    impl Magma for Concat {
+        type Set = String;
+        fn op(&self, x: String, y: String) -> String { x + &y }
+    }
+    impl Associative for Concat {}
+    impl Identity for Concat {
+        fn id(&self) -> String { String::new() }
+    }
+
+    #[derive(Default)]
+    struct ConcatRef;
+    impl Magma for ConcatRef {
+        type Set = String;
+        fn op(&self, x: String, y: String) -> String { x + &y }
+        fn op_ref(&self, x: &String, y: &String) -> String { x.clone() + y }
+    }
+    impl Associative for ConcatRef {}
+    impl Identity for ConcatRef {
+        fn id(&self) -> String { String::new() }
+    }
+
+    let s = "abcdefghijklm";
+    let a: Vec<_> = s.chars().map(|c| c.to_string()).collect();
+    let vs: VecSegtree<Concat> = a.clone().into();
+    let mut vs_ref: VecSegtree<ConcatRef> = a.into();
+    let n = s.len();
+    for l in 0..=n {
+        for r in l..=n {
+            assert_eq!(vs.fold(l..r), s[l..r]);
+            assert_eq!(vs_ref.fold(l..r), s[l..r]);
+        }
+        let pred = |x: &String| x.len() <= 3;
+        assert_eq!(vs.fold_bisect(l, pred).0, (l + 3).min(n));
+        assert_eq!(vs.fold_bisect_rev(l, pred).0, l.saturating_sub(3));
+        assert_eq!(vs.fold_bisect(l, pred), vs_ref.fold_bisect(l, pred));
+        assert_eq!(
+            vs.fold_bisect_rev(l, pred),
+            vs_ref.fold_bisect_rev(l, pred)
+        );
+    }
+
+    *vs_ref.get_mut(3).unwrap() = "xyz".to_string();
+    assert_eq!(vs_ref.fold(..), "abcxyzefghijklm");
+}
+
+#[test]
+fn test_clone_count() {
+    use std::cell::Cell;
+
+    use binop::{Associative, Identity, Magma};
+
+    thread_local! {
+        static CLONES: Cell<usize> = const { Cell::new(0) };
+    }
+    fn clones() -> usize { CLONES.with(|c| c.replace(0)) }
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct Counted(u32);
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            CLONES.with(|c| c.set(c.get() + 1));
+            Counted(self.0)
+        }
+    }
+
+    #[derive(Default)]
+    struct Sum;
+    impl Magma for Sum {
+        type Set = Counted;
+        fn op(&self, x: Counted, y: Counted) -> Counted { Counted(x.0 + y.0) }
+    }
+    impl Associative for Sum {}
+    impl Identity for Sum {
+        fn id(&self) -> Counted { Counted(0) }
+    }
+
+    #[derive(Default)]
+    struct SumRef;
+    impl Magma for SumRef {
+        type Set = Counted;
+        fn op(&self, x: Counted, y: Counted) -> Counted { Counted(x.0 + y.0) }
+        fn op_ref(&self, x: &Counted, y: &Counted) -> Counted {
+            Counted(x.0 + y.0)
+        }
+    }
+    impl Associative for SumRef {}
+    impl Identity for SumRef {
+        fn id(&self) -> Counted { Counted(0) }
+    }
+
+    let n = 13;
+    let a: Vec<_> = (0..n as u32).map(Counted).collect();
+
+    // fold では各ノードを一度ずつ clone し、累積値は clone しない
+    let vs: VecSegtree<Sum> = a.clone().into();
+    clones();
+    for l in 0..=n {
+        for r in l..=n {
+            assert_eq!(vs.fold(l..r), Counted((l as u32..r as u32).sum()));
+            assert_eq!(clones(), vs.nodes(l, r).len());
+        }
+    }
+
+    // 構築と更新、二分探索では op_ref のみを使う（単位元の初期化による clone は除く）
+    clones();
+    let mut vs: VecSegtree<SumRef> = a.into();
+    assert!(clones() < n);
+    *vs.get_mut(3).unwrap() = Counted(100);
+    assert_eq!(clones(), 0);
+    assert_eq!(vs.fold(..), Counted(78 - 3 + 100));
+    for i in 0..=n {
+        let pred = |x: &Counted| x.0 <= 50;
+        clones();
+        let (r, x) = vs.fold_bisect(i, pred);
+        let (l, y) = vs.fold_bisect_rev(i, pred);
+        assert_eq!(clones(), 0);
+        assert_eq!((vs.fold(i..r), vs.fold(l..i)), (x, y));
     }
 }
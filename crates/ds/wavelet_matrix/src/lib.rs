@@ -1,8 +1,10 @@
 //! wavelet matrix。
 
-use std::ops::{Index, Range, RangeBounds, RangeInclusive};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::{Add, Deref, Index, Range, RangeBounds, RangeInclusive, Sub};
 
-use buf_range::bounds_within;
+use buf_range::{bounds_within, check_bounds};
 use count::{Count, Count3way, Count3wayResult};
 use find_nth::FindNth;
 use quantile::Quantile;
@@ -39,6 +41,21 @@ use rs_dict::RsDict;
 /// assert_eq!(wm.find_nth(5.., 2, 0), Some(7));
 /// assert_eq!(wm.find_nth(5.., 2, 1), None);
 /// ```
+///
+/// 符号付き整数も扱える。
+/// 内部では符号ビットを反転させた値（i.e. $x-\\mathtt{MIN}$）で管理するので、
+/// $\\log(\\sigma)$ は型のビット幅となる。
+/// ```
+/// use nekolib::ds::WaveletMatrix;
+/// use nekolib::traits::{Count3way, Quantile};
+///
+/// let wm: WaveletMatrix<i32> = vec![-3, 1, -4, 1, -5, 9, -2].into();
+/// assert_eq!(wm.quantile(.., 0), Some(-5));
+/// assert_eq!(wm.quantile(2..5, 1), Some(-4)); // [-4, 1, -5]
+///
+/// let c3 = wm.count_3way(.., -3..=1); // [-3, 1, -4, 1, -5, 9, -2]
+/// assert_eq!((c3.lt(), c3.eq(), c3.gt()), (2, 4, 1));
+/// ```
 pub struct WaveletMatrix<I> {
     len: usize,
     bitlen: usize,
@@ -58,13 +75,15 @@ impl<I: WmInt> From<Vec<I>> for WaveletMatrix<I> {
         for i in (0..bitlen).rev() {
             let mut zero = vec![];
             let mut one = vec![];
-            let mut vb = vec![false; len];
+            let mut vb = vec![0_u64; len.div_ceil(64)];
             for (j, aj) in whole.into_iter().enumerate() {
                 (if aj.test(i) { &mut one } else { &mut zero }).push(aj);
-                vb[j] = aj.test(i);
+                if aj.test(i) {
+                    vb[j / 64] |= 1 << (j % 64);
+                }
             }
             zeros[i] = zero.len();
-            buf.push(vb.into());
+            buf.push((vb, len).into());
             whole = zero;
             whole.append(&mut one);
         }
@@ -224,10 +243,10 @@ impl<I: WmInt> FindNth<I> for WaveletMatrix<I> {
         value: I,
         n: usize,
     ) -> Option<usize> {
-        let start = bounds_within(range, self.len).start;
+        let Range { start, end } = bounds_within(range, self.len);
         let (lt, gt) = self.count_3way_internal(0..start, value);
         let offset = start - (lt + gt);
-        Some(self.select(value, n + offset + 1)? - 1)
+        Some(self.select(value, n + offset + 1)? - 1).filter(|&i| i < end)
     }
 }
 
@@ -235,9 +254,251 @@ impl<I: WmInt> WaveletMatrix<I> {
     pub fn len(&self) -> usize { self.len }
     pub fn is_empty(&self) -> bool { self.len == 0 }
 
+    /// `self` の後ろに `other` をつなげた配列の wavelet matrix を返す。
+    ///
+    /// 元の配列をつなげてから作り直すので、$O((n+m)\\log(\\sigma))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::WaveletMatrix;
+    /// use nekolib::traits::Quantile;
+    ///
+    /// let wm: WaveletMatrix<u32> = vec![1, 8, 4].into();
+    /// let other: WaveletMatrix<u32> = vec![9, 2, 7].into();
+    ///
+    /// let wm = wm.concat(other);
+    /// assert_eq!(wm.len(), 6);
+    /// assert_eq!(wm[3], 9);
+    /// assert_eq!(wm.quantile(1..5, 1), Some(4)); // [8, 4, 9, 2]
+    /// ```
+    pub fn concat(self, other: Self) -> Self {
+        let mut orig = self.orig;
+        orig.extend(other.orig);
+        orig.into()
+    }
+
+    /// $i$ 番目と $i+1$ 番目の要素を入れ替える。
+    ///
+    /// # Idea
+    /// 上位ビットの層から見ていくと、二つの要素はビットが一致している間は
+    /// 各層で隣接したまま同じ側に移るので、次の層での位置のみを更新する。
+    /// 初めてビットが異なる層では、その層のビットを入れ替えればよく、
+    /// 次の層以降での二つの要素の位置は入れ替える前と変わらない。
+    /// 二つの要素が等しいときは何もしない。
+    ///
+    /// # Panics
+    /// $i+1 \\ge n$ のとき。
+    ///
+    /// # Complexity
+    /// $O(\\log(\\sigma))$ time.
+    /// ただし、[`RsDict::swap`] で前計算を作り直す場合は $O(w)$ time が加わる。
+    ///
+    /// [`RsDict::swap`]: ../rs_dict/struct.RsDict.html#method.swap
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::WaveletMatrix;
+    /// use nekolib::traits::{Count3way, Quantile};
+    ///
+    /// let mut wm: WaveletMatrix<u32> = vec![1, 8, 4, 9, 2, 7, 5, 2].into();
+    /// wm.swap(2); // [1, 8, 9, 4, 2, 7, 5, 2]
+    /// wm.swap(3); // [1, 8, 9, 2, 4, 7, 5, 2]
+    /// assert_eq!(wm[2], 9);
+    /// assert_eq!(wm.quantile(..4, 1), Some(2)); // [1, 8, 9, 2]
+    /// assert_eq!(wm.count_3way(..4, 4).lt(), 2); // [1, _, _, 2]
+    /// ```
+    pub fn swap(&mut self, i: usize) {
+        check_bounds(i + 1, self.len);
+        let (a, b) = (self.orig[i], self.orig[i + 1]);
+        if (0..self.bitlen).all(|j| a.test(j) == b.test(j)) {
+            return;
+        }
+        self.orig.swap(i, i + 1);
+
+        let mut p = i;
+        for j in (0..self.bitlen).rev() {
+            if a.test(j) != b.test(j) {
+                self.buf[j].swap(p);
+                break;
+            }
+            p = if !a.test(j) {
+                self.buf[j].rank(p, 0)
+            } else {
+                self.zeros[j] + self.buf[j].rank(p, 1)
+            };
+        }
+    }
+
+    /// 区間 `range` の値のうち、`value` 以下で最大のものを返す。
+    ///
+    /// そのような値がなければ `None` を返す。
+    ///
+    /// # Complexity
+    /// $O(\\log(\\sigma))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::WaveletMatrix;
+    ///
+    /// let wm: WaveletMatrix<u32> = vec![1, 8, 4, 9, 2, 7, 5, 2].into();
+    /// assert_eq!(wm.prev_value(.., 6), Some(5));
+    /// assert_eq!(wm.prev_value(.., 7), Some(7));
+    /// assert_eq!(wm.prev_value(1..5, 3), Some(2)); // [8, 4, 9, 2]
+    /// assert_eq!(wm.prev_value(1..5, 1), None);
+    /// ```
+    pub fn prev_value(
+        &self,
+        range: impl RangeBounds<usize>,
+        value: I,
+    ) -> Option<I> {
+        let Range { start, end } = bounds_within(range, self.len);
+        let gt = self.count_3way_internal(start..end, value).1;
+        let le = (end - start) - gt;
+        if le == 0 { None } else { self.quantile(start..end, le - 1) }
+    }
+
+    /// 区間 `range` の値のうち、`value` 以上で最小のものを返す。
+    ///
+    /// そのような値がなければ `None` を返す。
+    ///
+    /// # Complexity
+    /// $O(\\log(\\sigma))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::WaveletMatrix;
+    ///
+    /// let wm: WaveletMatrix<u32> = vec![1, 8, 4, 9, 2, 7, 5, 2].into();
+    /// assert_eq!(wm.next_value(.., 6), Some(7));
+    /// assert_eq!(wm.next_value(.., 5), Some(5));
+    /// assert_eq!(wm.next_value(4..8, 3), Some(5)); // [2, 7, 5, 2]
+    /// assert_eq!(wm.next_value(4..8, 8), None);
+    /// ```
+    pub fn next_value(
+        &self,
+        range: impl RangeBounds<usize>,
+        value: I,
+    ) -> Option<I> {
+        let Range { start, end } = bounds_within(range, self.len);
+        let lt = self.count_3way_internal(start..end, value).0;
+        self.quantile(start..end, lt)
+    }
+
+    /// 区間 `range` に出現する値のうち、出現回数の多いものから $k$ 個を
+    /// `(値, 回数)` の組として返す。
+    ///
+    /// 出現回数が等しいものは値の昇順に並べる。
+    /// 区間中の相異なる値が $k$ 個未満の場合は、それらをすべて返す。
+    ///
+    /// # Complexity
+    /// 区間を $[l, r)$、$k$ 番目に返す値の出現回数を $c$ とする（返す値が
+    /// $k$ 個に満たない場合は $c = 1$ とする）。
+    /// 要素数が $c$ 以上のノードのみを取り出すので、各層で取り出すノードは
+    /// 高々 $\\min((r-l)/c, \\sigma)$ 個であり、その総数を
+    /// $P = \\min((r-l)/c, \\sigma)\\log(\\sigma)$ として $O(P\\log(P))$ time.
+    ///
+    /// $k$ が小さくても $c$ が小さければ遅い。たとえば値がすべて相異なるときは
+    /// $k = 1$ でも $\\Theta(\\min(r-l, \\sigma))$ 個のノードを取り出し、
+    /// $O(\\min(r-l, \\sigma)\\log(\\sigma)\\log(r-l))$ time となる。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::WaveletMatrix;
+    ///
+    /// let wm: WaveletMatrix<u32> = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5].into();
+    /// assert_eq!(wm.topk(.., 3), [(5, 3), (1, 2), (3, 2)]);
+    /// assert_eq!(wm.topk(..5, 2), [(1, 2), (3, 1)]);
+    /// assert_eq!(wm.topk(4..6, 10), [(5, 1), (9, 1)]);
+    /// assert!(wm.topk(.., 0).is_empty());
+    /// ```
+    pub fn topk(
+        &self,
+        range: impl RangeBounds<usize>,
+        k: usize,
+    ) -> Vec<(I, usize)>
+    where
+        I: Ord,
+    {
+        let Range { start, end } = bounds_within(range, self.len);
+        let mut res = vec![];
+        // (要素数, 値の下限, 残りの bit 数, 始端)。同じ要素数のノードは値の下限が
+        // 小さい方を先に見るので、葉として確定する順が規約と一致する。
+        let mut pq = BinaryHeap::new();
+        if start < end {
+            pq.push((end - start, Reverse(I::zero()), self.bitlen, start));
+        }
+        while let Some((count, Reverse(value), rem, start)) = pq.pop() {
+            if res.len() >= k {
+                break;
+            }
+            if rem == 0 {
+                res.push((value, count));
+                continue;
+            }
+            let i = rem - 1;
+            let end = start + count;
+            let start0 = self.buf[i].rank(start, 0);
+            let end0 = self.buf[i].rank(end, 0);
+            if start0 < end0 {
+                pq.push((end0 - start0, Reverse(value), i, start0));
+            }
+            if end - end0 > start - start0 {
+                let mut value = value;
+                value.set(i);
+                let start1 = self.zeros[i] + (start - start0);
+                let count1 = (end - end0) - (start - start0);
+                pq.push((count1, Reverse(value), i, start1));
+            }
+        }
+        res
+    }
+
     pub fn rank(&self, end: usize, value: I) -> usize {
         self.count(0..end, value)
     }
+
+    /// 区間 `range` の元の値を `Vec` で返す。
+    ///
+    /// 構築時の配列を保持しているので、それを切り出して返す。
+    /// 空区間に対しては空の `Vec` を返す。
+    ///
+    /// # Panics
+    /// 区間の終端が `self.len()` より大きいとき。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::WaveletMatrix;
+    ///
+    /// let wm: WaveletMatrix<u32> = vec![1, 8, 4, 9, 2].into();
+    /// assert_eq!(wm.to_vec_range(1..4), [8, 4, 9]);
+    /// assert_eq!(wm.to_vec_range(3..), [9, 2]);
+    /// assert_eq!(wm.to_vec_range(..), [1, 8, 4, 9, 2]);
+    /// assert!(wm.to_vec_range(2..2).is_empty());
+    /// ```
+    pub fn to_vec_range(&self, range: impl RangeBounds<usize>) -> Vec<I> {
+        let Range { start, end } = bounds_within(range, self.len);
+        self.orig[start..end].to_vec()
+    }
+
+    /// `self[i]` が、先頭から数えて何番目（0-indexed）の出現かを返す。
+    ///
+    /// `self.rank(i, self[i])` と等しい。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::WaveletMatrix;
+    /// use nekolib::traits::FindNth;
+    ///
+    /// let wm: WaveletMatrix<u32> = vec![1, 8, 1, 9, 8, 1].into();
+    /// assert_eq!(wm.occurrence_index(0), 0);
+    /// assert_eq!(wm.occurrence_index(2), 1);
+    /// assert_eq!(wm.occurrence_index(4), 1);
+    /// assert_eq!(wm.occurrence_index(5), 2);
+    /// assert_eq!(wm.find_nth(.., wm[5], wm.occurrence_index(5)), Some(5));
+    /// ```
+    pub fn occurrence_index(&self, i: usize) -> usize {
+        self.rank(i, self.orig[i])
+    }
     pub fn select(&self, value: I, mut n: usize) -> Option<usize> {
         if n == 0 {
             return Some(0);
@@ -247,6 +508,10 @@ impl<I: WmInt> WaveletMatrix<I> {
         if count < n {
             return None;
         }
+        if self.bitlen == 0 {
+            // すべて 0 なので、n 番目の出現は n - 1 番目
+            return Some(n);
+        }
         let si = self.start_pos(value);
         let value0 = value.test(0) as u64;
         n += self.buf[0].rank(si, value0);
@@ -254,14 +519,56 @@ impl<I: WmInt> WaveletMatrix<I> {
 
         for i in 1..self.bitlen {
             if !value.test(i) {
-                n = self.buf[i].select(0, n).unwrap();
+                n = self.buf[i].select0(n).unwrap();
             } else {
                 n -= self.zeros[i];
-                n = self.buf[i].select(1, n).unwrap();
+                n = self.buf[i].select1(n).unwrap();
             }
         }
         Some(n)
     }
+    /// 内部表現の不変条件を検査する。
+    ///
+    /// 以下が成り立つことを確かめる。
+    /// - `buf`、`zeros` の長さはともに `bitlen` であり、各 `buf[i]` の長さは
+    ///   `len` である。また、`orig` の各要素は `bitlen` bits で表せる。
+    /// - 上位ビットの層から順に、前の層の列を $i$ bit 目で安定ソートした列を
+    ///   考えると、`buf[i]` はその列の各要素の $i$ bit 目からなる。
+    /// - `zeros[i]` は `buf[i]` の `0` の個数である。
+    /// - `rank` と `select` は互いに逆関数である。すなわち、`orig`
+    ///   に現れる各値 $x$ と $1\\le k\\le \\mathtt{rank}\_x(n)$ に対して
+    ///   $s = \\mathtt{select}\_x(k)$ とおくと、$\\mathtt{rank}\_x(s) = k$
+    ///   かつ `orig[s - 1]` は $x$ である。
+    #[cfg(test)]
+    fn check_invariants(&self) {
+        let n = self.len;
+        assert_eq!(self.orig.len(), n);
+        assert_eq!(self.buf.len(), self.bitlen);
+        assert_eq!(self.zeros.len(), self.bitlen);
+        assert!(self.orig.iter().all(|ai| ai.bitlen() <= self.bitlen));
+
+        let mut whole = self.orig.clone();
+        for i in (0..self.bitlen).rev() {
+            let rs = &self.buf[i];
+            assert_eq!(rs.count(.., 0) + rs.count(.., 1), n);
+            assert_eq!(rs.count(.., 0), self.zeros[i]);
+            for (j, aj) in whole.iter().enumerate() {
+                assert_eq!(rs.count(j..=j, 1) == 1, aj.test(i));
+            }
+            let (zero, one): (Vec<_>, Vec<_>) =
+                whole.into_iter().partition(|aj| !aj.test(i));
+            whole = zero;
+            whole.extend(one);
+        }
+
+        for (i, &ai) in self.orig.iter().enumerate() {
+            let k = self.rank(i + 1, ai);
+            let s = self.select(ai, k).unwrap();
+            assert_eq!(self.rank(s, ai), k);
+            assert_eq!(s, i + 1);
+            assert_eq!(self.select(ai, self.rank(n, ai) + 1), None);
+        }
+    }
     fn start_pos(&self, value: I) -> usize {
         let mut start = 0;
         let mut end = 0;
@@ -283,16 +590,201 @@ impl<I: WmInt> Index<usize> for WaveletMatrix<I> {
     fn index(&self, i: usize) -> &I { &self.orig[i] }
 }
 
+/// 累積和を併せて持つ wavelet matrix。
+///
+/// [`WaveletMatrix`] に加えて区間和のクエリ [`sum`] と [`sum_lt`] に答える。
+/// 各層の累積和を [`WmInt::Wide`] 型で持つので、その分のメモリを余計に使う。
+/// それ以外のクエリは [`Deref`] を介して [`WaveletMatrix`] のものを呼び出せる。
+///
+/// [`sum`]: #method.sum
+/// [`sum_lt`]: #method.sum_lt
+///
+/// # Complexity
+/// $O(n\\log(\\sigma))$ space.
+///
+/// # Examples
+/// ```
+/// use nekolib::ds::WaveletMatrixSum;
+/// use nekolib::traits::Quantile;
+///
+/// let wm: WaveletMatrixSum<u32> = vec![1, 8, 4, 9, 2, 7, 5, 2].into();
+/// assert_eq!(wm.sum(2..5), 15); // [4, 9, 2]
+/// assert_eq!(wm.quantile(2..5, 1), Some(4)); // [2, 4, 9]
+/// ```
+pub struct WaveletMatrixSum<I: WmInt> {
+    wm: WaveletMatrix<I>,
+    sums: Vec<Vec<I::Wide>>,
+    orig_sum: Vec<I::Wide>,
+}
+
+impl<I: WmInt> From<Vec<I>> for WaveletMatrixSum<I> {
+    fn from(orig: Vec<I>) -> Self { WaveletMatrix::from(orig).into() }
+}
+
+impl<I: WmInt> From<WaveletMatrix<I>> for WaveletMatrixSum<I> {
+    fn from(wm: WaveletMatrix<I>) -> Self {
+        let mut whole = wm.orig.clone();
+        let mut sums = vec![vec![]; wm.bitlen];
+        for i in (0..wm.bitlen).rev() {
+            let (zero, one): (Vec<_>, Vec<_>) =
+                whole.into_iter().partition(|&aj| !aj.test(i));
+            whole = zero;
+            whole.extend(one);
+            sums[i] = prefix_sum(&whole);
+        }
+        let orig_sum = prefix_sum(&wm.orig);
+        Self { wm, sums, orig_sum }
+    }
+}
+
+fn prefix_sum<I: WmInt>(a: &[I]) -> Vec<I::Wide> {
+    let mut res = vec![I::Wide::default()];
+    for &ai in a {
+        res.push(*res.last().unwrap() + ai.widen());
+    }
+    res
+}
+
+impl<I: WmInt> Deref for WaveletMatrixSum<I> {
+    type Target = WaveletMatrix<I>;
+    fn deref(&self) -> &WaveletMatrix<I> { &self.wm }
+}
+
+impl<I: WmInt> WaveletMatrixSum<I> {
+    /// 累積和を捨てて [`WaveletMatrix`] を返す。
+    pub fn into_inner(self) -> WaveletMatrix<I> { self.wm }
+
+    /// $i$ 番目と $i+1$ 番目の要素を入れ替える。
+    ///
+    /// [`WaveletMatrix::swap`] と同様に、二つの要素のビットが一致している層の
+    /// 累積和を一箇所ずつ更新する。
+    ///
+    /// [`WaveletMatrix::swap`]: struct.WaveletMatrix.html#method.swap
+    ///
+    /// # Panics
+    /// $i+1 \\ge n$ のとき。
+    ///
+    /// # Complexity
+    /// $O(\\log(\\sigma))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::WaveletMatrixSum;
+    ///
+    /// let mut wm: WaveletMatrixSum<u32> = vec![1, 8, 4, 9, 2].into();
+    /// wm.swap(1); // [1, 4, 8, 9, 2]
+    /// assert_eq!(wm.sum(..2), 5);
+    /// assert_eq!(wm.sum_lt(..3, 5), 5); // [1, 4, _]
+    /// ```
+    pub fn swap(&mut self, i: usize) {
+        check_bounds(i + 1, self.wm.len);
+        let (a, b) = (self.wm.orig[i], self.wm.orig[i + 1]);
+        self.orig_sum[i + 1] = self.orig_sum[i] + b.widen();
+        let mut p = i;
+        for j in (0..self.wm.bitlen).rev() {
+            if a.test(j) != b.test(j) {
+                break;
+            }
+            p = if !a.test(j) {
+                self.wm.buf[j].rank(p, 0)
+            } else {
+                self.wm.zeros[j] + self.wm.buf[j].rank(p, 1)
+            };
+            self.sums[j][p + 1] = self.sums[j][p] + b.widen();
+        }
+        self.wm.swap(i);
+    }
+
+    /// 区間 `range` の値の総和を返す。
+    ///
+    /// オーバーフローを避けるため、和は [`WmInt::Wide`] 型（符号なし整数に対しては
+    /// `u128`、符号付き整数に対しては `i128`）で計算する。
+    ///
+    /// # Complexity
+    /// $O(1)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::WaveletMatrixSum;
+    ///
+    /// let wm: WaveletMatrixSum<u32> = vec![1, 8, 4, 9, 2, 7, 5, 2].into();
+    /// assert_eq!(wm.sum(..), 38);
+    /// assert_eq!(wm.sum(2..5), 15); // [4, 9, 2]
+    /// assert_eq!(wm.sum(3..3), 0);
+    ///
+    /// let wm: WaveletMatrixSum<u64> = vec![u64::MAX; 3].into();
+    /// assert_eq!(wm.sum(..), 3 * u64::MAX as u128);
+    /// ```
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> I::Wide {
+        let Range { start, end } = bounds_within(range, self.wm.len);
+        self.orig_sum[end] - self.orig_sum[start]
+    }
+
+    /// 区間 `range` の値のうち、`value` 未満のものの総和を返す。
+    ///
+    /// [`quantile`] と組み合わせると、区間の小さい方から $k$ 個の和なども求められる。
+    ///
+    /// [`quantile`]: struct.WaveletMatrix.html#method.quantile
+    ///
+    /// # Complexity
+    /// $O(\\log(\\sigma))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::ds::WaveletMatrixSum;
+    /// use nekolib::traits::{Count3way, Quantile};
+    ///
+    /// let wm: WaveletMatrixSum<i32> = vec![-3, 1, -4, 1, -5, 9, -2].into();
+    /// assert_eq!(wm.sum_lt(.., 1), -14); // [-3, _, -4, _, -5, _, -2]
+    /// assert_eq!(wm.sum_lt(1..6, 2), -7); // [1, -4, 1, -5, _]
+    /// assert_eq!(wm.sum_lt(.., i32::MIN), 0);
+    ///
+    /// // 小さい方から 3 個の和
+    /// let range = 1..6; // [1, -4, 1, -5, 9]
+    /// let x = wm.quantile(range.clone(), 2).unwrap();
+    /// let lt = wm.count_3way(range.clone(), x).lt();
+    /// let sum = wm.sum_lt(range, x) + (3 - lt) as i128 * x as i128;
+    /// assert_eq!(sum, -8);
+    /// ```
+    pub fn sum_lt(&self, range: impl RangeBounds<usize>, value: I) -> I::Wide {
+        let Range { mut start, mut end } = bounds_within(range, self.wm.len);
+        if value.bitlen() > self.wm.bitlen {
+            return self.orig_sum[end] - self.orig_sum[start];
+        }
+        let mut res = I::Wide::default();
+        for i in (0..self.wm.bitlen).rev() {
+            let start0 = self.wm.buf[i].rank(start, 0);
+            let end0 = self.wm.buf[i].rank(end, 0);
+            if !value.test(i) {
+                start = start0;
+                end = end0;
+            } else {
+                res = res + (self.sums[i][end0] - self.sums[i][start0]);
+                start = self.wm.zeros[i] + (start - start0);
+                end = self.wm.zeros[i] + (end - end0);
+            }
+        }
+        res
+    }
+}
+
 pub trait WmInt: Copy {
+    /// 総和の計算に用いる型。
+    type Wide: Copy
+        + Default
+        + Add<Output = Self::Wide>
+        + Sub<Output = Self::Wide>;
     fn test(self, i: usize) -> bool;
     fn set(&mut self, i: usize);
     fn bitlen(self) -> usize;
     fn zero() -> Self;
+    fn widen(self) -> Self::Wide;
 }
 
 macro_rules! impl_wm_int {
     ( $( $ty:ty )* ) => { $(
         impl WmInt for $ty {
+            type Wide = u128;
             fn test(self, i: usize) -> bool { self >> i & 1 != 0 }
             fn set(&mut self, i: usize) { *self |= 1 << i; }
             fn bitlen(self) -> usize {
@@ -304,12 +796,40 @@ macro_rules! impl_wm_int {
                 }
             }
             fn zero() -> $ty { 0 }
+            fn widen(self) -> u128 { self as u128 }
         }
     )* };
 }
 
 impl_wm_int! { u8 u16 u32 u64 u128 usize }
 
+// 符号ビットを反転させて（i.e. x - MIN として）非負整数に埋め込む。
+// 大小関係が保たれるので、ビットごとの処理はこの値に対して行えばよい。
+macro_rules! impl_wm_int_signed {
+    ( $( ($ty:ty, $uty:ty) )* ) => { $(
+        impl WmInt for $ty {
+            type Wide = i128;
+            fn test(self, i: usize) -> bool {
+                ((self as $uty) ^ (<$ty>::MIN as $uty)).test(i)
+            }
+            fn set(&mut self, i: usize) {
+                let mut u = (*self as $uty) ^ (<$ty>::MIN as $uty);
+                u.set(i);
+                *self = (u ^ (<$ty>::MIN as $uty)) as $ty;
+            }
+            fn bitlen(self) -> usize {
+                ((self as $uty) ^ (<$ty>::MIN as $uty)).bitlen()
+            }
+            fn zero() -> $ty { <$ty>::MIN }
+            fn widen(self) -> i128 { self as i128 }
+        }
+    )* };
+}
+
+impl_wm_int_signed! {
+    (i8, u8) (i16, u16) (i32, u32) (i64, u64) (i128, u128) (isize, usize)
+}
+
 #[test]
 fn test_simple() {
     let n = 300;
@@ -411,3 +931,427 @@ fn test_count() {
     assert_eq!(full.count_3way(.., 255), c3(0, n, 0));
     assert_eq!(full.count_3way(.., 255..=255), c3(0, n, 0));
 }
+
+#[test]
+fn test_concat() {
+    let n = 100;
+    let f = std::iter::successors(Some(296), |&x| Some((x * 258 + 185) % 397));
+    let buf: Vec<u32> = f.take(n).collect();
+
+    for mid in [0, 1, 37, n - 1, n] {
+        let left: WaveletMatrix<_> = buf[..mid].to_vec().into();
+        let right: WaveletMatrix<_> = buf[mid..].to_vec().into();
+        let actual = left.concat(right);
+        let expected: WaveletMatrix<_> = buf.clone().into();
+
+        assert_eq!(actual.len(), expected.len());
+        for i in 0..n {
+            assert_eq!(actual[i], expected[i]);
+        }
+        for start in (0..=n).step_by(7) {
+            for end in (start..=n).step_by(5) {
+                for x in (0..400).step_by(13) {
+                    assert_eq!(
+                        actual.count_3way(start..end, x),
+                        expected.count_3way(start..end, x)
+                    );
+                    assert_eq!(
+                        actual.find_nth(start.., x, 0),
+                        expected.find_nth(start.., x, 0)
+                    );
+                }
+                for i in 0..=end - start {
+                    assert_eq!(
+                        actual.quantile(start..end, i),
+                        expected.quantile(start..end, i)
+                    );
+                }
+            }
+        }
+        for &x in &buf {
+            assert_eq!(actual.select(x, 1), expected.select(x, 1));
+        }
+    }
+}
+
+#[test]
+fn test_occurrence_index() {
+    let n = 300;
+    let f = std::iter::successors(Some(296), |&x| Some((x * 258 + 185) % 397))
+        .map(|x| x & 7);
+    let buf: Vec<u32> = f.take(n).collect();
+    let wm: WaveletMatrix<_> = buf.clone().into();
+
+    let mut count = [0; 8];
+    for i in 0..n {
+        let k = wm.occurrence_index(i);
+        assert_eq!(k, count[buf[i] as usize]);
+        assert_eq!(wm.find_nth(0.., wm[i], k), Some(i));
+        count[buf[i] as usize] += 1;
+    }
+}
+
+#[test]
+fn test_find_nth_end() {
+    let n = 100;
+    let f = std::iter::successors(Some(296), |&x| Some((x * 258 + 185) % 397))
+        .map(|x| x & 7);
+    let buf: Vec<u32> = f.take(n).collect();
+    let wm: WaveletMatrix<_> = buf.clone().into();
+
+    // 区間の外側にある出現位置を返してはいけない
+    for start in 0..n {
+        for end in start..=n {
+            for x in 0..8 {
+                let pos: Vec<_> =
+                    (start..end).filter(|&i| buf[i] == x).collect();
+                for k in 0..=pos.len() {
+                    assert_eq!(
+                        wm.find_nth(start..end, x, k),
+                        pos.get(k).copied()
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_invariants() {
+    let mut f =
+        std::iter::successors(Some(296_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    for &n in &[0, 1, 2, 10, 100, 1000] {
+        for &m in &[1, 2, 7, 64, 1 << 20] {
+            let buf: Vec<u32> = (0..n).map(|_| (rand() % m) as u32).collect();
+            let wm: WaveletMatrix<_> = buf.into();
+            wm.check_invariants();
+        }
+        let buf: Vec<u64> = (0..n).map(|_| (rand() << 33) ^ rand()).collect();
+        let wm: WaveletMatrix<_> = buf.into();
+        wm.check_invariants();
+    }
+}
+
+#[test]
+fn test_swap() {
+    let mut f =
+        std::iter::successors(Some(185_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    for &n in &[2, 3, 10, 100, 300] {
+        for &m in &[1, 2, 7, 64, 1 << 20] {
+            let mut buf: Vec<u32> =
+                (0..n).map(|_| (rand() % m) as u32).collect();
+            let mut wm: WaveletMatrix<_> = buf.clone().into();
+            let mut ws: WaveletMatrixSum<_> = buf.clone().into();
+            for _ in 0..100 {
+                let i = (rand() % (n as u64 - 1)) as usize;
+                buf.swap(i, i + 1);
+                wm.swap(i);
+                ws.swap(i);
+
+                let l = (rand() % (n as u64 + 1)) as usize;
+                let r = (rand() % (n as u64 + 1)) as usize;
+                let (l, r) = (l.min(r), l.max(r));
+                let x = (rand() % m) as u32;
+                let mut sorted = buf[l..r].to_vec();
+                sorted.sort_unstable();
+                let lt = sorted.iter().filter(|&&y| y < x).count();
+                let eq = sorted.iter().filter(|&&y| y == x).count();
+                let c3 = wm.count_3way(l..r, x);
+                assert_eq!(c3, Count3wayResult::new(lt, eq, r - l - lt - eq));
+                for k in 0..=r - l {
+                    assert_eq!(wm.quantile(l..r, k), sorted.get(k).copied());
+                }
+                let sum: u128 = buf[l..r].iter().map(|&y| y as u128).sum();
+                assert_eq!(ws.sum(l..r), sum);
+                let sum: u128 = (buf[l..r].iter())
+                    .filter(|&&y| y < x)
+                    .map(|&y| y as u128)
+                    .sum();
+                assert_eq!(ws.sum_lt(l..r, x), sum);
+            }
+            assert_eq!(wm.to_vec_range(..), buf);
+            wm.check_invariants();
+        }
+    }
+
+    // バブルソート
+    let buf: Vec<i32> = vec![3, -1, 4, -1, 5, -9, 2, 6, -5, 3];
+    let n = buf.len();
+    let mut wm: WaveletMatrix<_> = buf.clone().into();
+    for k in (1..n).rev() {
+        for i in 0..k {
+            if wm[i] > wm[i + 1] {
+                wm.swap(i);
+            }
+        }
+    }
+    let mut sorted = buf;
+    sorted.sort_unstable();
+    assert_eq!(wm.to_vec_range(..), sorted);
+    wm.check_invariants();
+    for (k, &x) in sorted.iter().enumerate() {
+        assert_eq!(wm.quantile(k..=k, 0), Some(x));
+    }
+}
+
+#[test]
+fn test_range_order_query() {
+    use range_order_query::RangeOrderQuery;
+
+    // 一つの境界ですべてのクエリを使えることを確かめる
+    fn queries<Q: RangeOrderQuery<u32>>(
+        q: &Q,
+        l: usize,
+        r: usize,
+        x: u32,
+    ) -> (usize, Count3wayResult, Vec<Option<u32>>, Vec<Option<usize>>) {
+        let count = q.count(l..r, x);
+        let c3 = q.count_3way(l..r, x);
+        let quantile = (0..=r - l).map(|i| q.quantile(l..r, i)).collect();
+        let find_nth = (0..=count).map(|i| q.find_nth(l..r, x, i)).collect();
+        (count, c3, quantile, find_nth)
+    }
+
+    let n = 50;
+    let f = std::iter::successors(Some(296), |&x| Some((x * 258 + 185) % 397))
+        .map(|x| x % 13);
+    let buf: Vec<u32> = f.take(n).collect();
+    let wm: WaveletMatrix<_> = buf.clone().into();
+
+    for l in 0..=n {
+        for r in l..=n {
+            let sorted = {
+                let mut tmp = buf[l..r].to_vec();
+                tmp.sort_unstable();
+                tmp
+            };
+            for x in 0..14 {
+                let (count, c3, quantile, find_nth) = queries(&wm, l, r, x);
+
+                let lt = sorted.iter().filter(|&&y| y < x).count();
+                let eq = sorted.iter().filter(|&&y| y == x).count();
+                assert_eq!(count, eq);
+                assert_eq!(c3, Count3wayResult::new(lt, eq, r - l - lt - eq));
+
+                let expected: Vec<_> =
+                    (0..=r - l).map(|i| sorted.get(i).copied()).collect();
+                assert_eq!(quantile, expected);
+
+                let expected: Vec<_> = (l..r)
+                    .filter(|&i| buf[i] == x)
+                    .map(Some)
+                    .chain(Some(None))
+                    .collect();
+                assert_eq!(find_nth, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_signed() {
+    let n = 60;
+    let f = std::iter::successors(Some(296), |&x| Some((x * 258 + 185) % 397))
+        .map(|x| x % 11 - 5);
+    let buf: Vec<i32> = f.take(n).collect();
+    let wm: WaveletMatrix<_> = buf.clone().into();
+    let wm64: WaveletMatrix<_> = buf
+        .iter()
+        .map(|&x| x as i64 * 1_000_000_007)
+        .collect::<Vec<_>>()
+        .into();
+    for start in 0..n {
+        for end in start..=n {
+            let mut sorted = buf[start..end].to_vec();
+            sorted.sort_unstable();
+            for k in 0..=end - start {
+                let q = sorted.get(k).copied();
+                assert_eq!(wm.quantile(start..end, k), q);
+                let q64 = q.map(|x| x as i64 * 1_000_000_007);
+                assert_eq!(wm64.quantile(start..end, k), q64);
+            }
+            for x in -7..=7 {
+                let lt = sorted.iter().filter(|&&y| y < x).count();
+                let gt = sorted.iter().filter(|&&y| y > x).count();
+                let eq = (end - start) - (lt + gt);
+                let c3 = Count3wayResult::new(lt, eq, gt);
+                assert_eq!(wm.count_3way(start..end, x), c3);
+                let c3 = Count3wayResult::new(lt, (end - start) - lt, 0);
+                assert_eq!(wm.count_3way(start..end, x..=i32::MAX), c3);
+            }
+        }
+    }
+
+    let extreme = vec![i8::MIN, -1, 0, i8::MAX, -1, i8::MIN];
+    let wm: WaveletMatrix<_> = extreme.clone().into();
+    let mut sorted = extreme.clone();
+    sorted.sort_unstable();
+    for (k, &x) in sorted.iter().enumerate() {
+        assert_eq!(wm.quantile(.., k), Some(x));
+    }
+    assert_eq!(wm.count(.., -1), 2);
+    assert_eq!(wm.count(.., i8::MIN..=0), 5);
+    assert_eq!(wm.find_nth(1.., i8::MIN, 0), Some(5));
+}
+
+#[test]
+fn test_to_vec_range() {
+    use std::ops::Bound::{Excluded, Included};
+
+    let buf: Vec<u32> = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+    let n = buf.len();
+    let wm: WaveletMatrix<_> = buf.clone().into();
+    assert_eq!(wm.to_vec_range(..), buf);
+    for l in 0..=n {
+        assert_eq!(wm.to_vec_range(l..), buf[l..]);
+        assert_eq!(wm.to_vec_range(..l), buf[..l]);
+        for r in l..=n {
+            assert_eq!(wm.to_vec_range(l..r), buf[l..r]);
+            let v = wm.to_vec_range(l..r);
+            assert_eq!(v, (l..r).map(|i| wm[i]).collect::<Vec<_>>());
+        }
+    }
+    assert_eq!(wm.to_vec_range((Excluded(2), Included(4))), [1, 5]);
+    assert!(wm.to_vec_range(4..4).is_empty());
+    assert!(wm.to_vec_range(n..).is_empty());
+
+    let empty: WaveletMatrix<u32> = vec![].into();
+    assert!(empty.to_vec_range(..).is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_to_vec_range_out_of_bounds() {
+    let wm: WaveletMatrix<u32> = vec![1, 2, 3].into();
+    wm.to_vec_range(1..4);
+}
+
+#[test]
+fn test_sum() {
+    let n = 60;
+    let f = std::iter::successors(Some(296), |&x| Some((x * 258 + 185) % 397));
+    let buf: Vec<u32> = f.take(n).collect();
+    let wm: WaveletMatrixSum<_> = buf.clone().into();
+    let signed: Vec<i64> =
+        buf.iter().map(|&x| (x as i64 - 200) * 1_000_000_007).collect();
+    let wm_signed: WaveletMatrixSum<_> = signed.clone().into();
+    for start in 0..=n {
+        for end in start..=n {
+            let expected: u128 =
+                buf[start..end].iter().map(|&x| x as u128).sum();
+            assert_eq!(wm.sum(start..end), expected);
+            for x in (0..=400).step_by(7).chain(Some(u32::MAX)) {
+                let expected: u128 = (buf[start..end].iter())
+                    .filter(|&&y| y < x)
+                    .map(|&y| y as u128)
+                    .sum();
+                assert_eq!(wm.sum_lt(start..end, x), expected);
+            }
+
+            let expected: i128 =
+                signed[start..end].iter().map(|&x| x as i128).sum();
+            assert_eq!(wm_signed.sum(start..end), expected);
+            for x in (-210..=210).step_by(10).map(|x| x * 1_000_000_007) {
+                let expected: i128 = (signed[start..end].iter())
+                    .filter(|&&y| y < x)
+                    .map(|&y| y as i128)
+                    .sum();
+                assert_eq!(wm_signed.sum_lt(start..end, x), expected);
+            }
+            assert_eq!(wm_signed.sum_lt(start..end, i64::MIN), 0);
+            assert_eq!(wm_signed.sum_lt(start..end, i64::MAX), expected);
+        }
+    }
+
+    // 型の上限付近の値でもオーバーフローしない
+    let extreme = vec![u64::MAX, 0, u64::MAX, u64::MAX - 1];
+    let wm: WaveletMatrixSum<_> = extreme.into();
+    assert_eq!(wm.sum(..), 3 * u64::MAX as u128 - 1);
+    assert_eq!(wm.sum_lt(.., u64::MAX), u64::MAX as u128 - 1);
+    let extreme = vec![i8::MIN, i8::MIN, i8::MAX, -1];
+    let wm: WaveletMatrixSum<_> = extreme.into();
+    assert_eq!(wm.sum(..), -130);
+    assert_eq!(wm.sum_lt(.., 0), -257);
+
+    let zero: WaveletMatrixSum<u32> = vec![0; 5].into();
+    assert_eq!(zero.sum(..), 0);
+    assert_eq!(zero.sum_lt(.., 0), 0);
+    assert_eq!(zero.sum_lt(.., 1), 0);
+    let empty: WaveletMatrixSum<u32> = vec![].into();
+    assert_eq!(empty.sum(..), 0);
+    assert_eq!(empty.sum_lt(.., 1), 0);
+}
+
+#[test]
+fn test_topk() {
+    use std::collections::BTreeMap;
+
+    fn naive<I: Copy + Ord>(a: &[I], k: usize) -> Vec<(I, usize)> {
+        let mut count = BTreeMap::new();
+        for &x in a {
+            *count.entry(x).or_insert(0) += 1;
+        }
+        let mut res: Vec<_> = count.into_iter().collect();
+        res.sort_by_key(|&(x, c)| (Reverse(c), x));
+        res.truncate(k);
+        res
+    }
+
+    let n = 60;
+    let f = std::iter::successors(Some(296), |&x| Some((x * 258 + 185) % 397));
+    let buf: Vec<u32> = f.take(n).map(|x| x % 13).collect();
+    let wm: WaveletMatrix<_> = buf.clone().into();
+    let signed: Vec<i32> = buf.iter().map(|&x| x as i32 - 6).collect();
+    let wm_signed: WaveletMatrix<_> = signed.clone().into();
+    for start in 0..=n {
+        for end in start..=n {
+            for k in 0..=15 {
+                let expected = naive(&buf[start..end], k);
+                assert_eq!(wm.topk(start..end, k), expected);
+                let expected = naive(&signed[start..end], k);
+                assert_eq!(wm_signed.topk(start..end, k), expected);
+            }
+        }
+    }
+
+    let zero: WaveletMatrix<u32> = vec![0; 5].into();
+    assert_eq!(zero.topk(.., 2), [(0, 5)]);
+    assert_eq!(zero.topk(1..3, 1), [(0, 2)]);
+    let extreme = vec![i8::MAX, i8::MIN, -1, i8::MIN, 0, i8::MAX, -1];
+    let wm: WaveletMatrix<_> = extreme.into();
+    assert_eq!(wm.topk(.., 5), [(i8::MIN, 2), (-1, 2), (i8::MAX, 2), (0, 1)]);
+    let empty: WaveletMatrix<u32> = vec![].into();
+    assert!(empty.topk(.., 3).is_empty());
+}
+
+#[test]
+fn test_prev_next_value() {
+    let n = 80;
+    let f = std::iter::successors(Some(296), |&x| Some((x * 258 + 185) % 397));
+    let buf: Vec<u32> = f.take(n).map(|x| x % 29).collect();
+    let wm: WaveletMatrix<_> = buf.clone().into();
+    let signed: Vec<i32> = buf.iter().map(|&x| x as i32 - 14).collect();
+    let wm_signed: WaveletMatrix<_> = signed.clone().into();
+    for start in 0..=n {
+        for end in start..=n {
+            let a = &buf[start..end];
+            for x in (0..=31).chain(Some(u32::MAX)) {
+                let prev = a.iter().copied().filter(|&y| y <= x).max();
+                let next = a.iter().copied().filter(|&y| y >= x).min();
+                assert_eq!(wm.prev_value(start..end, x), prev);
+                assert_eq!(wm.next_value(start..end, x), next);
+            }
+            let a = &signed[start..end];
+            for x in (-16..=16).chain(vec![i32::MIN, i32::MAX]) {
+                let prev = a.iter().copied().filter(|&y| y <= x).max();
+                let next = a.iter().copied().filter(|&y| y >= x).min();
+                assert_eq!(wm_signed.prev_value(start..end, x), prev);
+                assert_eq!(wm_signed.next_value(start..end, x), next);
+            }
+        }
+    }
+}
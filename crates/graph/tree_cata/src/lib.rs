@@ -32,8 +32,11 @@ use std::collections::VecDeque;
 ///
 /// このように定められる $f$ に対し、$f(0), f(1), \\dots, f(n-1)$ を求める。
 ///
+/// 入力が森の場合は、各連結成分をそれぞれ一つの木とみなして求める。
+///
 /// # Idea
 /// まず、根を $0$ として木をトポロジカルソートしておく。
+/// 森の場合は、各連結成分で番号最小の頂点を根とし、成分ごとに並べる。
 /// これにより、ボトムアップの DP を単にループで行うことができ、$f(0)$
 /// が求まる。次に、上で $f(1)$ を求めたときのように、トップダウンに DP
 /// をしながら（ボトムアップの DP での結果を利用して）残りの頂点について求める。
@@ -290,25 +293,34 @@ impl<T> From<Vec<Vec<(usize, T)>>> for TreeCata<T> {
     fn from(mut g: Vec<Vec<(usize, T)>>) -> Self {
         let n = g.len();
         let mut par: Vec<_> = (0..n).map(|_| None).collect();
-        let mut q: VecDeque<_> = vec![0].into();
+        let mut seen = vec![false; n];
         let mut order = vec![];
         let mut child: Vec<_> = (0..n).map(|_| vec![]).collect();
         let mut bound = vec![n; n];
 
-        while let Some(v) = q.pop_front() {
-            order.push(v);
-            let gv = std::mem::take(&mut g[v]);
-            let mut left = true;
-            for (nv, w) in gv {
-                if nv == 0 || par[nv].is_some() {
-                    par[v] = Some((nv, w));
-                    left = false;
-                } else {
-                    if !left && bound[v] == n {
-                        bound[v] = nv;
+        // 連結成分ごとに、番号最小の頂点を根とする。
+        for root in 0..n {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let mut q: VecDeque<_> = vec![root].into();
+            while let Some(v) = q.pop_front() {
+                order.push(v);
+                let gv = std::mem::take(&mut g[v]);
+                let mut left = true;
+                for (nv, w) in gv {
+                    if seen[nv] {
+                        par[v] = Some((nv, w));
+                        left = false;
+                    } else {
+                        if !left && bound[v] == n {
+                            bound[v] = nv;
+                        }
+                        seen[nv] = true;
+                        child[v].push((nv, w));
+                        q.push_back(nv);
                     }
-                    child[v].push((nv, w));
-                    q.push_back(nv);
                 }
             }
         }
@@ -318,6 +330,170 @@ impl<T> From<Vec<Vec<(usize, T)>>> for TreeCata<T> {
 }
 
 impl<T> TreeCata<T> {
+    /// 頂点数 $n$ と無向辺のリストから構築する。
+    ///
+    /// 辺 $(u, v, w)$ は、$u$ の隣接リストに $(v, w)$、$v$ の隣接リストに
+    /// $(u, w)$ として、与えられた順に登録される。
+    /// 隣接リストから [`From`] で構築したときと同様に前処理する。
+    /// すなわち、森の場合は各連結成分で番号最小の頂点を根とする。
+    ///
+    /// # Panics
+    /// 以下のいずれかのとき。
+    /// - 自己ループがあるとき。
+    /// - 閉路があるとき。すなわち、連結成分の個数を $c$ として、
+    ///   辺の本数が $n-c$ でないとき。多重辺がある場合はこれに該当する。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// //      0 -- 2
+    /// //      |
+    /// // 4 -- 1 -- 3
+    /// //      |
+    /// //      5
+    /// let es = [(0, 1), (0, 2), (1, 3), (1, 4), (1, 5)];
+    /// let tc = TreeCata::from_edges(6, es.iter().map(|&(u, v)| (u, v, ())));
+    ///
+    /// let empty = 0;
+    /// let map = |&x: &usize, _: &()| x + 1;
+    /// let fold = |&x: &usize, &y: &usize| x.max(y);
+    /// assert_eq!(tc.each_root(empty, map, fold), [2, 2, 3, 3, 3, 3]);
+    /// ```
+    pub fn from_edges(
+        n: usize,
+        edges: impl IntoIterator<Item = (usize, usize, T)>,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let mut g: Vec<_> = (0..n).map(|_| vec![]).collect();
+        let mut m = 0;
+        for (u, v, w) in edges {
+            assert_ne!(u, v, "self-loops are not allowed");
+            g[u].push((v, w.clone()));
+            g[v].push((u, w));
+            m += 1;
+        }
+
+        // 連結成分が c 個のとき、辺が n - c 本であれば森
+        let mut seen = vec![false; n];
+        let mut c = 0;
+        for root in 0..n {
+            if seen[root] {
+                continue;
+            }
+            c += 1;
+            seen[root] = true;
+            let mut stack = vec![root];
+            while let Some(v) = stack.pop() {
+                for &(nv, _) in &g[v] {
+                    if !seen[nv] {
+                        seen[nv] = true;
+                        stack.push(nv);
+                    }
+                }
+            }
+        }
+        assert_eq!(m + c, n, "not a forest");
+
+        g.into()
+    }
+
+    /// 隣接リストと、各頂点に隣接する頂点の順序から構築する。
+    ///
+    /// 各頂点 $v$ の隣接リストを `order[v]` の順に並べ替えてから、
+    /// [`From`] で構築したときと同様に前処理する。`fold` が非可換のとき、
+    /// 各頂点で畳まれる順序を隣接リストの与え方によらずに指定するのに用いる。
+    ///
+    /// # Panics
+    /// `order.len()` が頂点数と異なるとき、あるいはある $v$ について
+    /// `order[v]` が $v$ に隣接する頂点の並べ替えでないとき。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// let g = vec![
+    ///     vec![(1, 0), (2, 0)],
+    ///     vec![(0, 1), (3, 1), (4, 1), (5, 1)],
+    ///     vec![(0, 2)],
+    ///     vec![(1, 3)],
+    ///     vec![(1, 4)],
+    ///     vec![(1, 5)],
+    /// ];
+    /// let order =
+    ///     vec![vec![2, 1], vec![5, 0, 3, 4], vec![0], vec![1], vec![1], vec![1]];
+    ///
+    /// let tc = TreeCata::from_with_order(g, &order);
+    /// assert_eq!(tc.neighbors(1), [5, 0, 3, 4]);
+    ///
+    /// let empty = "".to_owned();
+    /// let map = |x: &String, c: &usize| format!("({} {} )", x, c);
+    /// let fold = |x: &String, y: &String| format!("{}{}", x, y);
+    ///
+    /// let res = tc.each_root(empty, map, fold);
+    /// assert_eq!(res[0], "( 2 )(( 5 )( 3 )( 4 ) 1 )");
+    /// assert_eq!(res[1], "( 5 )(( 2 ) 0 )( 3 )( 4 )");
+    /// ```
+    pub fn from_with_order(
+        mut g: Vec<Vec<(usize, T)>>,
+        order: &[Vec<usize>],
+    ) -> Self {
+        let n = g.len();
+        assert_eq!(order.len(), n, "invalid order");
+        // rank[u] は stamp[u] == v のときのみ有効で、order[v] における u
+        // の位置を指す。
+        let mut rank = vec![0; n];
+        let mut stamp = vec![n; n];
+        for (v, (gv, ov)) in g.iter_mut().zip(order).enumerate() {
+            assert_eq!(gv.len(), ov.len(), "invalid order");
+            for (i, &u) in ov.iter().enumerate() {
+                assert!(u < n, "invalid order");
+                stamp[u] = v;
+                rank[u] = i;
+            }
+            let mut sorted: Vec<_> = ov.iter().map(|_| None).collect();
+            for (u, w) in gv.drain(..) {
+                assert!(u < n && stamp[u] == v, "invalid order");
+                let i = rank[u];
+                assert!(sorted[i].is_none(), "invalid order");
+                sorted[i] = Some((u, w));
+            }
+            *gv = sorted.into_iter().map(Option::unwrap).collect();
+        }
+        g.into()
+    }
+
+    /// 頂点 $v$ に隣接する頂点を、隣接リストの順に返す。
+    ///
+    /// # Complexity
+    /// $O(\\deg(v))$ time.
+    pub fn neighbors(&self, v: usize) -> Vec<usize> {
+        let mut res: Vec<_> = self.child[v].iter().map(|&(u, _)| u).collect();
+        if let Some((p, _)) = self.par[v] {
+            let i = (res.iter().position(|&u| u == self.bound[v]))
+                .unwrap_or(res.len());
+            res.insert(i, p);
+        }
+        res
+    }
+
+    /// 各頂点を根としたときの値を求める。
+    ///
+    /// `fold` が非可換であっても、根 $v$ における値は隣接する頂点
+    /// $\\langle u\_1, u\_2, \\dots, u\_k\\rangle$ に対して左から
+    /// $(f\_v(u\_1)\\star e\_{u\_1, v})\\circ\\dots\\circ(f\_v(u\_k)\\star e\_{u\_k, v})$
+    /// の順で畳まれることを保証する。
+    ///
+    /// ただし、途中の $f\_v(u)$ において、$u$ が（頂点 $0$ を根としたときの）
+    /// $v$ の親であるときは、$u$ の親側の値が先頭に来て、残りの子が隣接リストの順に続く。
     pub fn each_root<U: Clone>(
         &self,
         empty: U,
@@ -329,21 +505,7 @@ impl<T> TreeCata<T> {
             return vec![];
         }
 
-        let mut ascl: Vec<_> = vec![empty.clone(); n];
-        let mut ascr: Vec<_> = vec![empty.clone(); n];
-        let mut dp: Vec<_> = vec![empty.clone(); n];
-        let mut right: Vec<_> = self.bound.iter().map(|&bi| bi < n).collect();
-        for &i in self.order[1..].iter().rev() {
-            dp[i] = fold(&ascl[i], &ascr[i]);
-            let &(p, ref x) = self.par[i].as_ref().unwrap();
-            if right[p] {
-                ascr[p] = fold(&map(&dp[i], x), &ascr[p]);
-                right[p] = self.bound[p] != i;
-            } else {
-                ascl[p] = fold(&map(&dp[i], x), &ascl[p]);
-            }
-        }
-        dp[0] = fold(&ascl[0], &ascr[0]);
+        let (mut dp, ascl, ascr) = self.ascend(&empty, &mut map, &mut fold);
 
         let mut desc: Vec<_> = vec![empty.clone(); n];
         for &i in &self.order {
@@ -364,6 +526,480 @@ impl<T> TreeCata<T> {
         }
         dp
     }
+
+    /// 頂点 $0$ を根としたときの、各頂点の部分木における値を求める。
+    ///
+    /// 頂点 $v$ の親を $p$ として、$v$ に対する値は $f\_p(v)$ である
+    /// （$v = 0$ のときは $f(0)$）。子は隣接リストの順に畳まれる。
+    /// 森の場合は、各連結成分で番号最小の頂点を根とする。
+    /// [`each_root`] のボトムアップのパートのみを行うので、
+    /// 根 $0$ での値のみが必要な場合はこちらを用いる方が速い。
+    ///
+    /// [`each_root`]: #method.each_root
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// let g = vec![
+    ///     vec![(1, 0), (2, 0)],
+    ///     vec![(0, 1), (3, 1), (4, 1), (5, 1)],
+    ///     vec![(0, 2)],
+    ///     vec![(1, 3)],
+    ///     vec![(1, 4)],
+    ///     vec![(1, 5)],
+    /// ];
+    ///
+    /// let tc: TreeCata<_> = g.into();
+    ///
+    /// let empty = "".to_owned();
+    /// let map = |x: &String, c: &usize| format!("({} {} )", x, c);
+    /// let fold = |x: &String, y: &String| format!("{}{}", x, y);
+    ///
+    /// assert_eq!(tc.subtree(empty, map, fold), [
+    ///     "(( 3 )( 4 )( 5 ) 1 )( 2 )",
+    ///     "( 3 )( 4 )( 5 )",
+    ///     "",
+    ///     "",
+    ///     "",
+    ///     "",
+    /// ]);
+    /// ```
+    pub fn subtree<U: Clone>(
+        &self,
+        empty: U,
+        mut map: impl FnMut(&U, &T) -> U,
+        mut fold: impl FnMut(&U, &U) -> U,
+    ) -> Vec<U> {
+        if self.child.is_empty() {
+            return vec![];
+        }
+        self.ascend(&empty, &mut map, &mut fold).0
+    }
+
+    /// 各連結成分の根からボトムアップに DP をする。
+    ///
+    /// 各頂点について、部分木での値と、子のうち隣接リストで親より左にあるもの・
+    /// 右にあるものをそれぞれ畳んだ値を返す。
+    fn ascend<U: Clone>(
+        &self,
+        empty: &U,
+        map: &mut impl FnMut(&U, &T) -> U,
+        fold: &mut impl FnMut(&U, &U) -> U,
+    ) -> (Vec<U>, Vec<U>, Vec<U>) {
+        let n = self.child.len();
+        let mut ascl: Vec<_> = vec![empty.clone(); n];
+        let mut ascr: Vec<_> = vec![empty.clone(); n];
+        let mut dp: Vec<_> = vec![empty.clone(); n];
+        let mut right: Vec<_> = self.bound.iter().map(|&bi| bi < n).collect();
+        for &i in self.order.iter().rev() {
+            dp[i] = fold(&ascl[i], &ascr[i]);
+            let &(p, ref x) = match self.par[i].as_ref() {
+                Some(px) => px,
+                None => continue,
+            };
+            if right[p] {
+                ascr[p] = fold(&map(&dp[i], x), &ascr[p]);
+                right[p] = self.bound[p] != i;
+            } else {
+                ascl[p] = fold(&map(&dp[i], x), &ascl[p]);
+            }
+        }
+        (dp, ascl, ascr)
+    }
+
+    /// 各頂点を根としたときの値を、子を隣接リストの逆順に畳んで求める。
+    ///
+    /// 各頂点 $v$ において
+    /// $(f\_v(u\_k)\\star e\_{u\_k, v})\\circ\\dots\\circ(f\_v(u\_1)\\star e\_{u\_1, v})$
+    /// の順になる。途中の $f\_v(u)$ においても、[`each_root`]
+    /// で畳まれる順序をちょうど逆にしたものになる。
+    ///
+    /// [`each_root`]: #method.each_root
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// let g = vec![
+    ///     vec![(1, 0), (2, 0)],
+    ///     vec![(0, 1), (3, 1), (4, 1), (5, 1)],
+    ///     vec![(0, 2)],
+    ///     vec![(1, 3)],
+    ///     vec![(1, 4)],
+    ///     vec![(1, 5)],
+    /// ];
+    ///
+    /// let tc: TreeCata<_> = g.into();
+    ///
+    /// let empty = "".to_owned();
+    /// let map = |x: &String, c: &usize| format!("({} {} )", x, c);
+    /// let fold = |x: &String, y: &String| format!("{}{}", x, y);
+    ///
+    /// assert_eq!(tc.each_root_rev(empty, map, fold), [
+    ///     "( 2 )(( 5 )( 4 )( 3 ) 1 )",
+    ///     "( 5 )( 4 )( 3 )(( 2 ) 0 )",
+    ///     "((( 5 )( 4 )( 3 ) 1 ) 0 )",
+    ///     "(( 5 )( 4 )(( 2 ) 0 ) 1 )",
+    ///     "(( 5 )( 3 )(( 2 ) 0 ) 1 )",
+    ///     "(( 4 )( 3 )(( 2 ) 0 ) 1 )",
+    /// ]);
+    /// ```
+    pub fn each_root_rev<U: Clone>(
+        &self,
+        empty: U,
+        map: impl FnMut(&U, &T) -> U,
+        mut fold: impl FnMut(&U, &U) -> U,
+    ) -> Vec<U> {
+        // 反対モノイドで畳めば、各頂点での順序がちょうど逆になる。
+        self.each_root(empty, map, |x, y| fold(y, x))
+    }
+
+    /// 各頂点から、`sources` のうち最も近い頂点までの距離を求める。
+    ///
+    /// 辺の長さは `edge_dist` で与える。
+    /// 頂点 $0$ を根として、子方向の最小距離をボトムアップに、
+    /// 親方向の最小距離をトップダウンに求めて合成する。
+    /// `sources` が空のときは、各頂点の値を `u64::MAX` とする。
+    /// 森の場合、`sources` の頂点を含まない連結成分についても同様である。
+    ///
+    /// # Complexity
+    /// $O(n + |S|)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// //       1     2     4
+    /// //  [0] --- 1 --- 2 --- 3
+    /// //          |
+    /// //          | 8
+    /// //          |
+    /// //         [4]
+    /// let g = vec![
+    ///     vec![(1, 1)],
+    ///     vec![(0, 1), (2, 2), (4, 8)],
+    ///     vec![(1, 2), (3, 4)],
+    ///     vec![(2, 4)],
+    ///     vec![(1, 8)],
+    /// ];
+    /// let tc: TreeCata<_> = g.into();
+    /// assert_eq!(tc.nearest_from(&[0, 4], |&w| w), [0, 1, 3, 7, 0]);
+    /// assert_eq!(tc.nearest_from(&[], |&w| w), [u64::MAX; 5]);
+    /// ```
+    pub fn nearest_from(
+        &self,
+        sources: &[usize],
+        edge_dist: impl Fn(&T) -> u64,
+    ) -> Vec<u64> {
+        let n = self.child.len();
+        let mut down = vec![u64::MAX; n];
+        for &s in sources {
+            down[s] = 0;
+        }
+        if n == 0 {
+            return down;
+        }
+
+        for (i, p, x) in self.edges_topdown().rev() {
+            down[p] = down[p].min(down[i].saturating_add(edge_dist(x)));
+        }
+
+        // 親方向の最小距離を合成した値で上書きしていく。
+        let mut res = down;
+        for (i, p, x) in self.edges_topdown() {
+            res[i] = res[i].min(res[p].saturating_add(edge_dist(x)));
+        }
+        res
+    }
+
+    /// 各頂点を根としたときの木の高さを求める。
+    ///
+    /// 辺の長さはすべて $1$ とする。
+    /// 辺の長さを指定する場合は [`heights_by`] を用いる。
+    ///
+    /// [`heights_by`]: #method.heights_by
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// //  0 --- 1 --- 2 --- 3
+    /// //        |
+    /// //        4
+    /// let g = vec![
+    ///     vec![(1, ())],
+    ///     vec![(0, ()), (2, ()), (4, ())],
+    ///     vec![(1, ()), (3, ())],
+    ///     vec![(2, ())],
+    ///     vec![(1, ())],
+    /// ];
+    /// let tc: TreeCata<_> = g.into();
+    /// assert_eq!(tc.heights(), [3, 2, 2, 3, 3]);
+    /// ```
+    pub fn heights(&self) -> Vec<u64> { self.heights_by(|_| 1) }
+
+    /// 各頂点を根としたときの木の高さを求める。
+    ///
+    /// 辺の長さは `edge_dist` で与える。
+    /// 頂点 $0$ を根として、子方向の高さの上位二つをボトムアップに求めておき、
+    /// 親方向の高さをトップダウンに求めて合成する。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// //      1     2     4
+    /// //  0 --- 1 --- 2 --- 3
+    /// //        |
+    /// //        | 8
+    /// //        |
+    /// //        4
+    /// let g = vec![
+    ///     vec![(1, 1)],
+    ///     vec![(0, 1), (2, 2), (4, 8)],
+    ///     vec![(1, 2), (3, 4)],
+    ///     vec![(2, 4)],
+    ///     vec![(1, 8)],
+    /// ];
+    /// let tc: TreeCata<_> = g.into();
+    /// assert_eq!(tc.heights_by(|&w| w), [9, 8, 10, 14, 14]);
+    /// ```
+    pub fn heights_by(&self, edge_dist: impl Fn(&T) -> u64) -> Vec<u64> {
+        let n = self.child.len();
+        if n == 0 {
+            return vec![];
+        }
+
+        // 子方向の高さの最大値と、それを与える子、二番目の値。
+        let mut first = vec![(0, n); n];
+        let mut second = vec![0; n];
+        for (i, p, x) in self.edges_topdown().rev() {
+            let h = first[i].0 + edge_dist(x);
+            if h > first[p].0 {
+                second[p] = first[p].0;
+                first[p] = (h, i);
+            } else if h > second[p] {
+                second[p] = h;
+            }
+        }
+
+        // 親方向の高さ。
+        let mut up = vec![0; n];
+        for (i, p, x) in self.edges_topdown() {
+            let sibling = if first[p].1 == i { second[p] } else { first[p].0 };
+            up[i] = up[p].max(sibling) + edge_dist(x);
+        }
+        first.into_iter().zip(up).map(|((down, _), up)| down.max(up)).collect()
+    }
+
+    /// 木の直径を求める。
+    ///
+    /// 辺の長さはすべて $1$ とし、直径の長さ $d$ と、両端の頂点 $u$, $v$
+    /// の組 $(d, u, v)$ を返す。
+    /// 辺の長さを指定する場合は [`diameter_by`] を用いる。
+    ///
+    /// [`diameter_by`]: #method.diameter_by
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// //  0 --- 1 --- 2 --- 3
+    /// //        |
+    /// //        4
+    /// let g = vec![
+    ///     vec![(1, ())],
+    ///     vec![(0, ()), (2, ()), (4, ())],
+    ///     vec![(1, ()), (3, ())],
+    ///     vec![(2, ())],
+    ///     vec![(1, ())],
+    /// ];
+    /// let tc: TreeCata<_> = g.into();
+    /// let (d, u, v) = tc.diameter();
+    /// assert_eq!(d, 3);
+    /// assert!([(0, 3), (3, 0), (3, 4), (4, 3)].contains(&(u, v)));
+    /// ```
+    pub fn diameter(&self) -> (u64, usize, usize) { self.diameter_by(|_| 1) }
+
+    /// 木の直径を求める。
+    ///
+    /// 辺の長さは `edge_dist` で与える。
+    /// 高さが最大の頂点 $u$ を求め、$u$ から最も遠い頂点 $v$
+    /// を求めると、$u$-$v$ パスが直径となる。
+    /// 森の場合は、各連結成分の直径のうち最長のものを返す。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// //      1     2     4
+    /// //  0 --- 1 --- 2 --- 3
+    /// //        |
+    /// //        | 8
+    /// //        |
+    /// //        4
+    /// let g = vec![
+    ///     vec![(1, 1)],
+    ///     vec![(0, 1), (2, 2), (4, 8)],
+    ///     vec![(1, 2), (3, 4)],
+    ///     vec![(2, 4)],
+    ///     vec![(1, 8)],
+    /// ];
+    /// let tc: TreeCata<_> = g.into();
+    /// let (d, u, v) = tc.diameter_by(|&w| w);
+    /// assert_eq!(d, 14);
+    /// assert!([(3, 4), (4, 3)].contains(&(u, v)));
+    /// ```
+    pub fn diameter_by(
+        &self,
+        edge_dist: impl Fn(&T) -> u64,
+    ) -> (u64, usize, usize) {
+        let farthest = |s: usize| {
+            let n = self.child.len();
+            let mut dist = vec![u64::MAX; n];
+            dist[s] = 0;
+            let mut stack = vec![s];
+            while let Some(v) = stack.pop() {
+                let par = self.par[v].as_ref().map(|(p, x)| (*p, x));
+                let child = self.child[v].iter().map(|(c, x)| (*c, x));
+                for (nv, x) in par.into_iter().chain(child) {
+                    if dist[nv] == u64::MAX {
+                        dist[nv] = dist[v] + edge_dist(x);
+                        stack.push(nv);
+                    }
+                }
+            }
+            (0..n)
+                .filter(|&v| dist[v] < u64::MAX)
+                .map(|v| (dist[v], v))
+                .max_by_key(|&(d, _)| d)
+                .unwrap()
+        };
+
+        // 高さが最大の頂点は、それを含む成分の直径の端点となる。
+        let heights = self.heights_by(&edge_dist);
+        let u = (0..heights.len()).max_by_key(|&v| heights[v]).unwrap();
+        let (d, v) = farthest(u);
+        (d, u, v)
+    }
+
+    /// 頂点 `root` を根としたときの各頂点の部分木のサイズを求める。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// //  0 --- 1 --- 2 --- 3
+    /// //        |
+    /// //        4
+    /// let g = vec![
+    ///     vec![(1, ())],
+    ///     vec![(0, ()), (2, ()), (4, ())],
+    ///     vec![(1, ()), (3, ())],
+    ///     vec![(2, ())],
+    ///     vec![(1, ())],
+    /// ];
+    /// let tc: TreeCata<_> = g.into();
+    /// assert_eq!(tc.subtree_sizes(0), [5, 4, 2, 1, 1]);
+    /// assert_eq!(tc.subtree_sizes(2), [1, 3, 5, 1, 1]);
+    /// ```
+    pub fn subtree_sizes(&self, root: usize) -> Vec<usize> {
+        let ones = vec![1; self.child.len()];
+        let sums = self.subtree_weight_sums(root, &ones);
+        sums.into_iter().map(|s| s as usize).collect()
+    }
+
+    /// 頂点 `root` を根としたときの各頂点の部分木の重みの総和を求める。
+    ///
+    /// 頂点 $v$ の重みを `weight[v]` とする。
+    /// 辺に重みがある場合は、各辺の重みを子側の頂点に持たせればよい
+    /// （このとき根の重みは $0$ とする）。
+    /// `root` を含まない連結成分の頂点については `weight[v]` をそのまま返す。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::graph::TreeCata;
+    ///
+    /// //  0 --- 1 --- 2 --- 3
+    /// //        |
+    /// //        4
+    /// let g = vec![
+    ///     vec![(1, ())],
+    ///     vec![(0, ()), (2, ()), (4, ())],
+    ///     vec![(1, ()), (3, ())],
+    ///     vec![(2, ())],
+    ///     vec![(1, ())],
+    /// ];
+    /// let tc: TreeCata<_> = g.into();
+    /// let weight = [1, 10, 100, 1000, 10000];
+    /// assert_eq!(
+    ///     tc.subtree_weight_sums(0, &weight),
+    ///     [11111, 11110, 1100, 1000, 10000]
+    /// );
+    /// assert_eq!(
+    ///     tc.subtree_weight_sums(3, &weight),
+    ///     [1, 10011, 10111, 11111, 10000]
+    /// );
+    /// ```
+    pub fn subtree_weight_sums(&self, root: usize, weight: &[u64]) -> Vec<u64> {
+        let n = self.child.len();
+        assert_eq!(weight.len(), n);
+
+        // root からの DFS 順と、root を根としたときの親。
+        let mut par = vec![n; n];
+        let mut order = vec![];
+        let mut stack = vec![root];
+        par[root] = root;
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            let up = self.par[v].as_ref().map(|&(p, _)| p);
+            let down = self.child[v].iter().map(|&(c, _)| c);
+            for nv in up.into_iter().chain(down) {
+                if par[nv] == n {
+                    par[nv] = v;
+                    stack.push(nv);
+                }
+            }
+        }
+
+        let mut res = weight.to_vec();
+        for &v in order[1..].iter().rev() {
+            res[par[v]] += res[v];
+        }
+        res
+    }
+
+    /// 根以外の各頂点 $v$ について、$v$ と親 $p$ と辺の値の組を
+    /// トップダウンの順に返す。
+    fn edges_topdown(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (usize, usize, &T)> + '_ {
+        self.order
+            .iter()
+            .filter_map(move |&v| self.par[v].as_ref().map(|(p, x)| (v, *p, x)))
+    }
 }
 
 #[test]
@@ -529,3 +1165,573 @@ fn test_order() {
         "((( 2 ) 0 )( 3 )( 4 ) 1 )",
     ]);
 }
+
+#[test]
+fn test_from_with_order() {
+    let empty = || "".to_owned();
+    let map = |x: &String, c: &usize| format!("({} {} )", x, c);
+    let fold = |x: &String, y: &String| format!("{}{}", x, y);
+
+    let g = vec![
+        vec![(1, 0), (2, 0)],
+        vec![(0, 1), (3, 1), (4, 1), (5, 1)],
+        vec![(0, 2)],
+        vec![(1, 3)],
+        vec![(1, 4)],
+        vec![(1, 5)],
+    ];
+
+    // test_order の inner (2) と同じ順序
+    let order =
+        vec![vec![1, 2], vec![3, 4, 0, 5], vec![0], vec![1], vec![1], vec![1]];
+    let tree_cata = TreeCata::from_with_order(g.clone(), &order);
+    for (v, ov) in order.iter().enumerate() {
+        assert_eq!(&tree_cata.neighbors(v), ov);
+    }
+    assert_eq!(tree_cata.each_root(empty(), map, fold), [
+        "(( 3 )( 4 )( 5 ) 1 )( 2 )",
+        "( 3 )( 4 )(( 2 ) 0 )( 5 )",
+        "((( 3 )( 4 )( 5 ) 1 ) 0 )",
+        "((( 2 ) 0 )( 4 )( 5 ) 1 )",
+        "((( 2 ) 0 )( 3 )( 5 ) 1 )",
+        "((( 2 ) 0 )( 3 )( 4 ) 1 )",
+    ]);
+
+    // 各頂点で降順
+    let order: Vec<_> = g
+        .iter()
+        .map(|gv| {
+            let mut ov: Vec<_> = gv.iter().map(|&(u, _)| u).collect();
+            ov.sort_unstable_by(|x, y| y.cmp(x));
+            ov
+        })
+        .collect();
+    let tree_cata = TreeCata::from_with_order(g.clone(), &order);
+    for (v, ov) in order.iter().enumerate() {
+        assert_eq!(&tree_cata.neighbors(v), ov);
+    }
+    assert_eq!(tree_cata.each_root(empty(), map, fold), [
+        "( 2 )(( 5 )( 4 )( 3 ) 1 )",
+        "( 5 )( 4 )( 3 )(( 2 ) 0 )",
+        "((( 5 )( 4 )( 3 ) 1 ) 0 )",
+        "((( 2 ) 0 )( 5 )( 4 ) 1 )",
+        "((( 2 ) 0 )( 5 )( 3 ) 1 )",
+        "((( 2 ) 0 )( 4 )( 3 ) 1 )",
+    ]);
+
+    // 入力の順序によらない
+    let mut h = g.clone();
+    for hv in &mut h {
+        hv.reverse();
+    }
+    let tree_cata = TreeCata::from_with_order(h, &order);
+    let res = tree_cata.each_root(empty(), map, fold);
+    assert_eq!(res[1], "( 5 )( 4 )( 3 )(( 2 ) 0 )");
+}
+
+#[test]
+#[should_panic(expected = "invalid order")]
+fn test_from_with_order_invalid() {
+    let g = vec![vec![(1, ()), (2, ())], vec![(0, ())], vec![(0, ())]];
+    let order = vec![vec![1, 1], vec![0], vec![0]];
+    TreeCata::from_with_order(g, &order);
+}
+
+#[test]
+#[should_panic(expected = "invalid order")]
+fn test_from_with_order_stale_rank() {
+    // order[1] で得た 2 の位置を、order[3] の検査で誤って使わないこと
+    let g = vec![
+        vec![(1, ())],
+        vec![(0, ()), (2, ())],
+        vec![(1, ()), (3, ())],
+        vec![(2, ())],
+    ];
+    let order = vec![vec![1], vec![0, 2], vec![1, 3], vec![1]];
+    TreeCata::from_with_order(g, &order);
+}
+
+#[test]
+#[should_panic(expected = "invalid order")]
+fn test_from_with_order_out_of_range() {
+    let g = vec![vec![(1, ())], vec![(0, ())]];
+    let order = vec![vec![7], vec![0]];
+    TreeCata::from_with_order(g, &order);
+}
+
+#[test]
+fn test_order_rev() {
+    let empty = || "".to_owned();
+    let map = |x: &String, c: &usize| format!("({} {} )", x, c);
+    let fold = |x: &String, y: &String| format!("{}{}", x, y);
+
+    let adj1 = [
+        // leftmost
+        vec![(0, 1), (3, 1), (4, 1), (5, 1)],
+        // inner (1)
+        vec![(3, 1), (0, 1), (4, 1), (5, 1)],
+        // inner (2)
+        vec![(3, 1), (4, 1), (0, 1), (5, 1)],
+        // rightmost
+        vec![(3, 1), (4, 1), (5, 1), (0, 1)],
+    ];
+    let expected = [
+        [
+            "( 2 )(( 5 )( 4 )( 3 ) 1 )",
+            "( 5 )( 4 )( 3 )(( 2 ) 0 )",
+            "((( 5 )( 4 )( 3 ) 1 ) 0 )",
+            "(( 5 )( 4 )(( 2 ) 0 ) 1 )",
+            "(( 5 )( 3 )(( 2 ) 0 ) 1 )",
+            "(( 4 )( 3 )(( 2 ) 0 ) 1 )",
+        ],
+        [
+            "( 2 )(( 5 )( 4 )( 3 ) 1 )",
+            "( 5 )( 4 )(( 2 ) 0 )( 3 )",
+            "((( 5 )( 4 )( 3 ) 1 ) 0 )",
+            "(( 5 )( 4 )(( 2 ) 0 ) 1 )",
+            "(( 5 )( 3 )(( 2 ) 0 ) 1 )",
+            "(( 4 )( 3 )(( 2 ) 0 ) 1 )",
+        ],
+        [
+            "( 2 )(( 5 )( 4 )( 3 ) 1 )",
+            "( 5 )(( 2 ) 0 )( 4 )( 3 )",
+            "((( 5 )( 4 )( 3 ) 1 ) 0 )",
+            "(( 5 )( 4 )(( 2 ) 0 ) 1 )",
+            "(( 5 )( 3 )(( 2 ) 0 ) 1 )",
+            "(( 4 )( 3 )(( 2 ) 0 ) 1 )",
+        ],
+        [
+            "( 2 )(( 5 )( 4 )( 3 ) 1 )",
+            "(( 2 ) 0 )( 5 )( 4 )( 3 )",
+            "((( 5 )( 4 )( 3 ) 1 ) 0 )",
+            "(( 5 )( 4 )(( 2 ) 0 ) 1 )",
+            "(( 5 )( 3 )(( 2 ) 0 ) 1 )",
+            "(( 4 )( 3 )(( 2 ) 0 ) 1 )",
+        ],
+    ];
+
+    for (g1, expected) in adj1.iter().zip(&expected) {
+        let g = vec![
+            vec![(1, 0), (2, 0)],
+            g1.clone(),
+            vec![(0, 2)],
+            vec![(1, 3)],
+            vec![(1, 4)],
+            vec![(1, 5)],
+        ];
+        let tree_cata: TreeCata<_> = g.into();
+        assert_eq!(&tree_cata.each_root_rev(empty(), map, fold), expected);
+    }
+}
+
+#[test]
+fn test_nearest_from() {
+    let mut f =
+        std::iter::successors(Some(185_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = |n: u64| f.next().unwrap() % n;
+
+    for n in 1..=30 {
+        for _ in 0..10 {
+            let mut g = vec![vec![]; n];
+            for v in 1..n {
+                let u = rand(v as u64) as usize;
+                let w = rand(10);
+                g[u].push((v, w));
+                g[v].push((u, w));
+            }
+            let sources: Vec<_> = (0..n).filter(|_| rand(4) == 0).collect();
+
+            let expected: Vec<_> = (0..n)
+                .map(|v| {
+                    let mut dist = vec![u64::MAX; n];
+                    let mut stack = vec![v];
+                    dist[v] = 0;
+                    while let Some(v) = stack.pop() {
+                        for &(nv, w) in &g[v] {
+                            if dist[nv] == u64::MAX {
+                                dist[nv] = dist[v] + w;
+                                stack.push(nv);
+                            }
+                        }
+                    }
+                    sources.iter().map(|&s| dist[s]).min().unwrap_or(u64::MAX)
+                })
+                .collect();
+
+            let tree_cata: TreeCata<_> = g.into();
+            assert_eq!(tree_cata.nearest_from(&sources, |&w| w), expected);
+        }
+    }
+}
+
+#[test]
+fn test_heights_diameter() {
+    let mut f =
+        std::iter::successors(Some(748_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = |n: u64| f.next().unwrap() % n;
+
+    let all_dist = |g: &[Vec<(usize, u64)>]| -> Vec<Vec<u64>> {
+        let n = g.len();
+        (0..n)
+            .map(|v| {
+                let mut dist = vec![u64::MAX; n];
+                let mut stack = vec![v];
+                dist[v] = 0;
+                while let Some(v) = stack.pop() {
+                    for &(nv, w) in &g[v] {
+                        if dist[nv] == u64::MAX {
+                            dist[nv] = dist[v] + w;
+                            stack.push(nv);
+                        }
+                    }
+                }
+                dist
+            })
+            .collect()
+    };
+
+    let test = |g: Vec<Vec<(usize, u64)>>| {
+        let dist = all_dist(&g);
+        let heights: Vec<_> =
+            dist.iter().map(|di| *di.iter().max().unwrap()).collect();
+        let diameter = *heights.iter().max().unwrap();
+
+        let tc: TreeCata<_> = g.clone().into();
+        assert_eq!(tc.heights_by(|&w| w), heights);
+        let (d, u, v) = tc.diameter_by(|&w| w);
+        assert_eq!((d, dist[u][v]), (diameter, diameter));
+
+        let unweighted: Vec<_> = g
+            .into_iter()
+            .map(|gi| gi.into_iter().map(|(v, _)| (v, 1)).collect())
+            .collect();
+        let dist = all_dist(&unweighted);
+        let heights: Vec<_> =
+            dist.iter().map(|di| *di.iter().max().unwrap()).collect();
+        let diameter = *heights.iter().max().unwrap();
+        assert_eq!(tc.heights(), heights);
+        let (d, u, v) = tc.diameter();
+        assert_eq!((d, dist[u][v]), (diameter, diameter));
+    };
+
+    for n in 1..=30 {
+        // path
+        let mut g = vec![vec![]; n];
+        for v in 1..n {
+            let w = rand(10);
+            g[v - 1].push((v, w));
+            g[v].push((v - 1, w));
+        }
+        test(g);
+
+        // star
+        let center = rand(n as u64) as usize;
+        let mut g = vec![vec![]; n];
+        for v in (0..n).filter(|&v| v != center) {
+            let w = rand(10);
+            g[center].push((v, w));
+            g[v].push((center, w));
+        }
+        test(g);
+
+        // random
+        for _ in 0..10 {
+            let mut g = vec![vec![]; n];
+            for v in 1..n {
+                let u = rand(v as u64) as usize;
+                let w = rand(10);
+                g[u].push((v, w));
+                g[v].push((u, w));
+            }
+            test(g);
+        }
+    }
+}
+
+#[test]
+fn test_subtree_sums() {
+    let mut f =
+        std::iter::successors(Some(397_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = |n: u64| f.next().unwrap() % n;
+
+    let naive = |g: &[Vec<(usize, ())>], root: usize, weight: &[u64]| {
+        let n = g.len();
+        let mut par = vec![n; n];
+        let mut stack = vec![root];
+        par[root] = root;
+        while let Some(v) = stack.pop() {
+            for &(nv, ()) in &g[v] {
+                if par[nv] == n {
+                    par[nv] = v;
+                    stack.push(nv);
+                }
+            }
+        }
+        let mut res = vec![0; n];
+        for (u, &wu) in weight.iter().enumerate() {
+            // u の祖先（u 自身を含む）すべてに寄与する。
+            let mut v = u;
+            loop {
+                res[v] += wu;
+                if v == root {
+                    break;
+                }
+                v = par[v];
+            }
+        }
+        res
+    };
+
+    let test = |g: Vec<Vec<(usize, ())>>| {
+        let n = g.len();
+        let weight: Vec<_> = (0..n as u64).map(|v| v * v % 97).collect();
+        let ones = vec![1; n];
+        let tc: TreeCata<_> = g.clone().into();
+        for root in 0..n {
+            let sizes: Vec<_> = naive(&g, root, &ones)
+                .into_iter()
+                .map(|s| s as usize)
+                .collect();
+            assert_eq!(tc.subtree_sizes(root), sizes);
+            assert_eq!(
+                tc.subtree_weight_sums(root, &weight),
+                naive(&g, root, &weight)
+            );
+        }
+    };
+
+    for n in 1..=30 {
+        // path
+        let mut g = vec![vec![]; n];
+        for v in 1..n {
+            g[v - 1].push((v, ()));
+            g[v].push((v - 1, ()));
+        }
+        test(g);
+
+        // star
+        let center = n / 2;
+        let mut g = vec![vec![]; n];
+        for v in (0..n).filter(|&v| v != center) {
+            g[center].push((v, ()));
+            g[v].push((center, ()));
+        }
+        test(g);
+
+        // random
+        for _ in 0..10 {
+            let mut g = vec![vec![]; n];
+            for v in 1..n {
+                let u = rand(v as u64) as usize;
+                g[u].push((v, ()));
+                g[v].push((u, ()));
+            }
+            test(g);
+        }
+    }
+}
+
+#[test]
+fn test_from_edges() {
+    let mut f =
+        std::iter::successors(Some(258_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = |n: u64| f.next().unwrap() % n;
+
+    for n in 0..=30 {
+        // 辺を確率 1/4 で除くことで、森も試す
+        let es: Vec<_> = (1..n)
+            .filter_map(|v| {
+                let u = rand(v as u64) as usize;
+                match rand(4) {
+                    0 => None,
+                    1 => Some((u, v, v)),
+                    _ => Some((v, u, v)),
+                }
+            })
+            .collect();
+        let mut g: Vec<_> = (0..n).map(|_| vec![]).collect();
+        for &(u, v, w) in &es {
+            g[u].push((v, w));
+            g[v].push((u, w));
+        }
+
+        let empty = "".to_owned();
+        let map = |x: &String, c: &usize| format!("({} {} )", x, c);
+        let fold = |x: &String, y: &String| format!("{}{}", x, y);
+        let expected = TreeCata::from(g).each_root(empty.clone(), map, fold);
+        let actual =
+            TreeCata::from_edges(n, es).each_root(empty.clone(), map, fold);
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+#[should_panic(expected = "self-loops are not allowed")]
+fn test_from_edges_self_loop() {
+    TreeCata::from_edges(2, vec![(0, 1, ()), (1, 1, ())]);
+}
+
+#[test]
+#[should_panic(expected = "not a forest")]
+fn test_from_edges_multi_edge() {
+    TreeCata::from_edges(3, vec![(0, 1, ()), (1, 0, ())]);
+}
+
+#[test]
+#[should_panic(expected = "not a forest")]
+fn test_from_edges_too_many_edges() {
+    TreeCata::from_edges(2, vec![(0, 1, ()), (1, 0, ())]);
+}
+
+#[test]
+#[should_panic(expected = "not a forest")]
+fn test_from_edges_cycle() {
+    // 辺は n - 1 本だが、閉路と孤立点からなる
+    TreeCata::from_edges(4, vec![(0, 1, ()), (1, 2, ()), (2, 0, ())]);
+}
+
+#[test]
+fn test_subtree() {
+    let mut f =
+        std::iter::successors(Some(291_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = |n: u64| f.next().unwrap() % n;
+
+    fn naive(g: &[Vec<(usize, usize)>], v: usize, p: usize) -> String {
+        g[v].iter()
+            .filter(|&&(nv, _)| nv != p)
+            .map(|&(nv, c)| format!("({} {} )", naive(g, nv, v), c))
+            .collect()
+    }
+
+    let empty = "".to_owned();
+    let map = |x: &String, c: &usize| format!("({} {} )", x, c);
+    let fold = |x: &String, y: &String| format!("{}{}", x, y);
+
+    let tc: TreeCata<usize> = vec![vec![]].into();
+    assert_eq!(tc.subtree(empty.clone(), map, fold), [""]);
+
+    for n in 1..=30 {
+        let mut g: Vec<_> = (0..n).map(|_| vec![]).collect();
+        for v in 1..n {
+            let u = rand(v as u64) as usize;
+            g[u].push((v, v));
+            g[v].push((u, v));
+        }
+        for gv in &mut g {
+            // 隣接リストの順序を適当に入れ換える
+            let len = gv.len();
+            for i in 1..len {
+                gv.swap(i, rand(i as u64 + 1) as usize);
+            }
+        }
+
+        let par = {
+            let mut par = vec![n; n];
+            let mut stack = vec![0];
+            while let Some(v) = stack.pop() {
+                for &(nv, _) in &g[v] {
+                    if nv != 0 && par[nv] == n {
+                        par[nv] = v;
+                        stack.push(nv);
+                    }
+                }
+            }
+            par
+        };
+        let expected: Vec<_> = (0..n).map(|v| naive(&g, v, par[v])).collect();
+        let tc: TreeCata<_> = g.into();
+        let actual = tc.subtree(empty.clone(), map, fold);
+        assert_eq!(actual, expected);
+        assert_eq!(actual[0], tc.each_root(empty.clone(), map, fold)[0]);
+    }
+}
+
+#[test]
+fn test_forest() {
+    let mut f = std::iter::successors(Some(93739_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = |n: u64| f.next().unwrap() % n;
+
+    fn random_tree(
+        n: usize,
+        rand: &mut impl FnMut(u64) -> u64,
+    ) -> Vec<Vec<(usize, u64)>> {
+        let mut g: Vec<_> = (0..n).map(|_| vec![]).collect();
+        for v in 1..n {
+            let u = rand(v as u64) as usize;
+            let w = 1 + rand(10);
+            g[u].push((v, w));
+            g[v].push((u, w));
+        }
+        g
+    }
+
+    fn merge<U: Clone + Default>(
+        ids: [&[usize]; 2],
+        xs: [Vec<U>; 2],
+    ) -> Vec<U> {
+        let n = ids[0].len() + ids[1].len();
+        let mut res = vec![U::default(); n];
+        for (id, x) in ids.iter().zip(xs) {
+            for (v, xv) in x.into_iter().enumerate() {
+                res[id[v]] = xv;
+            }
+        }
+        res
+    }
+
+    let empty = "".to_owned();
+    let map = |x: &String, c: &u64| format!("({} {} )", x, c);
+    let fold = |x: &String, y: &String| format!("{}{}", x, y);
+
+    for &(n1, n2) in &[(1, 1), (1, 5), (5, 1), (10, 20), (30, 30)] {
+        let g1 = random_tree(n1, &mut rand);
+        let g2 = random_tree(n2, &mut rand);
+        let n = n1 + n2;
+
+        // 各成分の頂点番号の大小関係を保ったまま混ぜる。
+        let mut is_first: Vec<_> = (0..n).map(|i| i < n1).collect();
+        for i in 1..n {
+            is_first.swap(i, rand(i as u64 + 1) as usize);
+        }
+        let id1: Vec<_> = (0..n).filter(|&v| is_first[v]).collect();
+        let id2: Vec<_> = (0..n).filter(|&v| !is_first[v]).collect();
+        let ids = [&id1[..], &id2[..]];
+
+        let mut g: Vec<_> = (0..n).map(|_| vec![]).collect();
+        for (gi, id) in [&g1, &g2].iter().zip(ids) {
+            for (v, gv) in gi.iter().enumerate() {
+                g[id[v]] = gv.iter().map(|&(nv, w)| (id[nv], w)).collect();
+            }
+        }
+        let tc1: TreeCata<_> = g1.into();
+        let tc2: TreeCata<_> = g2.into();
+        let tc: TreeCata<_> = g.into();
+
+        let x1 = tc1.each_root(empty.clone(), map, fold);
+        let x2 = tc2.each_root(empty.clone(), map, fold);
+        let expected = merge(ids, [x1, x2]);
+        assert_eq!(tc.each_root(empty.clone(), map, fold), expected);
+
+        let x1 = tc1.subtree(empty.clone(), map, fold);
+        let x2 = tc2.subtree(empty.clone(), map, fold);
+        let expected = merge(ids, [x1, x2]);
+        assert_eq!(tc.subtree(empty.clone(), map, fold), expected);
+
+        let x1 = tc1.heights_by(|&w| w);
+        let x2 = tc2.heights_by(|&w| w);
+        assert_eq!(tc.heights_by(|&w| w), merge(ids, [x1, x2]));
+
+        let x1 = tc1.nearest_from(&[0], |&w| w);
+        let x2 = vec![u64::MAX; n2];
+        assert_eq!(tc.nearest_from(&[id1[0]], |&w| w), merge(ids, [x1, x2]));
+
+        let (d1, _, _) = tc1.diameter_by(|&w| w);
+        let (d2, _, _) = tc2.diameter_by(|&w| w);
+        let (d, u, v) = tc.diameter_by(|&w| w);
+        assert_eq!(d, d1.max(d2));
+        assert_eq!(is_first[u], is_first[v]);
+        assert_eq!(tc.nearest_from(&[u], |&w| w)[v], d);
+    }
+}
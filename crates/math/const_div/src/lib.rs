@@ -237,6 +237,91 @@ impl ConstDiv {
     }
 }
 
+/// `u128` に対する定数除算。
+///
+/// [`ConstDiv`] と同様に、同じ値で何度も除算する際に用いる。
+///
+/// $d = 2^s$ であれば $s$ bit 右シフトするだけである。
+/// そうでないとき、$M = \\lfloor (2^{128}-1)/d\\rfloor$ を前計算しておき、
+/// $q = \\lfloor n\\cdot M/2^{128}\\rfloor$ とする。
+/// $M\\gt (2^{128}-1)/d - 1$ より、$0\\le n\\lt 2^{128}$ に対して
+/// $$ \\frac{n\\vphantom{2^s}}{d} - 2
+/// \\lt \\frac{n\\cdot M}{2^{128}}
+/// \\lt \\frac{n\\vphantom{2^s}}{d} $$
+/// が成り立つので、$\\lfloor n/d\\rfloor-2\\le q\\le\\lfloor n/d\\rfloor$ となる。
+/// よって、$n-q\\cdot d$ から $d$ を高々 $2$ 回引くことで剰余が求まる。
+///
+/// $n\\cdot M$ の上位 128 bit は、各々を 64 bit ずつに分けて計算する。
+///
+/// [`ConstDiv`]: struct.ConstDiv.html
+///
+/// # Panics
+/// `ConstDiv128::new(0)` は panic する。
+///
+/// # Examples
+/// ```
+/// use nekolib::math::ConstDiv128;
+///
+/// let d = (1_u128 << 100) + 277;
+/// let cd = ConstDiv128::new(d);
+/// let n = u128::MAX - 12345;
+/// assert_eq!(cd.quot(n), n / d);
+/// assert_eq!(cd.rem(n), n % d);
+///
+/// let cd = ConstDiv128::new(1 << 70);
+/// assert_eq!(cd.quot(n), n >> 70);
+/// assert_eq!(cd.rem(n), n & ((1 << 70) - 1));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConstDiv128 {
+    n: u128,
+    di: DivAlgo128,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DivAlgo128 {
+    Shr(u32, u128),
+    MulHi(u128),
+}
+
+impl ConstDiv128 {
+    pub fn new(n: u128) -> Self {
+        assert_ne!(n, 0, "division by zero");
+        if n.is_power_of_two() {
+            let di = DivAlgo128::Shr(n.trailing_zeros(), n - 1);
+            return Self { n, di };
+        }
+        Self { n, di: DivAlgo128::MulHi(u128::MAX / n) }
+    }
+    fn quot_rem(&self, n: u128) -> (u128, u128) {
+        match self.di {
+            DivAlgo128::Shr(s, a) => (n >> s, n & a),
+            DivAlgo128::MulHi(m) => {
+                let mut q = mul_hi_128(n, m);
+                let mut r = n - q * self.n;
+                while r >= self.n {
+                    q += 1;
+                    r -= self.n;
+                }
+                (q, r)
+            }
+        }
+    }
+    pub fn quot(&self, n: u128) -> u128 { self.quot_rem(n).0 }
+    pub fn rem(&self, n: u128) -> u128 { self.quot_rem(n).1 }
+}
+
+fn mul_hi_128(x: u128, y: u128) -> u128 {
+    let mask = u64::MAX as u128;
+    let (x1, x0) = (x >> 64, x & mask);
+    let (y1, y0) = (y >> 64, y & mask);
+    let lo = x0 * y0;
+    let mid0 = x1 * y0;
+    let mid1 = x0 * y1;
+    let carry = ((lo >> 64) + (mid0 & mask) + (mid1 & mask)) >> 64;
+    x1 * y1 + (mid0 >> 64) + (mid1 >> 64) + carry
+}
+
 #[test]
 fn test_small_2() {
     for n in 1..=500 {
@@ -274,3 +359,69 @@ fn test_corner() {
         }
     }
 }
+
+#[test]
+fn test_128_small() {
+    for n in 1..=300 {
+        let cd = ConstDiv128::new(n);
+        for a in (0..5 * n * n).chain((0..5 * n * n).map(|a| !a)) {
+            assert_eq!(cd.quot(a), a / n);
+            assert_eq!(cd.rem(a), a % n);
+        }
+    }
+}
+
+#[test]
+fn test_128_random() {
+    let mut f = std::iter::successors(Some(748_u64), |&x| {
+        Some(x.wrapping_mul(6364136223846793005).wrapping_add(1))
+    });
+    let mut rand128 = || {
+        let (hi, lo) = (f.next().unwrap(), f.next().unwrap());
+        ((hi as u128) << 64 | lo as u128) >> (hi >> 57)
+    };
+
+    for _ in 0..3000 {
+        let d = rand128().max(1);
+        let cd = ConstDiv128::new(d);
+        for _ in 0..100 {
+            let n = rand128();
+            assert_eq!(cd.quot(n), n / d);
+            assert_eq!(cd.rem(n), n % d);
+        }
+        for &n in &[0, 1, d - 1, d, d.saturating_add(1), u128::MAX] {
+            assert_eq!(cd.quot(n), n / d);
+            assert_eq!(cd.rem(n), n % d);
+        }
+    }
+}
+
+#[test]
+fn test_128_corner() {
+    let ds = [
+        1,
+        2,
+        3,
+        (1 << 64) - 1,
+        1 << 64,
+        (1 << 64) + 1,
+        (1 << 127) - 1,
+        1 << 127,
+        (1 << 127) + 1,
+        u128::MAX - 1,
+        u128::MAX,
+    ];
+    for &d in &ds {
+        let cd = ConstDiv128::new(d);
+        for &n in &ds {
+            for n in [0, 1, n - 1, n, n.saturating_add(1)].iter().copied() {
+                assert_eq!(cd.quot(n), n / d);
+                assert_eq!(cd.rem(n), n % d);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "division by zero")]
+fn test_128_zero() { ConstDiv128::new(0); }
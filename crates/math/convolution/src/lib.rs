@@ -1,3 +1,4 @@
+use std::ops::{Add, Mul, Sub};
 use std::sync::OnceLock;
 
 use garner::{CrtMod as CrtModInternal, CrtWrapping};
@@ -280,6 +281,122 @@ pub fn butterfly_inv<M: NttFriendly>(a: &mut [StaticModInt<M>]) {
     }
 }
 
+/// `u32` の配列を直接 NTT する。
+///
+/// [`butterfly`] と同じ変換を、`StaticModInt` を介さずに in-place で行う。
+/// すなわち、`a` の要素を $\\mathbb{Z}/m\\mathbb{Z}$ の元と見なしたとき、
+/// 変換後の値は [`butterfly`] のそれと一致する（出力はビット反転順）。
+/// 内部では Montgomery 乗算を用いる。
+///
+/// # Requirements
+/// 以下を満たさない場合の結果は未規定である（panic するとは限らない）。
+/// - `a.len()` は $2$ の冪である。
+/// - `modulus` は $2^{31}$ 未満の素数で、`a.len()` は `modulus - 1` を割り切る。
+/// - `root` は `modulus` を法とする原始根である。
+/// - `a` の各要素は `modulus` 未満である。
+///
+/// [`NttFriendly`] を実装している法であれば、`NttFriendly::PRIMITIVE_ROOT`
+/// を `root` として渡せばよい。
+///
+/// # Complexity
+/// $O(n\\log(n) + \\log(m))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::{butterfly, ntt_u32, ModInt998244353, ModIntBase};
+///
+/// let mut a = vec![1, 2, 3, 4, 5, 6, 7, 8];
+/// let mut b: Vec<_> = a.iter().map(|&x| ModInt998244353::new(x)).collect();
+/// ntt_u32(&mut a, 998244353, 3);
+/// butterfly(&mut b);
+/// assert!(a.iter().zip(&b).all(|(&x, y)| x == y.get()));
+/// ```
+pub fn ntt_u32(a: &mut [u32], modulus: u32, root: u32) {
+    let n = a.len();
+    if n <= 1 {
+        return;
+    }
+    let h = n.trailing_zeros();
+
+    let mg = Montgomery::new(modulus);
+    let rank2 = (modulus - 1).trailing_zeros() as usize;
+    let mut roots = vec![0; rank2 + 1];
+    let mut iroots = vec![0; rank2 + 1];
+    roots[rank2] = mod_pow(root, (modulus - 1) >> rank2, modulus);
+    iroots[rank2] = mod_pow(roots[rank2], modulus - 2, modulus);
+    for i in (0..rank2).rev() {
+        roots[i] = mod_pow(roots[i + 1], 2, modulus);
+        iroots[i] = mod_pow(iroots[i + 1], 2, modulus);
+    }
+    let mut rate2 = vec![0; rank2.saturating_sub(1)];
+    let mut prod = mg.encode(1);
+    for (i, ri) in rate2.iter_mut().enumerate() {
+        *ri = mg.mul(mg.encode(roots[i + 2]), prod);
+        prod = mg.mul(prod, mg.encode(iroots[i + 2]));
+    }
+
+    for ai in a.iter_mut() {
+        *ai = mg.encode(*ai);
+    }
+    for len in 0..h {
+        let p = 1 << (h - len - 1);
+        let mut rot = mg.encode(1);
+        for s in 0..1 << len {
+            let offset = s << (h - len);
+            for i in offset..offset + p {
+                let l = a[i];
+                let r = mg.mul(a[i + p], rot);
+                a[i] = mg.add(l, r);
+                a[i + p] = mg.sub(l, r);
+            }
+            if s + 1 != 1 << len {
+                rot = mg.mul(rot, rate2[(!s).trailing_zeros() as usize]);
+            }
+        }
+    }
+    for ai in a.iter_mut() {
+        *ai = mg.decode(*ai);
+    }
+}
+
+/// Montgomery 乗算。
+///
+/// $R = 2^{32}$ とし、$x$ を $xR \\bmod m$ で表す。$m \\lt 2^{31}$ とする。
+struct Montgomery {
+    m: u32,
+    /// $-m^{-1} \\bmod R$
+    m_neg_inv: u32,
+    /// $R^2 \\bmod m$
+    r2: u32,
+}
+
+impl Montgomery {
+    fn new(m: u32) -> Self {
+        let mut inv = m;
+        for _ in 0..4 {
+            inv = inv.wrapping_mul(2_u32.wrapping_sub(m.wrapping_mul(inv)));
+        }
+        let r = (1_u64 << 32) % m as u64;
+        let r2 = (r * r % m as u64) as u32;
+        Self { m, m_neg_inv: inv.wrapping_neg(), r2 }
+    }
+    fn reduce(&self, t: u64) -> u32 {
+        let u = (t as u32).wrapping_mul(self.m_neg_inv) as u64;
+        let res = ((t + u * self.m as u64) >> 32) as u32;
+        if res >= self.m { res - self.m } else { res }
+    }
+    fn mul(&self, x: u32, y: u32) -> u32 { self.reduce(x as u64 * y as u64) }
+    fn add(&self, x: u32, y: u32) -> u32 {
+        let res = x + y;
+        if res >= self.m { res - self.m } else { res }
+    }
+    fn sub(&self, x: u32, y: u32) -> u32 {
+        if x >= y { x - y } else { x + self.m - y }
+    }
+    fn encode(&self, x: u32) -> u32 { self.mul(x, self.r2) }
+    fn decode(&self, x: u32) -> u32 { self.reduce(x as u64) }
+}
+
 pub fn convolve<M: NttFriendly>(
     a: Vec<StaticModInt<M>>,
     b: Vec<StaticModInt<M>>,
@@ -298,25 +415,626 @@ pub fn convolve<M: NttFriendly>(
     }
 }
 
+pub fn convolve_into<M: NttFriendly>(
+    a: &[StaticModInt<M>],
+    b: &[StaticModInt<M>],
+    out: &mut Vec<StaticModInt<M>>,
+) {
+    out.clear();
+    if a.is_empty() || b.is_empty() {
+        return;
+    }
+    let (n, m) = (a.len(), b.len());
+
+    if n.min(m) <= 60 {
+        convolve_naive_into(a, b, out);
+    } else if (n + m - 2).is_power_of_two() {
+        convolve_pow2p1_into(a, b, out);
+    } else {
+        out.extend_from_slice(a);
+        let z = (n + m - 1).next_power_of_two();
+        let mut tmp = Vec::with_capacity(z);
+        tmp.extend_from_slice(b);
+        convolve_fft_inplace(out, tmp);
+    }
+}
+
+/// truncated FFT による畳み込み。
+///
+/// 出力長 $L$ が $2^k$ を少し超える場合に、長さ $2^{k+1}$ の NTT
+/// を行う代わりに、おおよそ長さ $L$ ぶんの計算量で畳み込みを行う。
+///
+/// # Idea
+/// 長さ $N = 2^{k+1}$ の NTT の評価点をビット反転順に並べたとき、先頭
+/// $L$ 個の点で評価すれば積は復元できる（van der Hoeven の truncated FFT）。
+/// $L = 2^{j\_1} + 2^{j\_2} + \\dots$ ($j\_1\\gt j\_2\\gt\\dots$) と分けると、
+/// 先頭から $2^{j\_i}$ 個ずつの点は $x^{2^{j\_i}}-c\_i$ の根全体となる。
+/// そこで、$a\\bmod (x^{2^{j\_i}}-c\_i)$ を求め、根の間隔に合わせて係数を
+/// ひねってから長さ $2^{j\_i}$ の NTT で $ab\\bmod (x^{2^{j\_i}}-c\_i)$
+/// を求める。最後に、これらから中国剰余定理（Garner のアルゴリズム）で
+/// $ab$ を復元する。$x^{2^{j\_l}}\\equiv c\_i^{2^{j\_l-j\_i}}$
+/// $\\pmod{x^{2^{j\_i}}-c\_i}$ より、必要な法の積の剰余はすべて定数になる。
+///
+/// # Implementation notes
+/// van der Hoeven のものとは異なり in-place ではなく、ブロックごとに
+/// 剰余を取る $O(L)$ 時間の処理がかかる。ブロック数が多いと損なので、
+/// $L$ を切り上げてブロックを高々 4 個に抑えている。
+///
+/// $L = 2^{20}+19$ ($n = m = 2^{19}+10$) では、長さ $2^{21}$ の NTT
+/// による畳み込みと比べて 6〜7 割程度の時間で動作した（`tft_bench` テストで計測。
+/// `cargo test --release -- --ignored` で実行できる）。
+/// なお、$L = 2^k+1$ のときは [`convolve`] も専用の処理を行うため、そちらの方が速い。
+///
+/// # Complexity
+/// $O(L\\log(L))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::{convolve, convolve_tft, ModInt998244353, ModIntBase};
+///
+/// type Mi = ModInt998244353;
+/// let a: Vec<_> = (0..300).map(Mi::new).collect();
+/// let b: Vec<_> = (0..300).map(|x| Mi::new(x * x)).collect();
+/// assert_eq!(convolve_tft(a.clone(), b.clone()), convolve(a, b));
+/// ```
+pub fn convolve_tft<M: NttFriendly>(
+    a: Vec<StaticModInt<M>>,
+    b: Vec<StaticModInt<M>>,
+) -> Vec<StaticModInt<M>> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let (n, m) = (a.len(), b.len());
+    if n.min(m) <= 60 {
+        return convolve_naive(&a, &b);
+    }
+
+    let len = n + m - 1;
+    let blocks = tft_blocks(len);
+    if blocks.len() == 1 {
+        return convolve_fft(a, b);
+    }
+    let h = ceil_pow2(blocks.iter().sum::<usize>() as u32);
+    let omega = M::butterfly_cache().root[h as usize];
+
+    // (c_i, ab mod (x^{d_i} - c_i))
+    let mut rems = vec![];
+    let mut offset = 0;
+    for &d in &blocks {
+        let rev = (offset as u32).reverse_bits().checked_shr(32 - h);
+        let gamma = omega.pow(rev.unwrap_or(0).into());
+        let c = gamma.pow(d as u64);
+        let mut fa = fold_rem(&a, d, c);
+        let mut fb = fold_rem(&b, d, c);
+        let mut tw = StaticModInt::new(1);
+        for (ai, bi) in fa.iter_mut().zip(&mut fb) {
+            *ai *= tw;
+            *bi *= tw;
+            tw *= gamma;
+        }
+        butterfly(&mut fa);
+        butterfly(&mut fb);
+        for (ai, bi) in fa.iter_mut().zip(&fb) {
+            *ai *= *bi;
+        }
+        butterfly_inv(&mut fa);
+        let mut tw = StaticModInt::new(d).recip();
+        let igamma = gamma.recip();
+        for ai in &mut fa {
+            *ai *= tw;
+            tw *= igamma;
+        }
+        rems.push((c, fa));
+        offset += d;
+    }
+
+    // ab = v_0 + m_0 (v_1 + m_1 (v_2 + ...)), m_i = x^{d_i} - c_i
+    let mut vs: Vec<Vec<StaticModInt<M>>> = vec![];
+    for (ck, rk) in &rems {
+        let dk = rk.len();
+        let mut vk = rk.clone();
+        // prod_{l < i} m_l mod m_k
+        let mut coef = StaticModInt::new(1);
+        for (i, vi) in vs.iter().enumerate() {
+            for (x, y) in vk.iter_mut().zip(fold_rem(vi, dk, *ck)) {
+                *x -= coef * y;
+            }
+            let (ci, di) = (rems[i].0, rems[i].1.len());
+            coef *= ck.pow((di / dk) as u64) - ci;
+        }
+        let icoef = coef.recip();
+        for x in &mut vk {
+            *x *= icoef;
+        }
+        vs.push(vk);
+    }
+
+    let mut res = vs.pop().unwrap();
+    while let Some(vk) = vs.pop() {
+        let (ck, dk) = (rems[vs.len()].0, vk.len());
+        let mut tmp = vec![StaticModInt::new(0); dk + res.len()];
+        for (i, &x) in res.iter().enumerate() {
+            tmp[i + dk] += x;
+            tmp[i] -= ck * x;
+        }
+        for (x, y) in tmp.iter_mut().zip(vk) {
+            *x += y;
+        }
+        res = tmp;
+    }
+    res.truncate(len);
+    res
+}
+
+/// 畳み込みに関する $k$ 乗。
+///
+/// $a$ を $k$ 個畳み込んだ列 $a\\ast a\\ast\\dots\\ast a$ を返す。
+/// 多項式として見ると $a(x)^k$ であり、$x^n$ などで打ち切らずにすべての係数を求める。
+/// 長さ $n$ の $a$ に対して、結果の長さは $(n-1)k+1$ となる。
+/// ただし、$k = 0$ のときは $[1]$、$a$ が空のときは空を返す。
+///
+/// # Idea
+/// 結果の長さ $L = (n-1)k+1$ 以上の $2$ べき $z$ を取り、長さ $z$ の NTT
+/// をしたのち各点で $k$ 乗して逆変換する。次数が $z$ 未満なので巡回による重なりは起きない。
+/// 繰り返し二乗法で畳み込むのに比べて、NTT が一度で済む。
+///
+/// # Complexity
+/// $O(L\\log(L) + z\\log(k))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::{convolve_pow, ModInt998244353, ModIntBase};
+///
+/// type Mi = ModInt998244353;
+/// // サイコロを 3 個振ったときの出目の和の分布
+/// let dice: Vec<_> = (0..=6).map(|i| Mi::new((i > 0) as u32)).collect();
+/// let sum: Vec<_> = convolve_pow(dice, 3).iter().map(|x| x.get()).collect();
+/// assert_eq!(sum.len(), 19);
+/// assert_eq!(sum[3], 1);
+/// assert_eq!(sum[10], 27);
+/// assert_eq!(sum.iter().sum::<u32>(), 216);
+/// ```
+pub fn convolve_pow<M: NttFriendly>(
+    mut a: Vec<StaticModInt<M>>,
+    k: u64,
+) -> Vec<StaticModInt<M>> {
+    if k == 0 {
+        return vec![StaticModInt::new(1)];
+    }
+    if a.is_empty() || k == 1 {
+        return a;
+    }
+    let n = a.len();
+    if n == 1 {
+        return vec![a[0].pow(k)];
+    }
+
+    let len = (n - 1) * k as usize + 1;
+    let z = len.next_power_of_two();
+    a.resize(z, StaticModInt::new(0));
+    butterfly(&mut a);
+    for ai in a.iter_mut() {
+        *ai = ai.pow(k);
+    }
+    butterfly_inv(&mut a);
+
+    a.truncate(len);
+    let iz = StaticModInt::new(z).recip();
+    for ai in a.iter_mut() {
+        *ai *= iz;
+    }
+    a
+}
+
+/// subset sum convolution。
+///
+/// 長さ $2^n$ の $a$, $b$ に対して、長さ $2^n$ の $c$ であって
+/// $$ c\_S = \\sum\_{T\\subseteq S} a\_T\\cdot b\_{S\\setminus T} $$
+/// なるものを返す。添字の集合は $n$ bit の整数で表す。
+/// $n = 0$（長さ $1$）のときは $[a\_0 b\_0]$ を返す。
+///
+/// # Idea
+/// 単に $T\\cup U = S$ なる組について $a\_T b\_U$ を足し合わせるのであれば、
+/// 部分集合についての累積和（zeta 変換）をして各点で掛け、
+/// 逆変換（Möbius 変換）すればよい（OR convolution）。
+/// ここでは $T\\cap U = \\emptyset$ の条件も必要だが、これは
+/// $|T|+|U| = |S|$ と同値なので、要素数ごとに分けて
+/// $\\hat{a}\_k$ と $\\hat{b}\_{k\'}$ をそれぞれ変換し、各点で
+/// 要素数についての畳み込みを行えばよい。
+/// 最後に $\\hat{c}\_{|S|}$ を逆変換したものの $S$ の値が $c\_S$ となる。
+///
+/// # Panics
+/// $a$ と $b$ の長さが異なるとき、または長さが $2$ べきでないとき。
+/// 空の列も $2$ べきの長さではないので panic する。
+///
+/// # Complexity
+/// $O(n^2 2^n)$ time, $O(n 2^n)$ space.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::{subset_convolve, ModInt998244353, ModIntBase};
+///
+/// type Mi = ModInt998244353;
+/// let a: Vec<_> = [1, 2, 3, 4].iter().map(|&x| Mi::new(x)).collect();
+/// let b: Vec<_> = [5, 6, 7, 8].iter().map(|&x| Mi::new(x)).collect();
+/// let c: Vec<_> = subset_convolve(&a, &b).iter().map(|x| x.get()).collect();
+/// // c[0b11] = a[0b00] b[0b11] + a[0b01] b[0b10] + a[0b10] b[0b01] + a[0b11] b[0b00]
+/// assert_eq!(c, [5, 16, 22, 60]);
+/// ```
+pub fn subset_convolve<M: Modulus>(
+    a: &[StaticModInt<M>],
+    b: &[StaticModInt<M>],
+) -> Vec<StaticModInt<M>> {
+    assert_eq!(a.len(), b.len(), "the lengths must be equal");
+    assert!(a.len().is_power_of_two(), "the length must be a power of two");
+
+    let len = a.len();
+    let n = len.trailing_zeros() as usize;
+    let zero = StaticModInt::new(0);
+
+    let ranked = |a: &[StaticModInt<M>]| {
+        let mut res = vec![vec![zero; len]; n + 1];
+        for (s, &x) in a.iter().enumerate() {
+            res[s.count_ones() as usize][s] = x;
+        }
+        for row in &mut res {
+            subset_zeta(row);
+        }
+        res
+    };
+    let ra = ranked(a);
+    let rb = ranked(b);
+
+    let mut rc = vec![vec![zero; len]; n + 1];
+    for s in 0..len {
+        for i in 0..=n {
+            for j in 0..=n - i {
+                rc[i + j][s] += ra[i][s] * rb[j][s];
+            }
+        }
+    }
+    for row in &mut rc {
+        subset_mobius(row);
+    }
+    (0..len).map(|s| rc[s.count_ones() as usize][s]).collect()
+}
+
+fn subset_zeta<M: Modulus>(a: &mut [StaticModInt<M>]) {
+    let len = a.len();
+    let mut w = 1;
+    while w < len {
+        for s in (0..len).filter(|&s| s & w != 0) {
+            let x = a[s ^ w];
+            a[s] += x;
+        }
+        w <<= 1;
+    }
+}
+
+fn subset_mobius<M: Modulus>(a: &mut [StaticModInt<M>]) {
+    let len = a.len();
+    let mut w = 1;
+    while w < len {
+        for s in (0..len).filter(|&s| s & w != 0) {
+            let x = a[s ^ w];
+            a[s] -= x;
+        }
+        w <<= 1;
+    }
+}
+
+/// relaxed convolution（オンライン畳み込み）。
+///
+/// $a\_i$, $b\_i$ を $i = 0, 1, \\dots$ の順に 1 項ずつ与え、その都度
+/// $$ c\_i = \\sum\_{j+k=i} a\_j b\_k $$
+/// を得る。$c\_i$ を得た後で $a\_{i+1}$, $b\_{i+1}$ を決めてよいので、
+/// $c\_i = \\sum\_{j\\lt i} a\_j c\_{i-j}$ のような、畳み込みの結果を使いながら
+/// 値が決まっていく DP に用いることができる。
+///
+/// # Idea
+/// $j, k\\ge 1$ なる $(j, k)$ の組を次のブロックに分割する。
+/// - $[s, 2s)\\times [ms, (m+1)s)$ ($s$ は $2$ べき、$m\\ge 1$)
+/// - $[ms, (m+1)s)\\times [s, 2s)$ ($s$ は $2$ べき、$m\\ge 2$)
+///
+/// 各ブロックは $(m+1)s-1$ 項目を受け取った時点で必要な値が揃い、
+/// その寄与先は $(m+1)s$ 項目以降なので、その時点で畳み込んで加算しておけばよい。
+/// $j = 0$ または $k = 0$ の組は、各項を受け取った時点で直接加算する。
+///
+/// # Complexity
+/// $n$ 項を与えるのに $O(n\\log(n)^2)$ time.
+///
+/// # Examples
+/// Catalan 数 $f(x) = 1 + x f(x)^2$ の計算。
+/// ```
+/// use nekolib::math::{ModInt998244353, ModIntBase, RelaxedConvolution};
+///
+/// type Mi = ModInt998244353;
+/// let mut rc = RelaxedConvolution::new();
+/// let mut f = vec![Mi::new(1)];
+/// for i in 0..10 {
+///     let fi = f[i];
+///     f.push(rc.next(fi, fi));
+/// }
+/// let f: Vec<_> = f.iter().map(|x| x.get()).collect();
+/// assert_eq!(f, [1, 1, 2, 5, 14, 42, 132, 429, 1430, 4862, 16796]);
+/// ```
+pub struct RelaxedConvolution<M: NttFriendly> {
+    a: Vec<StaticModInt<M>>,
+    b: Vec<StaticModInt<M>>,
+    c: Vec<StaticModInt<M>>,
+    buf: Vec<StaticModInt<M>>,
+}
+
+impl<M: NttFriendly> RelaxedConvolution<M> {
+    pub fn new() -> Self {
+        Self { a: vec![], b: vec![], c: vec![], buf: vec![] }
+    }
+
+    pub fn next(
+        &mut self,
+        a_i: StaticModInt<M>,
+        b_i: StaticModInt<M>,
+    ) -> StaticModInt<M> {
+        let i = self.a.len();
+        self.a.push(a_i);
+        self.b.push(b_i);
+        if self.c.len() < 2 * (i + 1) {
+            self.c.resize(2 * (i + 1), StaticModInt::new(0));
+        }
+
+        self.c[i] += self.a[i] * self.b[0];
+        if i > 0 {
+            self.c[i] += self.a[0] * self.b[i];
+        }
+
+        let k = i + 1;
+        let lowbit = k & k.wrapping_neg();
+        let mut s = 1;
+        while s <= lowbit && k / s >= 2 {
+            convolve_into(&self.a[s..2 * s], &self.b[k - s..k], &mut self.buf);
+            for (ci, &x) in self.c[k..].iter_mut().zip(&self.buf) {
+                *ci += x;
+            }
+            if k / s >= 3 {
+                convolve_into(
+                    &self.b[s..2 * s],
+                    &self.a[k - s..k],
+                    &mut self.buf,
+                );
+                for (ci, &x) in self.c[k..].iter_mut().zip(&self.buf) {
+                    *ci += x;
+                }
+            }
+            s *= 2;
+        }
+        self.c[i]
+    }
+}
+
+impl<M: NttFriendly> Default for RelaxedConvolution<M> {
+    fn default() -> Self { Self::new() }
+}
+
+/// 浮動小数点数の列の畳み込み。
+///
+/// 長さ $n$ の $a$ と長さ $m$ の $b$ に対して、長さ $n+m-1$ の $c$ であって
+/// $$ c\_k = \\sum\_{i+j=k} a\_i b\_j $$
+/// なるものを返す。$a$ または $b$ が空のときは空の列を返す。
+///
+/// # Idea
+/// 複素数の FFT を用いる。実数列 $a$, $b$ から $z\_i = a\_i + b\_i\\sqrt{-1}$ を作って
+/// FFT したものを $Z$ とすると、$a$, $b$ が実数列であることから
+/// $\\overline{Z\_{-k}} = A\_k - B\_k\\sqrt{-1}$ が成り立つので、
+/// $$ A\_k B\_k = \\frac{Z\_k^2 - \\overline{Z\_{-k}}^2}{4\\sqrt{-1}} $$
+/// となる。よって、FFT を 1 回、逆 FFT を 1 回行うだけで済む。
+///
+/// $\\min\\{n, m\\}$ が小さいときは素朴な $O(nm)$ 時間の方法で計算する。
+///
+/// # Precision
+/// 回転因子は都度 $\\cos$, $\\sin$ で計算しており、誤差の蓄積はない。
+/// マシンイプシロンを $\\varepsilon = 2^{-52}$、FFT の長さを $N$ として、
+/// 各要素の絶対誤差はおおよそ $\\varepsilon\\log\_2(N)\\cdot\\|a\\|\_2\\,\\|b\\|\_2$
+/// 程度に収まる。
+/// たとえば、$0$ 以上 $10^3$ 未満の整数の長さ $10^5$ の列同士であれば
+/// 絶対誤差は $10^{-3}$ 程度以下となり、丸めることで正確な値が得られる。
+/// 値が大きい場合は [`convolve_u64`] などを用いるべきである。
+///
+/// # Complexity
+/// $O((n+m)\\log(n+m))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::convolve_f64;
+///
+/// let a = [1.0, 0.5, -2.0];
+/// let b = [2.0, 4.0];
+/// let c = convolve_f64(&a, &b);
+/// let expected = [2.0, 5.0, -2.0, -8.0];
+/// assert_eq!(c.len(), expected.len());
+/// assert!(c.iter().zip(&expected).all(|(x, y)| (x - y).abs() < 1e-9));
+///
+/// assert!(convolve_f64(&a, &[]).is_empty());
+/// ```
+pub fn convolve_f64(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let (n, m) = (a.len(), b.len());
+    if n.min(m) <= 60 {
+        return convolve_f64_naive(a, b);
+    }
+
+    let len = (n + m - 1).next_power_of_two();
+    let mut z = vec![Complex::new(0.0, 0.0); len];
+    for (zi, &ai) in z.iter_mut().zip(a) {
+        zi.re = ai;
+    }
+    for (zi, &bi) in z.iter_mut().zip(b) {
+        zi.im = bi;
+    }
+    fft(&mut z, false);
+
+    // (Z_k^2 - conj(Z_{-k})^2) / 4i
+    let quarter_neg_i = Complex::new(0.0, -0.25);
+    let mut prod: Vec<_> = (0..len)
+        .map(|k| {
+            let zk = z[k];
+            let zr = z[(len - k) & (len - 1)].conj();
+            (zk * zk - zr * zr) * quarter_neg_i
+        })
+        .collect();
+    fft(&mut prod, true);
+
+    let inv_len = (len as f64).recip();
+    prod[..n + m - 1].iter().map(|x| x.re * inv_len).collect()
+}
+
+fn convolve_f64_naive(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut res = vec![0.0; a.len() + b.len() - 1];
+    for (i, &ai) in a.iter().enumerate() {
+        for (r, &bj) in res[i..].iter_mut().zip(b) {
+            *r += ai * bj;
+        }
+    }
+    res
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Self { Self { re, im } }
+    fn conj(self) -> Self { Self::new(self.re, -self.im) }
+}
+
+impl Add for Complex {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+/// Cooley--Tukey の FFT。`inv` のときは正規化しない逆変換を行う。
+fn fft(a: &mut [Complex], inv: bool) {
+    let len = a.len();
+    let h = len.trailing_zeros();
+    if h == 0 {
+        return;
+    }
+    for i in 0..len {
+        let j = i.reverse_bits() >> (usize::BITS - h);
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let sign = if inv { 1.0 } else { -1.0 };
+    let root: Vec<_> = (0..len / 2)
+        .map(|j| {
+            let theta = sign * std::f64::consts::TAU * j as f64 / len as f64;
+            Complex::new(theta.cos(), theta.sin())
+        })
+        .collect();
+
+    let mut w = 1;
+    while w < len {
+        let step = len / (2 * w);
+        for chunk in a.chunks_exact_mut(2 * w) {
+            let (lo, hi) = chunk.split_at_mut(w);
+            for (j, (x, y)) in lo.iter_mut().zip(hi).enumerate() {
+                let u = *x;
+                let v = *y * root[j * step];
+                *x = u + v;
+                *y = u - v;
+            }
+        }
+        w *= 2;
+    }
+}
+
+fn tft_blocks(len: usize) -> Vec<usize> {
+    // 高々 4 個のブロックになるように切り上げる。
+    let unit = (len.next_power_of_two() >> 4).max(1);
+    let len = len.div_ceil(unit) * unit;
+    (0..usize::BITS).rev().map(|i| 1 << i).filter(|&d| len & d != 0).collect()
+}
+
+fn fold_rem<M: NttFriendly>(
+    a: &[StaticModInt<M>],
+    d: usize,
+    c: StaticModInt<M>,
+) -> Vec<StaticModInt<M>> {
+    // a mod (x^d - c)
+    let mut res = vec![StaticModInt::new(0); d];
+    let mut pw = StaticModInt::new(1);
+    for chunk in a.chunks(d) {
+        for (x, &y) in res.iter_mut().zip(chunk) {
+            *x += y * pw;
+        }
+        pw *= c;
+    }
+    res
+}
+
 fn convolve_naive<M: NttFriendly>(
     a: &[StaticModInt<M>],
     b: &[StaticModInt<M>],
 ) -> Vec<StaticModInt<M>> {
+    let mut res = vec![];
+    convolve_naive_into(a, b, &mut res);
+    res
+}
+
+fn convolve_naive_into<M: NttFriendly>(
+    a: &[StaticModInt<M>],
+    b: &[StaticModInt<M>],
+    out: &mut Vec<StaticModInt<M>>,
+) {
     let (n, m) = (a.len(), b.len());
     let (n, m, a, b) = if n < m { (m, n, b, a) } else { (n, m, a, b) };
-    let mut res = vec![StaticModInt::new(0); n + m - 1];
+    out.clear();
+    out.resize(n + m - 1, StaticModInt::new(0));
     for i in 0..n {
         for j in 0..m {
-            res[i + j] += a[i] * b[j];
+            out[i + j] += a[i] * b[j];
         }
     }
-    res
 }
 
 fn convolve_pow2p1<M: NttFriendly>(
     a: Vec<StaticModInt<M>>,
     b: Vec<StaticModInt<M>>,
 ) -> Vec<StaticModInt<M>> {
+    let mut res = vec![];
+    convolve_pow2p1_into(&a, &b, &mut res);
+    res
+}
+
+fn convolve_pow2p1_into<M: NttFriendly>(
+    a: &[StaticModInt<M>],
+    b: &[StaticModInt<M>],
+    out: &mut Vec<StaticModInt<M>>,
+) {
     let n = a.len();
     let m = b.len();
     let len = n + m - 1;
@@ -327,43 +1045,52 @@ fn convolve_pow2p1<M: NttFriendly>(
     // (a[0] + x a[1..]) (b[0] + x b[1..])
     // a[0] b[0] + x (a[0] b[1..] + b[0] a[1..]) + x^2 a[1..] b[1..]
 
-    let mut res = convolve_fft(a[1..].to_vec(), b[1..].to_vec());
-    res.splice(0..0, (0..2).map(|_| StaticModInt::new(0)));
+    // 後で 2 つ足すぶんも確保しておく。
+    out.clear();
+    out.reserve(len);
+    out.extend_from_slice(&a[1..]);
+    convolve_fft_inplace(out, b[1..].to_vec());
+    out.splice(0..0, (0..2).map(|_| StaticModInt::new(0)));
 
-    res[0] += a[0] * b[0];
+    out[0] += a[0] * b[0];
     for i in 1..n {
-        res[i] += a[i] * b[0];
+        out[i] += a[i] * b[0];
     }
     for j in 1..m {
-        res[j] += a[0] * b[j];
+        out[j] += a[0] * b[j];
     }
-    res
 }
 
 fn convolve_fft<M: NttFriendly>(
     mut a: Vec<StaticModInt<M>>,
-    mut b: Vec<StaticModInt<M>>,
+    b: Vec<StaticModInt<M>>,
 ) -> Vec<StaticModInt<M>> {
+    convolve_fft_inplace(&mut a, b);
+    a
+}
+
+fn convolve_fft_inplace<M: NttFriendly>(
+    a: &mut Vec<StaticModInt<M>>,
+    mut b: Vec<StaticModInt<M>>,
+) {
     let (n, m) = (a.len(), b.len());
     let z = (n + m - 1).next_power_of_two();
     a.resize(z, StaticModInt::new(0));
     b.resize(z, StaticModInt::new(0));
 
-    butterfly(&mut a);
+    butterfly(a);
     butterfly(&mut b);
 
     for (ai, bi) in a.iter_mut().zip(&mut b) {
         *ai *= *bi;
     }
-    butterfly_inv(&mut a);
+    butterfly_inv(a);
 
     a.truncate(n + m - 1);
     let iz = StaticModInt::new(z).recip();
-    for ai in &mut a {
+    for ai in a.iter_mut() {
         *ai *= iz;
     }
-
-    a
 }
 
 macro_rules! impl_modint_ntt {
@@ -739,6 +1466,69 @@ fn sanity_check() {
     assert_eq!(c, [0, 0, 1, 4, 11, 26, 36, 40, 32]);
 }
 
+#[test]
+fn ntt_u32_matches_butterfly() {
+    fn test<M: NttFriendly>() {
+        let mut f = std::iter::successors(Some(296_u64), |&x| {
+            Some(x * 48271 % 2147483647)
+        })
+        .map(|x| (x % M::VALUE as u64) as u32);
+        for h in 0..=12 {
+            let a: Vec<u32> = f.by_ref().take(1 << h).collect();
+            let mut expected: Vec<_> =
+                a.iter().map(|&ai| StaticModInt::<M>::new(ai)).collect();
+            butterfly(&mut expected);
+            let expected: Vec<_> = expected.iter().map(|x| x.get()).collect();
+
+            let mut actual = a;
+            ntt_u32(&mut actual, M::VALUE, M::PRIMITIVE_ROOT);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    test::<Mod998244353>();
+    test::<Mod45e24p1>();
+    test::<Mod5e25p1>();
+    test::<Mod15e27p1>();
+
+    let mut a = vec![998244352; 4];
+    ntt_u32(&mut a, 998244353, 3);
+    assert_eq!(a, [998244349, 0, 0, 0]);
+}
+
+#[test]
+fn pow() {
+    type Mi = modint::ModInt998244353;
+
+    let mut f =
+        std::iter::successors(Some(185_u64), |&x| Some(x * 48271 % 2147483647))
+            .map(Mi::new);
+
+    for n in 0..=10 {
+        let a: Vec<_> = f.by_ref().take(n).collect();
+        let mut expected = vec![Mi::new(1)];
+        for k in 0..=30 {
+            assert_eq!(convolve_pow(a.clone(), k), expected);
+            expected = convolve(expected, a.clone());
+        }
+    }
+
+    // 先頭や末尾が 0 の場合
+    let a: Vec<_> = [0, 0, 3, 0, 1, 0].iter().map(|&x| Mi::new(x)).collect();
+    let mut expected = vec![Mi::new(1)];
+    for k in 0..=20 {
+        assert_eq!(convolve_pow(a.clone(), k), expected);
+        expected = convolve(expected, a.clone());
+    }
+
+    let a: Vec<_> = f.by_ref().take(100).collect();
+    let mut expected = a.clone();
+    for _ in 1..7 {
+        expected = convolve(expected, a.clone());
+    }
+    assert_eq!(convolve_pow(a, 7), expected);
+}
+
 #[test]
 fn proot() {
     assert_eq!(Mod45e24p1::PRIMITIVE_ROOT, 11);
@@ -836,3 +1626,243 @@ fn pow2p1() {
     let expected = convolve_naive(&a, &b);
     assert_eq!(convolve(a, b), expected);
 }
+
+#[test]
+fn into() {
+    type Mi = modint::ModInt998244353;
+
+    let mut out = vec![Mi::new(1); 3];
+    let nm = [(0, 5), (5, 0), (1, 1), (5, 7), (60, 100), (65, 65), (100, 120)];
+    for &(n, m) in &nm {
+        let a: Vec<_> = (0..n).map(|x| Mi::new(x * x + 1)).collect();
+        let b: Vec<_> = (0..m).map(|x| Mi::new(3 * x + 2)).collect();
+        convolve_into(&a, &b, &mut out);
+        assert_eq!(out, convolve(a, b));
+    }
+
+    let a: Vec<_> = (0..200).map(|x| Mi::new(x + 1)).collect();
+    let b = a.clone();
+    let mut out = Vec::with_capacity(1024);
+    let ptr = out.as_ptr();
+    convolve_into(&a, &b, &mut out);
+    assert_eq!(out, convolve_naive(&a, &b));
+    convolve_into(&a[..100], &b[..150], &mut out);
+    assert_eq!(out, convolve_naive(&a[..100], &b[..150]));
+    assert_eq!(out.as_ptr(), ptr);
+}
+
+#[test]
+fn tft() {
+    type Mi = modint::ModInt998244353;
+
+    let nm = [
+        (61, 61),
+        (61, 70),
+        (64, 65),
+        (65, 65),
+        (100, 157),
+        (129, 129),
+        (200, 313),
+        (256, 257),
+        (513, 512),
+        (1000, 1000),
+        (1025, 1024),
+        (1500, 2000),
+    ];
+    for &(n, m) in &nm {
+        let a: Vec<_> = (0..n).map(|x| Mi::new(x * x + 1)).collect();
+        let b: Vec<_> = (0..m).map(|x| Mi::new(3 * x + 2)).collect();
+        let expected = convolve_naive(&a, &b);
+        assert_eq!(convolve_tft(a, b), expected);
+    }
+
+    for len in 1..=2000 {
+        let blocks = tft_blocks(len);
+        assert!(blocks.len() <= 4);
+        assert!(blocks.iter().sum::<usize>() >= len);
+        assert!(blocks.iter().sum::<usize>() <= len.next_power_of_two());
+    }
+}
+
+#[test]
+#[ignore]
+fn tft_bench() {
+    // cargo test --release -p convolution tft_bench -- --ignored --nocapture
+    use std::time::{Duration, Instant};
+
+    type Mi = modint::ModInt998244353;
+
+    let n = (1 << 19) + 10;
+    let a: Vec<_> = (0..n).map(|x| Mi::new(x * x + 1)).collect();
+    let b: Vec<_> = (0..n).map(|x| Mi::new(3 * x + 2)).collect();
+    let bench = |f: fn(Vec<Mi>, Vec<Mi>) -> Vec<Mi>| {
+        let mut best = Duration::MAX;
+        let mut res = vec![];
+        for _ in 0..5 {
+            let (a, b) = (a.clone(), b.clone());
+            let start = Instant::now();
+            res = f(a, b);
+            best = best.min(start.elapsed());
+        }
+        (best, res)
+    };
+
+    let (t_ntt, expected) = bench(convolve);
+    let (t_tft, actual) = bench(convolve_tft);
+    assert_eq!(actual, expected);
+    eprintln!(
+        "L = {}: convolve {:?}, convolve_tft {:?} ({:.2})",
+        n + n - 1,
+        t_ntt,
+        t_tft,
+        t_tft.as_secs_f64() / t_ntt.as_secs_f64()
+    );
+    assert!(t_tft < t_ntt);
+}
+
+#[test]
+fn subset_convolve_naive() {
+    type Mi = modint::ModInt998244353;
+
+    let mut f =
+        std::iter::successors(Some(748_u64), |&x| Some(x * 48271 % 2147483647))
+            .map(Mi::new);
+
+    for n in 0..=8 {
+        let len = 1 << n;
+        let a: Vec<_> = f.by_ref().take(len).collect();
+        let b: Vec<_> = f.by_ref().take(len).collect();
+        let mut expected = vec![Mi::new(0); len];
+        for s in 0..len {
+            // s の部分集合 t を列挙する
+            let mut t = s;
+            loop {
+                expected[s] += a[t] * b[s ^ t];
+                if t == 0 {
+                    break;
+                }
+                t = (t - 1) & s;
+            }
+        }
+        assert_eq!(subset_convolve(&a, &b), expected);
+    }
+
+    let one = [Mi::new(3)];
+    assert_eq!(subset_convolve(&one, &[Mi::new(5)]), [Mi::new(15)]);
+}
+
+#[test]
+#[should_panic(expected = "the length must be a power of two")]
+fn subset_convolve_non_pow2() {
+    type Mi = modint::ModInt998244353;
+    let a = vec![Mi::new(1); 3];
+    subset_convolve(&a, &a);
+}
+
+#[test]
+#[should_panic(expected = "the length must be a power of two")]
+fn subset_convolve_empty() {
+    subset_convolve::<Mod998244353>(&[], &[]);
+}
+
+#[test]
+#[should_panic(expected = "the lengths must be equal")]
+fn subset_convolve_different_len() {
+    type Mi = modint::ModInt998244353;
+    subset_convolve(&[Mi::new(1); 2], &[Mi::new(1); 4]);
+}
+
+#[test]
+fn convolve_f64_small() {
+    let a = [1.0, 2.0, 3.0];
+    let b = [0.5, -1.0];
+    let c = convolve_f64(&a, &b);
+    let expected = [0.5, 0.0, -0.5, -3.0];
+    assert_eq!(c.len(), expected.len());
+    for (x, y) in c.iter().zip(&expected) {
+        assert!((x - y).abs() < 1e-12);
+    }
+
+    assert!(convolve_f64(&[], &b).is_empty());
+    assert!(convolve_f64(&a, &[]).is_empty());
+    assert!(convolve_f64(&[], &[]).is_empty());
+}
+
+#[test]
+fn convolve_f64_fft() {
+    let mut f =
+        std::iter::successors(Some(397_u64), |&x| Some(x * 48271 % 2147483647));
+
+    // 実数値 [-1, 1) の列
+    for &(n, m) in &[(61, 61), (100, 300), (1000, 999), (1024, 1025)] {
+        let mut rand_f64 =
+            || f.next().unwrap() as f64 / (1_u64 << 30) as f64 - 1.0;
+        let a: Vec<_> = (0..n).map(|_| rand_f64()).collect();
+        let b: Vec<_> = (0..m).map(|_| rand_f64()).collect();
+        let actual = convolve_f64(&a, &b);
+        let expected = convolve_f64_naive(&a, &b);
+        assert_eq!(actual.len(), n + m - 1);
+        for (x, y) in actual.iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-9, "{} vs {}", x, y);
+        }
+    }
+
+    // 整数値の列は丸めると一致する
+    let n = 5000;
+    let a: Vec<_> = (0..n).map(|_| (f.next().unwrap() % 1000) as f64).collect();
+    let b: Vec<_> = (0..n).map(|_| (f.next().unwrap() % 1000) as f64).collect();
+    let actual = convolve_f64(&a, &b);
+    let expected = convolve_f64_naive(&a, &b);
+    for (x, y) in actual.iter().zip(&expected) {
+        assert!((x - y).abs() < 1e-3, "{} vs {}", x, y);
+        assert_eq!(x.round(), *y);
+    }
+}
+
+#[test]
+fn relaxed() {
+    type Mi = modint::ModInt998244353;
+
+    let mut f = std::iter::successors(Some(1357_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = |k: u64| f.next().unwrap() % k;
+
+    for n in (1..=70).chain([127, 128, 129, 300]) {
+        let a: Vec<_> =
+            (0..n).map(|_| Mi::new(rand(Mi::modulus() as u64))).collect();
+        let b: Vec<_> =
+            (0..n).map(|_| Mi::new(rand(Mi::modulus() as u64))).collect();
+        let mut rc = RelaxedConvolution::new();
+        let actual: Vec<_> = (0..n).map(|i| rc.next(a[i], b[i])).collect();
+        let mut expected = convolve(a, b);
+        expected.truncate(n);
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn relaxed_ternary_trees() {
+    type Mi = modint::ModInt998244353;
+
+    // t(x) = 1 + x t(x)^3
+    let n = 500;
+    let mut rc2 = RelaxedConvolution::new();
+    let mut rc3 = RelaxedConvolution::new();
+    let mut t = vec![Mi::new(1)];
+    for i in 0..n {
+        let t2_i = rc2.next(t[i], t[i]);
+        let t3_i = rc3.next(t[i], t2_i);
+        t.push(t3_i);
+    }
+
+    // t_i = binom(3i, i) / (2i+1)
+    let mut fact = vec![Mi::new(1); 3 * n + 1];
+    for i in 1..=3 * n {
+        fact[i] = fact[i - 1] * Mi::new(i);
+    }
+    for i in 0..=n {
+        let binom = fact[3 * i] / (fact[i] * fact[2 * i]);
+        assert_eq!(t[i], binom / Mi::new(2 * i + 1));
+    }
+}
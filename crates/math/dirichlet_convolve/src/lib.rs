@@ -0,0 +1,104 @@
+//! Dirichlet 畳み込み。
+
+/// Dirichlet 畳み込み。
+///
+/// 数論的関数 $f$, $g$ に対して
+/// $$ (f\\ast g)(n) = \\sum\_{d\\mid n} f(d)\\cdot g(n/d) $$
+/// を $n = 1, 2, \\dots, N$ について求める。
+///
+/// 配列は 1-indexed とし、`f[i]` が $f(i)$ に対応する。`f[0]` は無視され、
+/// 返り値の `[0]` は $0$ となる。
+///
+/// # Requirements
+/// `f.len() == g.len()`.
+///
+/// # Idea
+/// $d$ を固定して、その倍数 $n = de$ に $f(d)\\cdot g(e)$ を足し込む。
+///
+/// # Complexity
+/// $O(N\\log(N))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::dirichlet_convolve;
+///
+/// let one = [0, 1, 1, 1, 1, 1, 1, 1];
+/// // 約数の個数
+/// assert_eq!(dirichlet_convolve(&one, &one), [0, 1, 2, 2, 3, 2, 4, 2]);
+///
+/// let id = [0, 1, 2, 3, 4, 5, 6, 7];
+/// // 約数の総和
+/// assert_eq!(dirichlet_convolve(&id, &one), [0, 1, 3, 4, 7, 6, 12, 8]);
+///
+/// let mu = [0, 1, -1, -1, 0, -1, 1, -1];
+/// // Möbius 関数は 1 の逆元
+/// assert_eq!(dirichlet_convolve(&mu, &one), [0, 1, 0, 0, 0, 0, 0, 0]);
+/// ```
+pub fn dirichlet_convolve(f: &[i64], g: &[i64]) -> Vec<i64> {
+    assert_eq!(f.len(), g.len());
+    let n = f.len();
+    let mut res = vec![0; n];
+    for d in 1..n {
+        for e in 1..=(n - 1) / d {
+            res[d * e] += f[d] * g[e];
+        }
+    }
+    res
+}
+
+#[test]
+fn test_known() {
+    let n = 1000;
+
+    // 素因数分解から Möbius 関数、Euler の phi 関数を求める
+    let mut mu = vec![0; n + 1];
+    let mut phi = vec![0; n + 1];
+    for i in 1..=n {
+        let (mut m, mut mu_i, mut phi_i) = (i, 1, i as i64);
+        for p in 2..=i {
+            if m % p != 0 {
+                continue;
+            }
+            let mut e = 0;
+            while m % p == 0 {
+                m /= p;
+                e += 1;
+            }
+            mu_i = if e == 1 { -mu_i } else { 0 };
+            phi_i = phi_i / p as i64 * (p as i64 - 1);
+        }
+        mu[i] = mu_i;
+        phi[i] = phi_i;
+    }
+
+    let mut e = vec![0; n + 1];
+    e[1] = 1;
+    let mut one = vec![1; n + 1];
+    one[0] = 0;
+    let id: Vec<_> = (0..=n as i64).collect();
+
+    let naive = |f: &[i64], g: &[i64]| -> Vec<i64> {
+        (0..=n)
+            .map(|i| {
+                (1..=i).filter(|d| i % d == 0).map(|d| f[d] * g[i / d]).sum()
+            })
+            .collect()
+    };
+
+    assert_eq!(dirichlet_convolve(&one, &mu), e);
+    assert_eq!(dirichlet_convolve(&mu, &one), e);
+    assert_eq!(dirichlet_convolve(&phi, &one), id);
+    assert_eq!(dirichlet_convolve(&id, &mu), phi);
+    assert_eq!(dirichlet_convolve(&e, &phi), phi);
+
+    let d = dirichlet_convolve(&one, &one);
+    assert_eq!(d, naive(&one, &one));
+    assert_eq!(dirichlet_convolve(&d, &mu), one);
+    let sigma = dirichlet_convolve(&id, &one);
+    assert_eq!(sigma, naive(&id, &one));
+    assert_eq!(dirichlet_convolve(&phi, &d), sigma);
+    assert_eq!(dirichlet_convolve(&mu, &phi), naive(&mu, &phi));
+
+    assert_eq!(dirichlet_convolve(&[], &[]), []);
+    assert_eq!(dirichlet_convolve(&[5], &[7]), [0]);
+}
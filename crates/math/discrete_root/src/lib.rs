@@ -0,0 +1,162 @@
+//! 離散 $k$ 乗根。
+
+use dlog::DLog;
+use factors::Factors;
+use gcd_recip::GcdRecip;
+use mod_ord::ModOrd;
+use mod_pow::ModPow;
+
+/// 離散 $k$ 乗根。
+///
+/// $x^k \\equiv a \\pmod{n}$ なる $0\\le x\\lt n$ が存在すれば、そのうちのひとつを返す。
+/// 最小のものを返すとは限らない。
+/// $0\\le a\\lt n$ とする。また、[`dlog`] と同様に $0^0 = 1$ とする。
+///
+/// [`dlog`]: ../dlog/trait.DLog.html
+///
+/// # Idea
+/// $n = \\prod\_i p\_i^{e\_i}$ と素因数分解し、各 $p\_i^{e\_i}$ を法として解いたものを
+/// 中国剰余定理でまとめればよい。以下、$q = p^e$ を法として考える。
+///
+/// $a\\equiv 0$ なら $x = 0$ が解である。そうでないとき、$a = p^s\\cdot u$
+/// ($0\\le s\\lt e$, $p\\nmid u$) と書く。$x = p^r\\cdot v$ ($p\\nmid v$) とすると
+/// $x^k = p^{kr}\\cdot v^k$ なので、$kr = s$ かつ $v^k\\equiv u\\pmod{p^{e-s}}$
+/// が解の存在の必要十分条件となる。よって、$k\\nmid s$ なら解なしで、そうでなければ
+/// $u$ が法と互いに素な場合に帰着される。
+///
+/// $q$ を法とする原始根 $g$ が存在するとき、$u\\equiv g^y$ となる $y$ を [`dlog`]
+/// で求め、$x = g^t$ とおく。$\\varphi = \\varphi(q)$ として $kt\\equiv y\\pmod{\\varphi}$
+/// を解けばよく、これは $\\gcd(k, \\varphi)\\mid y$ のときに限り解をもつ。
+///
+/// $q$ を法とする原始根が存在するのは、$p$ が奇素数のときと $q\\in\\{2, 4\\}$
+/// のときである。$8\\mid n$ のときは $(\\mathbb{Z}/2^e\\mathbb{Z})^\\times$
+/// が巡回群でないため、この方針は使えない。
+///
+/// # Panics
+/// - $n = 0$ のとき。
+/// - $8\\mid n$ のとき。
+///
+/// # Complexity
+/// 各 $q = p^e$ について、原始根を探す部分と [`dlog`] の部分が支配的となる。
+/// $n$ が素数のとき、原始根 $g$ を見つけるのに $O(g\\sqrt{n})$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::discrete_root;
+///
+/// // x^2 = 2 (mod 7)
+/// let x = discrete_root(2, 2, 7).unwrap();
+/// assert_eq!(x * x % 7, 2);
+/// // x^2 = 3 (mod 7) は解なし
+/// assert_eq!(discrete_root(2, 3, 7), None);
+///
+/// // x^3 = 10 (mod 30)
+/// let x = discrete_root(3, 10, 30).unwrap();
+/// assert_eq!(x * x * x % 30, 10);
+///
+/// // x^2 = 9 (mod 27) は x = 3 など
+/// let x = discrete_root(2, 9, 27).unwrap();
+/// assert_eq!(x * x % 27, 9);
+/// // x^2 = 3 (mod 9) は解なし
+/// assert_eq!(discrete_root(2, 3, 9), None);
+/// ```
+pub fn discrete_root(k: u64, a: u64, n: u64) -> Option<u64> {
+    assert_ne!(n, 0, "modulo must be positive");
+    assert_ne!(n % 8, 0, "modulo must not be a multiple of 8");
+    if k == 0 {
+        return (a == 1 % n).then_some(0);
+    }
+
+    // x = res (mod m) の形で持ち、素数冪ごとに条件を追加していく。
+    let mut res = 0;
+    let mut m = 1;
+    for (p, e) in n.factors() {
+        let q = p.pow(e);
+        let x = root_prime_power(k, a % q, p, e)?;
+        let (_, r) = m.gcd_recip(q);
+        let t = (x + q - res % q) % q * r % q;
+        res += m * t;
+        m *= q;
+    }
+    Some(res)
+}
+
+fn root_prime_power(k: u64, a: u64, p: u64, e: u32) -> Option<u64> {
+    if a == 0 {
+        return Some(0);
+    }
+    let mut u = a;
+    let mut s = 0;
+    while u.is_multiple_of(p) {
+        u /= p;
+        s += 1;
+    }
+    if !(s as u64).is_multiple_of(k) {
+        return None;
+    }
+    let r = s / k as u32;
+    let v = root_coprime(k, u, p, e - s)?;
+    Some(p.pow(r) * v)
+}
+
+fn root_coprime(k: u64, u: u64, p: u64, e: u32) -> Option<u64> {
+    let q = p.pow(e);
+    let phi = p.pow(e - 1) * (p - 1);
+    let g = (1..q).find(|&g| g.mod_ord(q) == Some(phi)).unwrap();
+    let y = g.dlog(u % q, q).unwrap();
+
+    // k t = y (mod phi)
+    let (d, r) = (k % phi).gcd_recip(phi);
+    if !y.is_multiple_of(d) {
+        return None;
+    }
+    let phi_d = phi / d;
+    let t = y / d % phi_d * r % phi_d;
+    Some(g.mod_pow(t, q))
+}
+
+#[test]
+fn test_small() {
+    for n in (1..=200).filter(|n| n % 8 != 0) {
+        for k in (0..=6).chain(vec![12, 100, 1_000_000_007]) {
+            let mut exists = vec![false; n as usize];
+            for x in 0..n {
+                exists[x.mod_pow(k, n) as usize] = true;
+            }
+            for a in 0..n {
+                let actual = discrete_root(k, a, n);
+                let expected = exists[a as usize];
+                assert_eq!(actual.is_some(), expected, "{:?}", (k, a, n));
+                if let Some(x) = actual {
+                    assert!(x < n);
+                    assert_eq!(x.mod_pow(k, n), a);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_prime() {
+    let p = 1_000_003;
+    let mut x = 1;
+    for k in (1..=50).chain(vec![p - 1, p, 1 << 20]) {
+        for _ in 0..20 {
+            x = x * 500_009 % p;
+            let a = x.mod_pow(k, p);
+            let y = discrete_root(k, a, p).unwrap();
+            assert_eq!(y.mod_pow(k, p), a);
+        }
+    }
+    // 平方非剰余
+    let a = (2..p).find(|&a| a.mod_pow((p - 1) / 2, p) != 1).unwrap();
+    assert_eq!(discrete_root(2, a, p), None);
+    assert_eq!(discrete_root(1, a, p), Some(a));
+    assert_eq!(discrete_root(3, 0, p), Some(0));
+}
+
+#[test]
+#[should_panic(expected = "modulo must not be a multiple of 8")]
+fn test_multiple_of_8() {
+    discrete_root(2, 1, 24);
+}
@@ -143,6 +143,14 @@ use mod_pow::ModPow;
 ///
 /// [`carmichael_lambda`]: fn.carmichael_lambda.html
 ///
+/// $n = p^k$ ($p$: prime, $k\\gt 1$) のときは専用のパスで求める。
+/// $p\\sqsubseteq b$ なら $\\mu\\le k$ かつ $\\lambda=1$ なので愚直に調べる。
+/// そうでなければ、まず $\\bmod p$ で解いておき、$q=p^j$ での解 $z$ と
+/// $\\mathrm{ord}\_q(b)$ から $q\\cdot p$ での解を順に持ち上げる (Hensel lifting)。
+/// $u = (b^{\\mathrm{ord}\_q(b)}-1)/q$ とすると、$(b^{\\mathrm{ord}\_q(b)})^t \\equiv
+/// 1+tuq \\pmod{qp}$ なので、$t$ は $\\bmod p$ の一次方程式を解いて得られる。
+/// 各段 $O(\\log(n))$ 時間なので、全体の計算量は $\\bmod p$ の部分が支配的となる。
+///
 /// # Examples
 /// ```
 /// use nekolib::math::DLog;
@@ -188,6 +196,14 @@ pub trait DLog: Sized {
 }
 
 trait DLogInternal: Sized {
+    fn dlog_general(self, a: Self, n: Self) -> Option<Self>;
+    fn dlog_prime_power(
+        self,
+        a: Self,
+        p: Self,
+        k: u32,
+        n: Self,
+    ) -> Option<Self>;
     fn bs_gs(self, bb: Self, a: Self, n: Self, c: Self) -> Option<Self>;
 }
 
@@ -205,6 +221,18 @@ macro_rules! impl_uint {
                     _ => self,
                 };
 
+                let mut fs = n.factors();
+                if let (Some((p, k)), None) = (fs.next(), fs.next()) {
+                    if k > 1 {
+                        return b.dlog_prime_power(a, p, k, n);
+                    }
+                }
+                b.dlog_general(a, n)
+            }
+        }
+        impl DLogInternal for $t {
+            fn dlog_general(self, a: Self, n: Self) -> Option<Self> {
+                let b = self;
                 let mut n_ = n;
                 let tail = b.factors()
                     .map(|(p, e)| {
@@ -241,8 +269,59 @@ macro_rules! impl_uint {
 
                 b.bs_gs(bb, a, n, c).map(|head| tail + head)
             }
-        }
-        impl DLogInternal for $t {
+            fn dlog_prime_power(
+                self,
+                a: Self,
+                p: Self,
+                k: u32,
+                n: Self,
+            ) -> Option<Self> {
+                let b = self;
+                if b % p == 0 {
+                    // b^z は z >= ceil(k/e) で 0 になる。
+                    let mut bpow = 1;
+                    for i in 0.. {
+                        if bpow == a {
+                            return Some(i);
+                        }
+                        if bpow == 0 {
+                            return None;
+                        }
+                        bpow = bpow * b % n;
+                    }
+                }
+                if a % p == 0 {
+                    return None;
+                }
+
+                let mut z = (b % p).dlog(a % p, p)?;
+                let mut ord = (p - 1)
+                    .divisors()
+                    .find(|&c| b.mod_pow(c, p) == 1)
+                    .unwrap();
+
+                // b^z = a (mod q), ord = ord_q(b) から q p の場合に持ち上げる。
+                let mut q = p;
+                for _ in 1..k {
+                    let qp = q * p;
+                    let bz = b.mod_pow(z, qp);
+                    let w = (a % qp + qp - bz) % qp / q;
+                    let u = (b.mod_pow(ord, qp) + qp - 1) % qp / q;
+                    if u % p == 0 {
+                        if w % p != 0 {
+                            return None;
+                        }
+                    } else {
+                        // (b^ord)^t = 1 + t u q (mod q p) より t u bz = w q
+                        let ub = u * bz % p;
+                        let t = w % p * ub.mod_pow(p - 2, p) % p;
+                        z += t * ord;
+                        ord *= p;
+                    }
+                    q = qp;
+                }
+                Some(z)
+            }
             fn bs_gs(self, bb: Self, a: Self, n: Self, c: Self) -> Option<Self> {
                 let b = self;
                 let step = (1..).find(|&i| i * i * 2 >= c).unwrap();
@@ -300,3 +379,24 @@ fn test() {
         }
     }
 }
+
+#[test]
+fn prime_power() {
+    let ns = (1..=9)
+        .map(|k| 2_u64.pow(k))
+        .chain((2..=5).map(|k| 3_u64.pow(k)))
+        .chain(vec![5_u64.pow(4), 7_u64.pow(3), 11 * 11, 13 * 13]);
+
+    for n in ns {
+        for b in 2..n {
+            let mut x = 1;
+            for e in 0..n {
+                assert_eq!(b.dlog(x, n), b.dlog_general(x, n), "{}^{}", b, e);
+                x = x * b % n;
+            }
+            for a in (0..n).step_by(7) {
+                assert_eq!(b.dlog(a, n), b.dlog_general(a, n));
+            }
+        }
+    }
+}
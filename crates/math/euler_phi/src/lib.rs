@@ -39,6 +39,42 @@ macro_rules! impl_uint {
 
 impl_uint!(u8 u16 u32 u64 u128 usize);
 
+/// Euler の $\\varphi$ 関数のテーブル。
+///
+/// $a\_i = \\varphi(i)$ で定められる $a = (a\_0, a\_1, \\dots, a\_n)$ を返す。
+/// ただし、便宜上 $a\_0 = 0$ とする。
+///
+/// # Idea
+/// $a\_i = i$ で初期化し、各素数 $p$ について $p$ の倍数 $i$ を
+/// $a\_i \\gets a\_i - a\_i/p$ で更新する。
+/// $\\varphi(n) = n\\prod\_{p\\mid n} (1-1/p)$ より、最終的に $a\_i = \\varphi(i)$ となる。
+/// 各時点で $a\_i$ は $p$ で割り切れることに注意。
+///
+/// 素数 $p$ は、それまでの更新で $a\_p = p$ のままであることから判定できる。
+///
+/// # Complexity
+/// $O(n\\log(\\log(n)))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::euler_phi_table;
+///
+/// assert_eq!(euler_phi_table(0), [0]);
+/// assert_eq!(euler_phi_table(10), [0, 1, 1, 2, 2, 4, 2, 6, 4, 6, 4]);
+/// ```
+pub fn euler_phi_table(n: usize) -> Vec<u64> {
+    let mut res: Vec<_> = (0..=n as u64).collect();
+    for p in 2..=n {
+        if res[p] != p as u64 {
+            continue;
+        }
+        for i in (p..=n).step_by(p) {
+            res[i] -= res[i] / p as u64;
+        }
+    }
+    res
+}
+
 #[test]
 fn test_naive() {
     use gcd::Gcd;
@@ -48,3 +84,16 @@ fn test_naive() {
         assert_eq!(i.euler_phi(), phi);
     }
 }
+
+#[test]
+fn test_table() {
+    assert_eq!(euler_phi_table(0), [0]);
+    assert_eq!(euler_phi_table(1), [0, 1]);
+
+    let n = 10000;
+    let table = euler_phi_table(n);
+    assert_eq!(table.len(), n + 1);
+    for (i, &phi) in table.iter().enumerate().skip(1) {
+        assert_eq!(phi, (i as u64).euler_phi());
+    }
+}
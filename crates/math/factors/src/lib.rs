@@ -1,5 +1,8 @@
 //! 素因数分解。
 
+use gcd::Gcd;
+use miller_rabin::MillerRabin;
+
 /// 素因数分解。
 ///
 /// $n = \\prod\_{p\_i:\\text{ prime}} p\_i^{e\_i}$ に対して、各
@@ -69,6 +72,125 @@ macro_rules! impl_factors_uint {
 
 impl_factors_uint! { u8 u16 u32 u64 u128 usize }
 
+/// 素因数分解（Pollard の rho 法）。
+///
+/// [`Factors`] と同様に、各 $(p\_i, e\_i)$ を $p\_i$ の昇順に返す。
+/// $n$ が $10^{18}$ 程度の大きさでも高速に動作する。
+///
+/// [`Factors`]: trait.Factors.html
+///
+/// # Idea
+/// $n$ が素数かどうかを [`MillerRabin`] で判定し、合成数であれば非自明な約数
+/// $d$ を見つけて $d$ と $n/d$ を再帰的に分解する。
+/// [`MillerRabin`] は `u64` に対して既知の 7 つの底を用いる決定的なものである。
+///
+/// 約数は Pollard の rho 法で見つける。$f(x) = x^2+c \\bmod n$ による列
+/// $x\_{i+1} = f(x\_i)$ を考えると、$n$ の素因数 $p$ について $x\_i \\bmod p$
+/// は $O(\\sqrt{p})$ 項程度で周期に入ることが期待される。
+/// そのような $i\\ne j$ に対して $\\gcd(|x\_i-x\_j|, n)$ が $p$ の倍数となる。
+/// 周期の検出には Brent の方法を用い、$\\gcd$ は $|x\_i-x\_j|$
+/// をいくつかまとめて掛けたものに対して求める。失敗したら $c$ を変えてやり直す。
+///
+/// [`MillerRabin`]: ../miller_rabin/trait.MillerRabin.html
+///
+/// # Complexity
+/// 期待 $O(n^{1/4}\\log(n))$ time（ヒューリスティック）。
+///
+/// # Examples
+/// ```
+/// use nekolib::math::factors_big;
+///
+/// let n = 999999999999999989_u64; // 素数
+/// assert_eq!(factors_big(n).collect::<Vec<_>>(), [(n, 1)]);
+///
+/// let n = 1000000007 * 998244353_u64;
+/// assert_eq!(
+///     factors_big(n).collect::<Vec<_>>(),
+///     [(998244353, 1), (1000000007, 1)]
+/// );
+///
+/// let n = 735134400_u64;
+/// assert_eq!(
+///     factors_big(n).collect::<Vec<_>>(),
+///     [(2, 6), (3, 3), (5, 2), (7, 1), (11, 1), (13, 1), (17, 1)]
+/// );
+///
+/// assert_eq!(factors_big(1).next(), None);
+/// ```
+pub fn factors_big(n: u64) -> impl Iterator<Item = (u64, u32)> {
+    let mut ps = vec![];
+    if n > 1 {
+        let tz = n.trailing_zeros();
+        ps.extend((0..tz).map(|_| 2));
+        factors_big_rec(n >> tz, &mut ps);
+    }
+    ps.sort_unstable();
+
+    let mut res: Vec<(u64, u32)> = vec![];
+    for p in ps {
+        match res.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => res.push((p, 1)),
+        }
+    }
+    res.into_iter()
+}
+
+fn factors_big_rec(n: u64, ps: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if n.is_prime() {
+        ps.push(n);
+        return;
+    }
+    let d = (1..).find_map(|c| pollard_rho(n, c)).unwrap();
+    factors_big_rec(d, ps);
+    factors_big_rec(n / d, ps);
+}
+
+// n は奇数の合成数とする。非自明な約数が見つからなければ None を返す。
+fn pollard_rho(n: u64, c: u64) -> Option<u64> {
+    let mul = |x: u64, y: u64| (x as u128 * y as u128 % n as u128) as u64;
+    let f = |x: u64| ((x as u128 * x as u128 + c as u128) % n as u128) as u64;
+    let m = 128;
+
+    let mut x = 0;
+    let mut y = 2;
+    let mut ys = y;
+    let mut q = 1;
+    let mut g = 1;
+    let mut r = 1;
+    while g == 1 {
+        x = y;
+        for _ in 0..r {
+            y = f(y);
+        }
+        let mut k = 0;
+        while k < r && g == 1 {
+            ys = y;
+            for _ in 0..m.min(r - k) {
+                y = f(y);
+                q = mul(q, x.abs_diff(y));
+            }
+            g = q.gcd(n);
+            k += m;
+        }
+        r *= 2;
+    }
+    if g == n {
+        // まとめて掛けたせいで n になった場合は一つずつ戻って確かめる。
+        loop {
+            ys = f(ys);
+            g = x.abs_diff(ys).gcd(n);
+            if g > 1 {
+                break;
+            }
+        }
+    }
+    if g == n { None } else { Some(g) }
+}
+
 #[test]
 fn test_small() {
     let suite: &[(u64, &[(u64, u32)])] = &[
@@ -153,3 +275,51 @@ fn overflow_exhaustive() {
         assert_eq!(actual, expected);
     }
 }
+
+#[test]
+fn test_big() {
+    let suite: &[(u64, &[(u64, u32)])] = &[
+        (0, &[]),
+        (1, &[]),
+        (1 << 62, &[(2, 62)]),
+        (1_000_000_000_000_000_000, &[(2, 18), (5, 18)]),
+        (600851475143, &[(71, 1), (839, 1), (1471, 1), (6857, 1)]),
+        (3215031751, &[(151, 1), (751, 1), (28351, 1)]),
+        ((1 << 61) - 1, &[((1 << 61) - 1, 1)]),
+        (18446744073709551557, &[(18446744073709551557, 1)]),
+        (
+            u64::MAX,
+            &[
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6700417, 1),
+            ],
+        ),
+        (4294967291 * 4294967291, &[(4294967291, 2)]),
+        (4294967291 * 2147483647, &[(2147483647, 1), (4294967291, 1)]),
+        (999999937 * 999999929 * 2, &[(2, 1), (999999929, 1), (999999937, 1)]),
+    ];
+    for &(n, expected) in suite {
+        let actual: Vec<_> = factors_big(n).collect();
+        assert_eq!(actual, expected, "{}", n);
+    }
+
+    for n in 0..=10000_u64 {
+        assert!(factors_big(n).eq(n.factors()));
+    }
+
+    let f = std::iter::successors(Some(296_u64), |&x| {
+        Some(x.wrapping_mul(6364136223846793005).wrapping_add(1))
+    });
+    for x in f.take(300) {
+        let n = x >> ((x >> 32) % 40);
+        let fac: Vec<_> = factors_big(n).collect();
+        assert!(fac.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(fac.iter().all(|&(p, _)| p.is_prime()));
+        assert_eq!(fac.iter().map(|&(p, e)| p.pow(e)).product::<u64>(), n);
+    }
+}
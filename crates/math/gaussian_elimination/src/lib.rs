@@ -0,0 +1,393 @@
+//! ガウスの消去法。
+
+use binop::{Field, PartialRecip};
+
+/// ガウスの消去法。
+///
+/// 体 $K$ 上の $n\\times m$ 行列 $A$ を掃き出し、既約行階段形 $R$ と、$PA = R$
+/// なる正則行列 $P$ を求める。
+/// 掃き出した結果から、rank・行列式・逆行列・連立方程式の解を得られる。
+///
+/// ピボットは [`Field::try_recip`] が逆元を返す（すなわち非零の）要素から選ぶ。
+///
+/// # Complexity
+/// $O(nm\\cdot\\min\\{n, m\\} + n^2\\min\\{n, m\\})$ 回の体の演算。
+///
+/// # Examples
+/// ```
+/// # use nekolib::traits::binop::{
+/// #     Associative, Commutative, Distributive, Field, Identity, Magma,
+/// #     PartialRecip, Recip, Ring,
+/// # };
+/// use nekolib::math::gaussian_elimination;
+///
+/// # #[derive(Default)]
+/// # struct Add7;
+/// # impl Magma for Add7 {
+/// #     type Set = u32;
+/// #     fn op(&self, x: u32, y: u32) -> u32 { (x + y) % 7 }
+/// # }
+/// # impl Associative for Add7 {}
+/// # impl Commutative for Add7 {}
+/// # impl Identity for Add7 {
+/// #     fn id(&self) -> u32 { 0 }
+/// # }
+/// # impl PartialRecip for Add7 {
+/// #     fn partial_recip(&self, x: u32) -> Option<u32> { Some((7 - x) % 7) }
+/// # }
+/// # impl Recip for Add7 {}
+/// # #[derive(Default)]
+/// # struct Mul7;
+/// # impl Magma for Mul7 {
+/// #     type Set = u32;
+/// #     fn op(&self, x: u32, y: u32) -> u32 { x * y % 7 }
+/// # }
+/// # impl Associative for Mul7 {}
+/// # impl Commutative for Mul7 {}
+/// # impl Identity for Mul7 {
+/// #     fn id(&self) -> u32 { 1 }
+/// # }
+/// # impl PartialRecip for Mul7 {
+/// #     fn partial_recip(&self, x: u32) -> Option<u32> {
+/// #         (1..7).find(|&y| x * y % 7 == 1)
+/// #     }
+/// # }
+/// # impl Distributive<Add7> for Mul7 {}
+/// # #[derive(Default)]
+/// # struct Gf7(Add7, Mul7);
+/// # impl Ring for Gf7 {
+/// #     type Set = u32;
+/// #     type Additive = Add7;
+/// #     type Multiplicative = Mul7;
+/// #     fn additive(&self) -> &Add7 { &self.0 }
+/// #     fn multiplicative(&self) -> &Mul7 { &self.1 }
+/// # }
+/// # impl Field for Gf7 {}
+/// // Gf7 は整数 mod 7 からなる体
+/// let a = vec![vec![1, 2], vec![3, 4]];
+/// let ge = gaussian_elimination(Gf7::default(), a);
+/// assert_eq!(ge.rank(), 2);
+/// assert_eq!(ge.det(), Some(5)); // 1 * 4 - 2 * 3 = -2
+/// assert_eq!(ge.inverse(), Some(vec![vec![5, 1], vec![5, 3]]));
+/// assert_eq!(ge.solve(&[5, 4]), Some(vec![1, 2]));
+///
+/// let a = vec![vec![1, 2], vec![2, 4]];
+/// let ge = gaussian_elimination(Gf7::default(), a);
+/// assert_eq!(ge.rank(), 1);
+/// assert_eq!(ge.det(), Some(0));
+/// assert_eq!(ge.inverse(), None);
+/// assert_eq!(ge.solve(&[3, 6]), Some(vec![3, 0]));
+/// assert_eq!(ge.solve(&[3, 5]), None);
+/// ```
+pub fn gaussian_elimination<F: Field>(
+    field: F,
+    matrix: Vec<Vec<F::Set>>,
+) -> GaussianElimination<F>
+where
+    F::Multiplicative: PartialRecip,
+    F::Set: Clone,
+{
+    GaussianElimination::new(field, matrix)
+}
+
+/// ガウスの消去法の結果。
+///
+/// [`gaussian_elimination`] により得られる。
+pub struct GaussianElimination<F: Field>
+where
+    F::Multiplicative: PartialRecip,
+{
+    field: F,
+    m: usize,
+    reduced: Vec<Vec<F::Set>>,
+    trans: Vec<Vec<F::Set>>,
+    pivots: Vec<usize>,
+    det: F::Set,
+}
+
+impl<F: Field> GaussianElimination<F>
+where
+    F::Multiplicative: PartialRecip,
+    F::Set: Clone,
+{
+    fn new(field: F, mut a: Vec<Vec<F::Set>>) -> Self {
+        let n = a.len();
+        let m = a.first().map_or(0, |ai| ai.len());
+        assert!(a.iter().all(|ai| ai.len() == m), "ragged matrix");
+
+        let zero = field.zero();
+        let mut p: Vec<Vec<_>> = (0..n)
+            .map(|i| {
+                let mut pi = vec![zero.clone(); n];
+                pi[i] = field.one();
+                pi
+            })
+            .collect();
+
+        let mut det = field.one();
+        let mut pivots = vec![];
+        for j in 0..m {
+            let r = pivots.len();
+            if r == n {
+                break;
+            }
+            let (i, inv) = match (r..n).find_map(|i| {
+                field.try_recip(a[i][j].clone()).map(|inv| (i, inv))
+            }) {
+                Some(x) => x,
+                None => continue,
+            };
+            if i != r {
+                a.swap(i, r);
+                p.swap(i, r);
+                det = field.neg(det);
+            }
+            det = field.mul(det, a[r][j].clone());
+            for x in a[r].iter_mut().chain(p[r].iter_mut()) {
+                *x = field.mul(x.clone(), inv.clone());
+            }
+
+            let (ar, pr) = (a[r].clone(), p[r].clone());
+            for i in (0..n).filter(|&i| i != r) {
+                let c = a[i][j].clone();
+                if c == zero {
+                    continue;
+                }
+                let iter =
+                    a[i].iter_mut().zip(&ar).chain(p[i].iter_mut().zip(&pr));
+                for (x, y) in iter {
+                    let cy = field.mul(c.clone(), y.clone());
+                    *x = field.add(x.clone(), field.neg(cy));
+                }
+            }
+            pivots.push(j);
+        }
+        if pivots.len() < n {
+            det = zero;
+        }
+
+        Self { field, m, reduced: a, trans: p, pivots, det }
+    }
+
+    /// rank を返す。
+    ///
+    /// # Complexity
+    /// $O(1)$ time.
+    pub fn rank(&self) -> usize { self.pivots.len() }
+
+    /// 正方行列であれば行列式を、そうでなければ `None` を返す。
+    ///
+    /// # Complexity
+    /// $O(1)$ time.
+    pub fn det(&self) -> Option<F::Set> {
+        if self.reduced.len() == self.m { Some(self.det.clone()) } else { None }
+    }
+
+    /// 正則行列であれば逆行列を、そうでなければ `None` を返す。
+    ///
+    /// # Complexity
+    /// $O(n^2)$ time.
+    pub fn inverse(&self) -> Option<Vec<Vec<F::Set>>> {
+        let n = self.reduced.len();
+        if n == self.m && self.rank() == n {
+            Some(self.trans.clone())
+        } else {
+            None
+        }
+    }
+
+    /// $Ax = b$ なる $x$ が存在すればそのひとつを、そうでなければ `None`
+    /// を返す。
+    ///
+    /// 自由変数はすべて $0$ とする。
+    ///
+    /// # Complexity
+    /// $O(n^2 + m)$ time.
+    pub fn solve(&self, b: &[F::Set]) -> Option<Vec<F::Set>> {
+        let n = self.reduced.len();
+        assert_eq!(b.len(), n);
+
+        let field = &self.field;
+        let zero = field.zero();
+        let c: Vec<_> = self
+            .trans
+            .iter()
+            .map(|pi| {
+                pi.iter().zip(b).fold(zero.clone(), |acc, (x, y)| {
+                    field.add(acc, field.mul(x.clone(), y.clone()))
+                })
+            })
+            .collect();
+        if c[self.rank()..].iter().any(|ci| ci != &zero) {
+            return None;
+        }
+
+        let mut x = vec![zero; self.m];
+        for (&j, ci) in self.pivots.iter().zip(c) {
+            x[j] = ci;
+        }
+        Some(x)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use binop::{
+        new_monoid, Associative, Commutative, Distributive, Identity, Magma,
+        Recip, Ring,
+    };
+    use modint::{ModInt998244353, ModIntBase};
+
+    type Mi = ModInt998244353;
+
+    new_monoid! { OpAddMi = (Mi, |x, y| x + y, Mi::new(0), |x: Mi| -x, +commutative) }
+    new_monoid! { OpMulMi = (Mi, |x, y| x * y, Mi::new(1), |x: Mi| x.recip(), +commutative) }
+    impl Distributive<OpAddMi> for OpMulMi {}
+
+    struct ModField(OpAddMi, OpMulMi);
+    impl Ring for ModField {
+        type Set = Mi;
+        type Additive = OpAddMi;
+        type Multiplicative = OpMulMi;
+        fn additive(&self) -> &OpAddMi { &self.0 }
+        fn multiplicative(&self) -> &OpMulMi { &self.1 }
+    }
+    impl Field for ModField {}
+
+    fn field() -> ModField { ModField(OpAddMi, OpMulMi) }
+
+    fn naive_det(a: &[Vec<Mi>]) -> Mi {
+        fn dfs(a: &[Vec<Mi>], perm: &mut Vec<usize>) -> Mi {
+            let n = a.len();
+            if perm.len() == n {
+                let inv = (0..n)
+                    .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+                    .filter(|&(i, j)| perm[i] > perm[j])
+                    .count();
+                let sign = Mi::new(if inv % 2 == 0 { 1 } else { -1 });
+                let prod: Mi = (0..n).map(|i| a[i][perm[i]]).product();
+                return prod * sign;
+            }
+            let mut res = Mi::new(0);
+            for j in 0..n {
+                if !perm.contains(&j) {
+                    perm.push(j);
+                    res += dfs(a, perm);
+                    perm.pop();
+                }
+            }
+            res
+        }
+        dfs(a, &mut vec![])
+    }
+
+    fn naive_rank(a: &[Vec<Mi>]) -> usize {
+        // 非零な k 次小行列式が存在する最大の k
+        let n = a.len();
+        let m = a[0].len();
+        let subsets = |len: usize, k: usize| {
+            (0_u32..1 << len)
+                .filter(move |s| s.count_ones() as usize == k)
+                .map(move |s| (0..len).filter(|&i| s >> i & 1 != 0).collect())
+        };
+        (1..=n.min(m))
+            .rev()
+            .find(|&k| {
+                subsets(n, k).any(|rows: Vec<_>| {
+                    subsets(m, k).any(|cols: Vec<_>| {
+                        let sub: Vec<Vec<_>> = rows
+                            .iter()
+                            .map(|&i| cols.iter().map(|&j| a[i][j]).collect())
+                            .collect();
+                        naive_det(&sub) != Mi::new(0)
+                    })
+                })
+            })
+            .unwrap_or(0)
+    }
+
+    fn mat_mul(a: &[Vec<Mi>], b: &[Vec<Mi>]) -> Vec<Vec<Mi>> {
+        a.iter()
+            .map(|ai| {
+                (0..b[0].len())
+                    .map(|j| ai.iter().zip(b).map(|(&x, bk)| x * bk[j]).sum())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_naive_det() {
+        let a: Vec<Vec<_>> = vec![vec![2, 0, 1], vec![1, 3, 2], vec![1, 1, 1]]
+            .into_iter()
+            .map(|ai| ai.into_iter().map(Mi::new).collect())
+            .collect();
+        // 2 * (3 - 2) - 0 + 1 * (1 - 3) = 0
+        assert_eq!(naive_det(&a), Mi::new(0));
+        let a: Vec<Vec<_>> = vec![vec![0, 1], vec![1, 0]]
+            .into_iter()
+            .map(|ai| ai.into_iter().map(Mi::new).collect())
+            .collect();
+        assert_eq!(naive_det(&a), Mi::new(-1));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut f = std::iter::successors(Some(1732_u64), |&x| {
+            Some(x * 48271 % 2147483647)
+        });
+        let mut rand = |k: u64| f.next().unwrap() % k;
+
+        for _ in 0..2000 {
+            let n = 1 + rand(5) as usize;
+            let m = 1 + rand(5) as usize;
+            // 特異になりやすいよう小さい値を使う
+            let k = [2, 3, 998244353][rand(3) as usize];
+            let a: Vec<Vec<_>> = (0..n)
+                .map(|_| (0..m).map(|_| Mi::new(rand(k))).collect())
+                .collect();
+            let ge = gaussian_elimination(field(), a.clone());
+
+            let rank = naive_rank(&a);
+            assert_eq!(ge.rank(), rank);
+
+            if n == m {
+                assert_eq!(ge.det(), Some(naive_det(&a)));
+                let id: Vec<Vec<_>> = (0..n)
+                    .map(|i| (0..n).map(|j| Mi::new((i == j) as u32)).collect())
+                    .collect();
+                match ge.inverse() {
+                    Some(inv) => {
+                        assert_eq!(rank, n);
+                        assert_eq!(mat_mul(&a, &inv), id);
+                        assert_eq!(mat_mul(&inv, &a), id);
+                    }
+                    None => assert!(rank < n),
+                }
+            } else {
+                assert_eq!(ge.det(), None);
+                assert_eq!(ge.inverse(), None);
+            }
+
+            let b: Vec<_> = (0..n).map(|_| Mi::new(rand(k))).collect();
+            let ab: Vec<Vec<_>> = a
+                .iter()
+                .zip(&b)
+                .map(|(ai, &bi)| ai.iter().copied().chain(Some(bi)).collect())
+                .collect();
+            let solvable = naive_rank(&ab) == rank;
+            match ge.solve(&b) {
+                Some(x) => {
+                    assert!(solvable);
+                    let xs: Vec<_> = x.into_iter().map(|xi| vec![xi]).collect();
+                    let ax: Vec<_> =
+                        mat_mul(&a, &xs).into_iter().map(|r| r[0]).collect();
+                    assert_eq!(ax, b);
+                }
+                None => assert!(!solvable),
+            }
+        }
+    }
+}
@@ -91,6 +91,107 @@ macro_rules! impl_int {
 impl_uint!(u8 u16 u32 u64 u128 usize);
 impl_int!(i8 i16 i32 i64 i128 isize);
 
+/// 拡張ユークリッドの互除法。
+///
+/// $a\\cdot x + b\\cdot y = g$ を満たす $(x, y, g)$ を返す。
+/// ただし $g = \\gcd(a, b)\\ge 0$ であり、$\\gcd(0, 0) = 0$ とする。
+/// $a$, $b$ の符号は任意で、$g$ は常に非負となる。
+///
+/// $(x, y)$ は次のように正規化する。
+/// - $b \\ne 0$ のとき、$0\\le x\\lt |b|/g$ を満たす唯一のもの。
+///     - 特に $a = 0$ のときは $(0, \\operatorname{sgn}(b))$ となる。
+/// - $a \\ne 0$, $b = 0$ のとき、$(\\operatorname{sgn}(a), 0)$。
+/// - $a = b = 0$ のとき、$(0, 0)$。
+///
+/// $b \\ne 0$ のとき、$x$ は [`GcdRecip`] で得られる $r$ と一致する。
+/// また、$|y|\\le \\max\\{1, |a|/g\\}$ が成り立つ。
+///
+/// [`GcdRecip`]: trait.GcdRecip.html
+///
+/// # Panics
+/// $g = 2^{63}$ となるとき、すなわち $a, b\\in\\{0, -2^{63}\\}$ かつ
+/// $(a, b)\\ne (0, 0)$ のとき。
+///
+/// # Complexity
+/// $O(\\log(\\min\\{|a|, |b|\\}))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::ext_gcd;
+///
+/// assert_eq!(ext_gcd(240, 46), (14, -73, 2));
+/// assert_eq!(ext_gcd(-240, 46), (9, 47, 2));
+/// assert_eq!(ext_gcd(240, -46), (14, 73, 2));
+///
+/// assert_eq!(ext_gcd(0, -5), (0, -1, 5));
+/// assert_eq!(ext_gcd(-5, 0), (-1, 0, 5));
+/// assert_eq!(ext_gcd(0, 0), (0, 0, 0));
+/// ```
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (a, b) = (a as i128, b as i128);
+    let (mut s, mut t) = (a.abs(), b.abs());
+    let (mut x0, mut x1) = (1, 0);
+    while t > 0 {
+        let u = s / t;
+        s -= t * u;
+        x0 -= x1 * u;
+        std::mem::swap(&mut s, &mut t);
+        std::mem::swap(&mut x0, &mut x1);
+    }
+    let g = s;
+    assert!(g <= i64::MAX as i128, "gcd overflows");
+
+    if b == 0 {
+        return (a.signum() as i64, 0, g as i64);
+    }
+    let x = (x0 * a.signum()).rem_euclid(b.abs() / g);
+    let y = (g - a * x) / b;
+    (x as i64, y as i64, g as i64)
+}
+
+/// 二つの線形合同式を解く。
+///
+/// $\\gdef{\\lcm}{\\operatorname\*{lcm}}$
+/// $x\\equiv r\_1\\pmod{m\_1}$ かつ $x\\equiv r\_2\\pmod{m\_2}$ を満たす $x$
+/// が存在すれば、$m = \\lcm(m\_1, m\_2)$ として $x\\equiv r\\pmod{m}$
+/// なる $0\\le r\\lt m$ を用いて $(r, m)$ を返す。存在しなければ `None` を返す。
+/// $r\_1$, $r\_2$ は負でもよい。
+///
+/// [`ext_gcd`] により $m\_1\\cdot u\\equiv g\\pmod{m\_2}$ なる $u$ を求めて計算する。
+///
+/// [`ext_gcd`]: fn.ext_gcd.html
+///
+/// # Panics
+/// $m\_1\\le 0$ または $m\_2\\le 0$ のとき。
+/// また、解が存在して $m$ が `i64` に収まらないとき。
+///
+/// # Complexity
+/// $O(\\log(\\min\\{m\_1, m\_2\\}))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::crt;
+///
+/// assert_eq!(crt(2, 3, 3, 5), Some((8, 15)));
+/// assert_eq!(crt(1, 4, 3, 6), Some((9, 12)));
+/// assert_eq!(crt(1, 4, 2, 6), None);
+/// assert_eq!(crt(-1, 7, 0, 1), Some((6, 7)));
+/// ```
+pub fn crt(r1: i64, m1: i64, r2: i64, m2: i64) -> Option<(i64, i64)> {
+    assert!(m1 > 0 && m2 > 0, "moduli must be positive");
+    let (r1, r2) = (r1.rem_euclid(m1), r2.rem_euclid(m2));
+    let (u, _, g) = ext_gcd(m1, m2);
+    if (r2 - r1) % g != 0 {
+        return None;
+    }
+    let v = (m2 / g) as i128;
+    let t = ((r2 - r1) / g) as i128 % v * u as i128 % v;
+    let m = m1 as i128 * v;
+    assert!(m <= i64::MAX as i128, "lcm overflows");
+    let r = (r1 as i128 + t * m1 as i128).rem_euclid(m);
+    Some((r as i64, m as i64))
+}
+
 #[test]
 fn test() {
     for b in 1_i32..=1000 {
@@ -108,3 +209,108 @@ fn test() {
         }
     }
 }
+
+#[test]
+fn test_ext_gcd_known() {
+    assert_eq!(ext_gcd(240, 46), (14, -73, 2));
+    assert_eq!(ext_gcd(-240, 46), (9, 47, 2));
+    assert_eq!(ext_gcd(240, -46), (14, 73, 2));
+    assert_eq!(ext_gcd(-240, -46), (9, -47, 2));
+    assert_eq!(ext_gcd(6, 3), (0, 1, 3));
+    assert_eq!(ext_gcd(3, 6), (1, 0, 3));
+    assert_eq!(ext_gcd(1, 1), (0, 1, 1));
+
+    assert_eq!(ext_gcd(0, 0), (0, 0, 0));
+    assert_eq!(ext_gcd(0, 5), (0, 1, 5));
+    assert_eq!(ext_gcd(0, -5), (0, -1, 5));
+    assert_eq!(ext_gcd(5, 0), (1, 0, 5));
+    assert_eq!(ext_gcd(-5, 0), (-1, 0, 5));
+
+    assert_eq!(ext_gcd(i64::MIN, 1), (0, 1, 1));
+    assert_eq!(ext_gcd(1, i64::MIN), (1, 0, 1));
+    assert_eq!(ext_gcd(i64::MIN, i64::MAX), (i64::MAX - 1, i64::MAX, 1));
+    assert_eq!(ext_gcd(i64::MAX, i64::MAX - 1), (1, -1, 1));
+}
+
+#[test]
+fn test_ext_gcd() {
+    let gcd = |mut a: u64, mut b: u64| {
+        while b > 0 {
+            a %= b;
+            std::mem::swap(&mut a, &mut b);
+        }
+        a
+    };
+    let check = |a: i64, b: i64| {
+        let (x, y, g) = ext_gcd(a, b);
+        let (a_, b_) = (a as i128, b as i128);
+        assert_eq!(a_ * x as i128 + b_ * y as i128, g as i128);
+        assert_eq!(g as u64, gcd(a.unsigned_abs(), b.unsigned_abs()));
+        if b != 0 {
+            assert!(0 <= x && (x as i128) < b_.abs() / g as i128);
+            assert!((y as i128).abs() <= 1.max(a_.abs() / g as i128));
+        }
+    };
+
+    for a in -100..=100 {
+        for b in -100..=100 {
+            check(a, b);
+        }
+    }
+    for b in 1..=100 {
+        for a in 0..b {
+            assert_eq!(ext_gcd(a, b).0, a.gcd_recip(b).1);
+        }
+    }
+
+    let mut f = std::iter::successors(Some(185_u64), |&x| {
+        Some(x.wrapping_mul(6364136223846793005).wrapping_add(1))
+    })
+    .map(|x| (x as i64) >> (x >> 58));
+    for _ in 0..100000 {
+        check(f.next().unwrap(), f.next().unwrap());
+    }
+}
+
+#[test]
+#[should_panic(expected = "gcd overflows")]
+fn test_ext_gcd_overflow() { ext_gcd(i64::MIN, 0); }
+
+#[test]
+fn test_crt_known() {
+    assert_eq!(crt(2, 3, 3, 5), Some((8, 15)));
+    assert_eq!(crt(1, 4, 3, 6), Some((9, 12)));
+    assert_eq!(crt(1, 4, 2, 6), None);
+    assert_eq!(crt(-1, 7, 0, 1), Some((6, 7)));
+    assert_eq!(crt(0, 1, 0, 1), Some((0, 1)));
+    assert_eq!(crt(5, 6, 11, 12), Some((11, 12)));
+    assert_eq!(crt(5, 12, 11, 6), Some((5, 12)));
+    assert_eq!(crt(4, 12, 11, 6), None);
+    assert_eq!(crt(-13, 12, 5, 6), Some((11, 12)));
+
+    let p = 1_000_000_007;
+    let q = 998_244_353;
+    assert_eq!(crt(1, p, 2, q), Some((993_328_913_953_302_350, p * q)));
+    assert_eq!(crt(p - 1, p, q - 1, q), Some((p * q - 1, p * q)));
+}
+
+#[test]
+fn test_crt_small() {
+    for m1 in 1..=20 {
+        for m2 in 1..=20 {
+            let lcm = (1..).map(|k| k * m1).find(|x| x % m2 == 0).unwrap();
+            for r1 in -m1..2 * m1 {
+                for r2 in -m2..2 * m2 {
+                    let expected = (0..lcm)
+                        .find(|x| (x - r1) % m1 == 0 && (x - r2) % m2 == 0)
+                        .map(|x| (x, lcm));
+                    assert_eq!(crt(r1, m1, r2, m2), expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "lcm overflows")]
+fn test_crt_overflow() { crt(0, 1 << 32, 1, (1 << 32) - 1); }
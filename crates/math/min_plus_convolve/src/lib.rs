@@ -0,0 +1,188 @@
+//! $(\\min, +)$ 畳み込み。
+
+/// $(\\min, +)$ 畳み込み。
+///
+/// 長さ $n$ の $a$ と長さ $m$ の $b$ に対して、長さ $n+m-1$ の $c$ であって
+/// $$ c\_k = \\min\_{i+j=k} (a\_i + b\_j) $$
+/// なるものを返す。$a$ または $b$ が空のときは空の列を返す。
+///
+/// 片方が下に凸であれば [`min_plus_convolve_convex`] を使う方が速い。
+///
+/// # Complexity
+/// $O(nm)$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::min_plus_convolve;
+///
+/// let a = [0, 3, 1];
+/// let b = [2, 0, 5];
+/// assert_eq!(min_plus_convolve(&a, &b), [2, 0, 3, 1, 6]);
+/// ```
+pub fn min_plus_convolve(a: &[i64], b: &[i64]) -> Vec<i64> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let (n, m) = (a.len(), b.len());
+    let mut c = vec![i64::MAX; n + m - 1];
+    for (i, &ai) in a.iter().enumerate() {
+        for (j, &bj) in b.iter().enumerate() {
+            c[i + j] = c[i + j].min(ai + bj);
+        }
+    }
+    c
+}
+
+/// 片方が凸のときの $(\\min, +)$ 畳み込み。
+///
+/// [`min_plus_convolve`] と同様に
+/// $$ c\_k = \\min\_{i+j=k} (a\_i + b\_j) $$
+/// を返す。ただし、$b$ は下に凸、すなわち
+/// $b\_{j+1}-b\_j\\le b\_{j+2}-b\_{j+1}$ を満たすとする。$a$ は任意でよい。
+///
+/// # Idea
+/// $k$ 行 $i$ 列の成分が $a\_i + b\_{k-i}$ である $(n+m-1)\\times n$
+/// 行列を考え、その各行の最小値を求めればよい。$0\\le k-i\\lt m$
+/// でない成分については、$i\\gt k$ のものは同じ行の左側のどの成分よりも大きく、
+/// $i\\lt k-m+1$ のものは同じ行の右側のどの成分よりも大きいとみなす。
+///
+/// $i\_1\\lt i\_2$ について、行 $k$ で $i\_2$ 列の方が小さいとき、
+/// $b$ が凸であることから
+/// $$ b\_{k+1-i\_2}-b\_{k-i\_2} \\le b\_{k+1-i\_1}-b\_{k-i\_1} $$
+/// なので、行 $k+1$ でも $i\_2$ 列の方が小さい。
+/// すなわちこの行列は totally monotone であり、SMAWK algorithm
+/// によって各行の最小値を求めることができる。
+///
+/// # Panics
+/// $b$ が下に凸でないとき。
+///
+/// # Complexity
+/// $O(n+m)$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::{min_plus_convolve, min_plus_convolve_convex};
+///
+/// let a = [0, -5, -3, -9];
+/// let b: Vec<_> = (0..4).map(|j: i64| j * j).collect();
+/// let c = min_plus_convolve_convex(&a, &b);
+/// assert_eq!(c, min_plus_convolve(&a, &b));
+/// assert_eq!(c, [0, -5, -4, -9, -8, -5, 0]);
+/// ```
+///
+/// ```should_panic
+/// use nekolib::math::min_plus_convolve_convex;
+///
+/// let a = [0, 0, 0];
+/// let b = [0, 1, 0];
+/// let _ = min_plus_convolve_convex(&a, &b);
+/// ```
+///
+/// # References
+/// - Aggarwal, Alok, Maria M. Klawe, Shlomo Moran, Peter Shor, and Robert Wilber. "Geometric applications of a matrix-searching algorithm." *Algorithmica* 2, no. 1 (1987): 195--208.
+pub fn min_plus_convolve_convex(a: &[i64], b: &[i64]) -> Vec<i64> {
+    assert!(
+        b.windows(3).all(|w| w[1] - w[0] <= w[2] - w[1]),
+        "`b` must be convex"
+    );
+
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let (n, m) = (a.len(), b.len());
+
+    // 行 k において、i2 列が i1 列より真に小さいか (i1 < i2)
+    let lt = |k: usize, i1: usize, i2: usize| {
+        if i2 > k {
+            false
+        } else if i1 + m <= k {
+            true
+        } else {
+            a[i2] + b[k - i2] < a[i1] + b[k - i1]
+        }
+    };
+
+    let rows: Vec<_> = (0..n + m - 1).collect();
+    let cols: Vec<_> = (0..n).collect();
+    let mut argmin = vec![0; n + m - 1];
+    smawk(&rows, &cols, &lt, &mut argmin);
+    argmin.iter().enumerate().map(|(k, &i)| a[i] + b[k - i]).collect()
+}
+
+fn smawk(
+    rows: &[usize],
+    cols: &[usize],
+    lt: &impl Fn(usize, usize, usize) -> bool,
+    argmin: &mut [usize],
+) {
+    if rows.is_empty() {
+        return;
+    }
+
+    let mut st: Vec<usize> = vec![];
+    for &c in cols {
+        while let Some(&top) = st.last() {
+            if lt(rows[st.len() - 1], top, c) {
+                st.pop();
+            } else {
+                break;
+            }
+        }
+        if st.len() < rows.len() {
+            st.push(c);
+        }
+    }
+
+    let odd: Vec<_> = rows.iter().skip(1).step_by(2).copied().collect();
+    smawk(&odd, &st, lt, argmin);
+
+    let mut j = 0;
+    for i in (0..rows.len()).step_by(2) {
+        let last = rows.get(i + 1).map_or(st[st.len() - 1], |&r| argmin[r]);
+        let mut best = st[j];
+        while st[j] != last {
+            j += 1;
+            if lt(rows[i], best, st[j]) {
+                best = st[j];
+            }
+        }
+        argmin[rows[i]] = best;
+    }
+}
+
+#[test]
+fn test_convex() {
+    let mut f = std::iter::successors(Some(2236_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = |k: u64| f.next().unwrap() % k;
+
+    for n in 0..=20 {
+        for m in 0..=20 {
+            let a: Vec<_> = (0..n).map(|_| rand(200) as i64 - 100).collect();
+            let mut b = vec![rand(200) as i64 - 100];
+            let mut d = rand(30) as i64 - 15;
+            for _ in 1..m {
+                b.push(b[b.len() - 1] + d);
+                d += rand(4) as i64;
+            }
+            b.truncate(m);
+            assert_eq!(
+                min_plus_convolve_convex(&a, &b),
+                min_plus_convolve(&a, &b)
+            );
+        }
+    }
+
+    for _ in 0..30 {
+        let n = rand(1000) as usize + 1;
+        let m = rand(1000) as usize + 1;
+        let a: Vec<_> = (0..n).map(|_| rand(1_000_000) as i64).collect();
+        let b: Vec<_> = (0..m as i64).map(|j| (j - 300).pow(2)).collect();
+        assert_eq!(min_plus_convolve_convex(&a, &b), min_plus_convolve(&a, &b));
+    }
+}
+
+#[test]
+#[should_panic(expected = "`b` must be convex")]
+fn test_nonconvex() { min_plus_convolve_convex(&[0], &[0, 2, 3]); }
@@ -0,0 +1,123 @@
+//! 素数を法とする平方根。
+
+use const_div::ConstDiv;
+
+/// 素数を法とする平方根。
+///
+/// 素数 $p$ に対して、$x^2 \\equiv a \\pmod{p}$ なる $0\\le x\\lt p$
+/// が存在すれば、そのうち小さい方を返す。
+/// $0\\le a\\lt p\\lt 2^{32}$ とする。
+///
+/// # Idea
+/// $a = 0$ なら $x = 0$、$p = 2$ なら $x = a$ である。以下、$p$ は奇素数で $a\\ne 0$ とする。
+///
+/// Euler の規準より、$a$ が平方剰余であることと $a^{(p-1)/2}\\equiv 1$ は同値である。
+///
+/// 平方剰余のとき、Tonelli–Shanks のアルゴリズムで解を求める。
+/// $p-1 = 2^s\\cdot q$ ($q$ は奇数) とし、平方非剰余 $z$ を一つ見つけておく。
+/// $c = z^q$, $t = a^q$, $r = a^{(q+1)/2} = a^{\\lfloor q/2\\rfloor+1}$, $m = s$ で初期化すると、
+/// $r^2 \\equiv at$ であり、$t$ の位数は $2^{m-1}$ 以下、$c$ の位数は $2^m$ となっている。
+/// $t\\ne 1$ の間、$t^{2^i} = 1$ となる最小の $i$ ($0\\lt i\\lt m$) を求め、
+/// $b = c^{2^{m-i-1}}$ として $(c, t, r, m)\\gets (b^2, tb^2, rb, i)$ と更新する。
+/// 不変条件は保たれ、$m$ は真に減少するので、いずれ $t = 1$ となって $r$ が解となる。
+///
+/// 平方非剰余は半数あるので、$z = 2, 3, \\dots$ と順に試せばすぐ見つかる。
+///
+/// 剰余算には [`ConstDiv`] を用いている。
+///
+/// [`ConstDiv`]: ../const_div/struct.ConstDiv.html
+///
+/// # Complexity
+/// $O(\\log(p)^2)$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::mod_sqrt;
+///
+/// assert_eq!(mod_sqrt(2, 7), Some(3));
+/// assert_eq!(mod_sqrt(3, 7), None);
+/// assert_eq!(mod_sqrt(0, 7), Some(0));
+/// assert_eq!(mod_sqrt(1, 2), Some(1));
+///
+/// let p = 998244353;
+/// let x = mod_sqrt(2, p).unwrap();
+/// assert_eq!(x * x % p, 2);
+/// assert!(x <= p - x);
+/// assert_eq!(mod_sqrt(3, p), None);
+/// ```
+pub fn mod_sqrt(a: u64, p: u64) -> Option<u64> {
+    if a == 0 || p == 2 {
+        return Some(a);
+    }
+
+    let cd = ConstDiv::new(p);
+    if mod_pow_with_cd(a, (p - 1) / 2, cd) != 1 {
+        return None;
+    }
+
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p).find(|&z| mod_pow_with_cd(z, (p - 1) / 2, cd) != 1).unwrap();
+    let mut c = mod_pow_with_cd(z, q, cd);
+    let mut t = mod_pow_with_cd(a, q, cd);
+    let mut r = mod_pow_with_cd(a, q / 2 + 1, cd);
+    let mut m = s;
+    while t != 1 {
+        let mut i = 0;
+        let mut tt = t;
+        while tt != 1 {
+            tt = cd.rem(tt * tt);
+            i += 1;
+        }
+        let b = mod_pow_with_cd(c, 1 << (m - i - 1), cd);
+        m = i;
+        c = cd.rem(b * b);
+        t = cd.rem(t * c);
+        r = cd.rem(r * b);
+    }
+    Some(r.min(p - r))
+}
+
+fn mod_pow_with_cd(mut a: u64, mut b: u64, cd: ConstDiv) -> u64 {
+    let mut res = 1;
+    while b > 0 {
+        if b & 1 == 1 {
+            res = cd.rem(res * a);
+        }
+        a = cd.rem(a * a);
+        b >>= 1;
+    }
+    res
+}
+
+#[test]
+fn test_small() {
+    let primes = (2_u64..300).filter(|&p| (2..p).all(|d| p % d != 0));
+    for p in primes {
+        let mut expected = vec![None; p as usize];
+        for x in (0..p).rev() {
+            expected[(x * x % p) as usize] = Some(x);
+        }
+        for a in 0..p {
+            assert_eq!(mod_sqrt(a, p), expected[a as usize]);
+        }
+    }
+}
+
+#[test]
+fn test_large() {
+    // p - 1 が 2 で何度も割れるものとそうでないもの
+    for &p in &[998244353_u64, 1_000_000_007, 4294967291, 3221225473] {
+        let mut x = 1;
+        for _ in 0..1000 {
+            x = x * 48271 % p;
+            let a = x * x % p;
+            let y = mod_sqrt(a, p).unwrap();
+            assert!(y == x || y == p - x);
+            assert!(y <= p - y);
+            if p - a != a {
+                assert_eq!(mod_sqrt(p - a, p).is_some(), p % 4 == 1);
+            }
+        }
+    }
+}
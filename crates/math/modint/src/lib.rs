@@ -5,6 +5,7 @@ use std::marker::PhantomData;
 use std::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign,
 };
+use std::str::FromStr;
 use std::sync::atomic::{self, AtomicU32, AtomicU64};
 
 use gcd_recip::GcdRecip;
@@ -76,6 +77,26 @@ trait InternalImpls: ModIntBase {
     fn debug_impl(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} (mod {})", self.get(), Self::modulus())
     }
+    fn from_str_impl(s: &str) -> Result<Self, ParseModIntError> {
+        let (neg, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() {
+            return Err(ParseModIntError::Empty);
+        }
+        let m = Self::modulus() as u64;
+        let mut val = 0_u64;
+        for c in digits.bytes() {
+            if !c.is_ascii_digit() {
+                return Err(ParseModIntError::InvalidDigit);
+            }
+            val = (val * 10 + (c - b'0') as u64) % m;
+        }
+        let res = Self::new(val);
+        Ok(if neg { Self::new(0) - res } else { res })
+    }
     fn neg_impl(self) -> Self {
         let v = if self.get() == 0 { 0 } else { Self::modulus() - self.get() };
         unsafe { Self::new_unchecked(v) }
@@ -131,6 +152,106 @@ impl<I: RemEuclidU32, M: Modulus> From<I> for StaticModInt<M> {
     fn from(x: I) -> Self { Self::new(x) }
 }
 
+/// 文字列から剰余類を読む際のエラー。
+///
+/// [`StaticModInt`] および [`DynamicModInt`] は [`FromStr`] を実装しており、
+/// 十進表記の整数を読んで法で割った余りとする。
+/// 先頭に `-` または `+` の符号を一つだけつけてよく、`-3` は $m-3$ となる。
+/// 桁数に制限はなく、`u64` などに収まらない値も読める。
+/// 空白などを含む文字列は受け付けない。
+///
+/// [`Display`] では [`get`] の値を十進表記で出力する。
+/// $0$ に対しては `"0"` を出力し、符号はつけない。
+/// したがって、出力を [`FromStr`] で読むと元の値に戻る。
+///
+/// [`FromStr`] による実装なので、`proconio` の `input!` などでも直接読める。
+///
+/// # Examples
+/// ```
+/// use nekolib::math::{ModInt998244353, ModIntBase, ParseModIntError};
+///
+/// type Mi = ModInt998244353;
+/// assert_eq!("123".parse::<Mi>(), Ok(Mi::new(123)));
+/// assert_eq!("-3".parse::<Mi>(), Ok(Mi::new(998244350)));
+/// assert_eq!("998244353".parse::<Mi>(), Ok(Mi::new(0)));
+/// assert_eq!(
+///     "100000000000000000000".parse::<Mi>(),
+///     Ok(Mi::new(10_u128.pow(20))),
+/// );
+///
+/// assert_eq!("".parse::<Mi>(), Err(ParseModIntError::Empty));
+/// assert_eq!("-".parse::<Mi>(), Err(ParseModIntError::Empty));
+/// assert_eq!("1e9".parse::<Mi>(), Err(ParseModIntError::InvalidDigit));
+///
+/// assert_eq!(Mi::new(0).to_string(), "0");
+/// assert_eq!(Mi::new(-1).to_string(), "998244352");
+/// ```
+///
+/// [`get`]: trait.ModIntBase.html#tymethod.get
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseModIntError {
+    /// 数字がない。
+    Empty,
+    /// 数字でない文字を含む。
+    InvalidDigit,
+}
+
+impl Display for ParseModIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseModIntError::Empty => {
+                write!(f, "cannot parse modint from empty string")
+            }
+            ParseModIntError::InvalidDigit => {
+                write!(f, "invalid digit found in string")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseModIntError {}
+
+/// 逆元の一括計算。
+///
+/// `xs` の各要素の逆元を返す。
+///
+/// # Idea
+/// 累積積 $p\_i = \\prod\_{j\\lt i} x\_j$ を求めておき、$q = p\_n^{-1}$ とする。
+/// 後ろから順に $x\_i^{-1} = q\\cdot p\_i$ を求め、$q\\gets q\\cdot x\_i$ と更新する。
+/// 逆元の計算は $p\_n^{-1}$ の一回で済む。
+///
+/// # Panics
+/// 逆元を持たない要素（$0$ など）が含まれるとき。
+///
+/// # Complexity
+/// $O(n + \\log(m))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::{batch_recip, ModInt998244353, ModIntBase};
+///
+/// type Mi = ModInt998244353;
+/// let xs: Vec<_> = (1..=5).map(Mi::new).collect();
+/// let ys = batch_recip(&xs);
+/// assert_eq!(ys[1], Mi::new(2).recip());
+/// assert!(xs.iter().zip(&ys).all(|(&x, &y)| x * y == Mi::new(1)));
+/// ```
+pub fn batch_recip<T: ModIntBase>(xs: &[T]) -> Vec<T> {
+    let n = xs.len();
+    let mut res = Vec::with_capacity(n);
+    let mut acc = T::new(1);
+    for &x in xs {
+        res.push(acc);
+        acc *= x;
+    }
+    let mut q = acc.checked_recip().expect("not invertible");
+    for i in (0..n).rev() {
+        res[i] *= q;
+        q *= xs[i];
+    }
+    res
+}
+
 #[cfg(ignore)]
 const fn is_sprp_32(n: u32, a: u32) -> bool {
     let n = n as u64;
@@ -416,6 +537,12 @@ macro_rules! impl_basic_traits {
             type Output = $self;
             fn neg(self) -> Self { self.neg_impl() }
         }
+        impl<$generic_param: $bound> FromStr for $self {
+            type Err = ParseModIntError;
+            fn from_str(s: &str) -> Result<Self, ParseModIntError> {
+                Self::from_str_impl(s)
+            }
+        }
         impl_basic_traits!($($rest)*);
     };
 }
@@ -533,3 +660,96 @@ fn fmt() {
     assert_eq!(format!("{}", x), "123");
     assert_eq!(format!("{:?}", x), "123 (mod 998244353)");
 }
+
+#[test]
+fn batch() {
+    type Mi = ModInt998244353;
+
+    assert!(batch_recip::<Mi>(&[]).is_empty());
+    let xs: Vec<_> =
+        (1..=1000_u64).map(|i| Mi::new(i * i * 998244351 + 1)).collect();
+    let ys = batch_recip(&xs);
+    assert_eq!(ys.len(), xs.len());
+    for (&x, &y) in xs.iter().zip(&ys) {
+        assert_eq!(x * y, Mi::new(1));
+        assert_eq!(y, x.recip());
+    }
+
+    // 法が素数でない場合も、すべて可逆ならよい
+    // 他のテストと法を共有しないよう、専用の id を使う
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    enum BatchId {}
+    impl DynamicModIntId for BatchId {
+        fn barrett() -> &'static Barrett {
+            static BARRETT: Barrett = Barrett::default();
+            &BARRETT
+        }
+    }
+    type Md = DynamicModInt<BatchId>;
+    Md::set_modulus(10);
+    let xs: Vec<_> = [1, 3, 7, 9, 13].iter().map(|&x| Md::new(x)).collect();
+    let ys = batch_recip(&xs);
+    assert!(xs.iter().zip(&ys).all(|(&x, &y)| x * y == Md::new(1)));
+}
+
+#[test]
+#[should_panic(expected = "not invertible")]
+fn batch_zero() {
+    type Mi = ModInt998244353;
+    batch_recip(&[Mi::new(1), Mi::new(0), Mi::new(2)]);
+}
+
+#[test]
+fn parse() {
+    type Mi = ModInt998244353;
+
+    assert_eq!("0".parse::<Mi>(), Ok(Mi::new(0)));
+    assert_eq!("-0".parse::<Mi>(), Ok(Mi::new(0)));
+    assert_eq!("+5".parse::<Mi>(), Ok(Mi::new(5)));
+    assert_eq!("-3".parse::<Mi>(), Ok(Mi::new(998244350)));
+    assert_eq!("00012".parse::<Mi>(), Ok(Mi::new(12)));
+    assert_eq!("998244353".parse::<Mi>(), Ok(Mi::new(0)));
+    assert_eq!("-998244354".parse::<Mi>(), Ok(Mi::new(-1)));
+    assert_eq!(
+        "340282366920938463463374607431768211455".parse::<Mi>(),
+        Ok(Mi::new(u128::MAX)),
+    );
+    assert_eq!(
+        "-170141183460469231731687303715884105728".parse::<Mi>(),
+        Ok(Mi::new(i128::MIN)),
+    );
+
+    assert_eq!("".parse::<Mi>(), Err(ParseModIntError::Empty));
+    assert_eq!("+".parse::<Mi>(), Err(ParseModIntError::Empty));
+    assert_eq!("-".parse::<Mi>(), Err(ParseModIntError::Empty));
+    assert_eq!("--1".parse::<Mi>(), Err(ParseModIntError::InvalidDigit));
+    assert_eq!(" 1".parse::<Mi>(), Err(ParseModIntError::InvalidDigit));
+    assert_eq!("1 ".parse::<Mi>(), Err(ParseModIntError::InvalidDigit));
+    assert_eq!("12a".parse::<Mi>(), Err(ParseModIntError::InvalidDigit));
+    assert_eq!("１".parse::<Mi>(), Err(ParseModIntError::InvalidDigit));
+
+    for x in (-1000..1000).chain(998243353..998245353) {
+        let a = Mi::new(x);
+        assert_eq!(x.to_string().parse::<Mi>(), Ok(a));
+        assert_eq!(a.to_string().parse::<Mi>(), Ok(a));
+        assert_eq!(a.to_string(), a.get().to_string());
+    }
+    assert_eq!(Mi::new(0).to_string(), "0");
+    assert_eq!(Mi::new(-1).to_string(), "998244352");
+
+    // 他のテストと法を共有しないよう、専用の id を使う
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    enum ParseId {}
+    impl DynamicModIntId for ParseId {
+        fn barrett() -> &'static Barrett {
+            static BARRETT: Barrett = Barrett::default();
+            &BARRETT
+        }
+    }
+    type Md = DynamicModInt<ParseId>;
+    Md::set_modulus(10);
+    assert_eq!("12345".parse::<Md>(), Ok(Md::new(5)));
+    assert_eq!("-12345".parse::<Md>(), Ok(Md::new(5)));
+    assert_eq!("-3".parse::<Md>(), Ok(Md::new(7)));
+    assert_eq!("99999999999999999999999".parse::<Md>(), Ok(Md::new(9)));
+}
@@ -1,13 +1,19 @@
 //! 多項式。
 
 use std::fmt::{self, Debug, Display};
+use std::hash::{Hash, Hasher};
 use std::ops::{
     Add, AddAssign, BitAnd, BitAndAssign, Div, DivAssign, Mul, MulAssign, Neg,
     Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
 };
 
-use convolution::{butterfly, butterfly_inv, convolve, NttFriendly};
-use modint::{ModIntBase, StaticModInt};
+use convolution::{
+    butterfly, butterfly_inv, convolve, convolve_u32_mod, NttFriendly,
+};
+use mod_sqrt::mod_sqrt;
+use modint::{
+    batch_recip, DynamicModInt, DynamicModIntId, ModIntBase, StaticModInt,
+};
 
 /// 多項式。
 ///
@@ -59,6 +65,36 @@ impl<M: NttFriendly> Debug for Polynomial<M> {
     }
 }
 
+/// 係数列そのものをハッシュする。
+///
+/// 多項式としての構造に対するハッシュであり、`HashMap` のキーや `HashSet`
+/// の要素として使うためのもの。係数列を $\\bmod p$ の多項式とみなして
+/// ある点で評価する、いわゆる rolling hash のような状態圧縮とは別物である。
+///
+/// 末尾の $0$ を除いた係数列をハッシュするので、`Eq` と整合する。
+///
+/// # Examples
+/// ```
+/// use std::collections::HashSet;
+///
+/// # use nekolib::math::{Mod998244353, Polynomial};
+/// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+/// let f: Poly = [1, 2].into();
+/// let g: Poly = [1, 4, 4].into();
+///
+/// let mut set = HashSet::new();
+/// set.insert(&f * &f);
+/// assert!(set.contains(&g));
+/// assert!(!set.contains(&f));
+/// ```
+impl<M: NttFriendly> Hash for Polynomial<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let len =
+            self.0.iter().rposition(|c| c.get() != 0).map_or(0, |i| i + 1);
+        self.0[..len].hash(state);
+    }
+}
+
 impl<M: NttFriendly> Polynomial<M> {
     /// $f(x) = 0$ を返す。
     ///
@@ -131,38 +167,80 @@ impl<M: NttFriendly> Polynomial<M> {
         if len == 0 {
             return Self(vec![]);
         }
+        self.recip_newton(Self(vec![self.0[0].recip()]), len)
+    }
 
-        let mut res = Self(vec![self.0[0].recip()]);
-        let mut cur_len = 1;
-        while cur_len < len {
-            cur_len *= 2;
+    /// $f(x)\\cdot g(x) \\equiv 1\\pmod{x^n}$ なる $g(x) \\bmod x^n$ を、既知の先頭部分から求める。
+    ///
+    /// `seed` の長さを $k$ として、$g(x) \\bmod x^k$ が `seed` と一致するとき、
+    /// そこから Newton 法を再開する。反復回数は $\\lceil\\log\_2(n/k)\\rceil$ となる。
+    /// `seed` が $0$ のときは [`recip`] と同じ。
+    ///
+    /// [`recip`]: #method.recip
+    ///
+    /// # Panics
+    /// debug build では、`seed` が正しくない場合に panic する。
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [1, -1].into();
+    /// let seed: Poly = [1; 3].into();
+    /// assert_eq!(f.recip_from(&seed, 10), Poly::from([1; 10]));
+    /// ```
+    pub fn recip_from(&self, seed: &Self, len: usize) -> Self {
+        if seed.is_zero() {
+            return self.recip(len);
+        }
+        let k = seed.len();
+        debug_assert_eq!(
+            (&self.ref_truncated(k) * seed).truncated(k),
+            Self::from([1]),
+            "`seed` must be a prefix of the reciprocal"
+        );
+        self.recip_newton(seed.clone(), len)
+    }
+
+    // res が f(x)^{-1} mod x^k のとき、f(x)^{-1} mod x^len を返す。
+    fn recip_newton(&self, mut res: Self, len: usize) -> Self {
+        let mut k = res.0.len();
+        while k < len {
+            // 巡回畳み込みの長さ m >= 2k において、長さ k の g(x) との積の
+            // [k, 2k) の部分は回り込みの影響を受けない。
+            let m = (2 * k).next_power_of_two();
 
             let mut ff: Self =
-                self.0[..self.0.len().min(cur_len)].to_vec().into();
+                self.0[..self.0.len().min(2 * k)].to_vec().into();
             let mut gg = res.clone();
-            ff.0.resize(cur_len, StaticModInt::new(0));
-            gg.0.resize(cur_len, StaticModInt::new(0));
+            ff.0.resize(m, StaticModInt::new(0));
+            gg.0.resize(m, StaticModInt::new(0));
             butterfly(&mut ff.0);
             butterfly(&mut gg.0);
-            for i in 0..cur_len {
+            for i in 0..m {
                 ff.0[i] *= gg.0[i];
             }
             butterfly_inv(&mut ff.0);
-            let iz = StaticModInt::new(cur_len).recip();
-            for i in 0..cur_len / 2 {
-                ff.0[i] = StaticModInt::new(0);
-                ff.0[cur_len / 2 + i] = -ff.0[cur_len / 2 + i] * iz;
+            let iz = StaticModInt::new(m).recip();
+            for i in 0..m {
+                if (k..2 * k).contains(&i) {
+                    ff.0[i] = -ff.0[i] * iz;
+                } else {
+                    ff.0[i] = StaticModInt::new(0);
+                }
             }
             butterfly(&mut ff.0);
-            for i in 0..cur_len {
+            for i in 0..m {
                 ff.0[i] *= gg.0[i];
             }
             butterfly_inv(&mut ff.0);
-            for i in 0..cur_len / 2 {
+            ff.0.truncate(2 * k);
+            for i in 0..k {
                 ff.0[i] = res.0[i];
-                ff.0[cur_len / 2 + i] *= iz;
+                ff.0[k + i] *= iz;
             }
             res = ff;
+            k *= 2;
         }
         res.truncated(len)
     }
@@ -244,6 +322,29 @@ impl<M: NttFriendly> Polynomial<M> {
         self.normalize();
     }
 
+    /// $x^d\\cdot f(1/x)$ を返す。
+    ///
+    /// [`reversed`] と異なり、$\\deg(f) \\lt d$ の場合は $x^d$ の係数までゼロ埋めしてからリバースする。
+    /// $\\deg(f) \\gt d$ の場合は $f(x)\\bmod x^{d+1}$ に対して行う。
+    ///
+    /// [`reversed`]: #method.reversed
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [0, 1, 2].into();
+    /// let g: Poly = [0, 2, 1].into();
+    /// assert_eq!(f.reversed_at(3), g);
+    /// assert_eq!(g.reversed_at(3), f);
+    /// ```
+    pub fn reversed_at(&self, deg: usize) -> Self {
+        let mut res = self.0[..self.0.len().min(deg + 1)].to_vec();
+        res.resize(deg + 1, StaticModInt::new(0));
+        res.reverse();
+        res.into()
+    }
+
     /// $f\'(x)$ を返す。
     ///
     /// $n = \\deg(f) + 1$ とし、
@@ -342,21 +443,88 @@ impl<M: NttFriendly> Polynomial<M> {
             return;
         }
         let n = self.0.len();
-        let recip = {
-            let m = M::VALUE as u64;
-            let mut dp = vec![1_u64; n + 1];
-            for i in 2..=n {
-                let (q, r) = (m / i as u64, m % i as u64);
-                dp[i as usize] = m - q * dp[r as usize] % m;
-            }
-            dp
-        };
+        let recip = Self::recip_table(n);
         for i in 0..n {
-            self.0[i] *= StaticModInt::new(recip[i + 1]);
+            self.0[i] *= recip[i + 1];
         }
         self.0.insert(0, StaticModInt::new(0));
     }
 
+    // (1/i)_{i=0}^n を返す。ただし 1/0 の部分は 1 とする。
+    fn recip_table(n: usize) -> Vec<StaticModInt<M>> {
+        let m = M::VALUE as u64;
+        let mut dp = vec![1_u64; n + 1];
+        for i in 2..=n {
+            let (q, r) = (m / i as u64, m % i as u64);
+            dp[i] = m - q * dp[r as usize] % m;
+        }
+        dp.into_iter().map(StaticModInt::new).collect()
+    }
+
+    /// $f(x+c)$ を返す。
+    ///
+    /// # Ideas
+    /// $$
+    /// \\begin{aligned}
+    /// f(x+c) &= \\sum\_{i=0}^{n-1} f\_i (x+c)^i
+    /// = \\sum\_{i=0}^{n-1} f\_i \\sum\_{k=0}^i \\binom{i}{k} c^{i-k} x^k \\\\
+    /// &= \\sum\_{k=0}^{n-1} \\frac{x^k}{k!} \\sum\_{i=k}^{n-1} (f\_i\\cdot i!)\\cdot\\frac{c^{i-k}}{(i-k)!}
+    /// \\end{aligned}
+    /// $$
+    /// であり、内側の和は $(f\_i\\cdot i!)\_i$ を反転したものと $(c^j/j!)\_j$
+    /// の畳み込みで得られる。
+    ///
+    /// # Requirements
+    /// $\\deg(f)$ は法より小さい。
+    ///
+    /// # Complexity
+    /// $O(n\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [1, 2, 3].into();
+    /// // 1 + 2(x+1) + 3(x+1)^2 = 6 + 8x + 3x^2
+    /// assert_eq!(f.taylor_shift(1), Poly::from([6, 8, 3]));
+    /// assert_eq!(f.taylor_shift(1).taylor_shift(-1), f);
+    /// ```
+    pub fn taylor_shift(&self, c: impl Into<StaticModInt<M>>) -> Self {
+        let c = c.into();
+        if c.get() == 0 || self.0.len() <= 1 {
+            return self.clone();
+        }
+
+        let n = self.0.len();
+        let recip = Self::recip_table(n);
+        let mut fact = StaticModInt::new(1);
+        let mut fact_recip = StaticModInt::new(1);
+        let mut a = vec![StaticModInt::new(0); n];
+        let mut b = vec![StaticModInt::new(0); n];
+        let mut pow = StaticModInt::new(1);
+        for i in 0..n {
+            if i > 0 {
+                fact *= StaticModInt::new(i);
+                fact_recip *= recip[i];
+            }
+            a[n - 1 - i] = self.0[i] * fact;
+            b[i] = pow * fact_recip;
+            pow *= c;
+        }
+
+        let conv = convolve(a, b);
+        let mut fact_recip = StaticModInt::new(1);
+        let res: Vec<_> = (0..n)
+            .map(|k| {
+                if k > 0 {
+                    fact_recip *= recip[k];
+                }
+                conv[n - 1 - k] * fact_recip
+            })
+            .collect();
+        res.into()
+    }
+
     /// $\[x\^0] f(x) = 1$ なる $f$ に対し、$\\log(f(x)) \\bmod x^n$ を返す。
     ///
     /// $\\log(1-f(x)) = -\\sum\_{n=1}^{\\infty} \\frac{f(x)^n}{n}$ などで定義される。
@@ -546,6 +714,158 @@ impl<M: NttFriendly> Polynomial<M> {
         (g_pow << (l * k_)) * a_l.pow(k_ as u64)
     }
 
+    /// $\[x\^0] f(x) = 1$ なる $f$ に対し、$f(x)\^{\\alpha} \\bmod x^n$ を返す。
+    ///
+    /// $\\alpha$ は整数とは限らず、$f(x)\^{\\alpha} = \\exp(\\alpha\\log(f(x)))$
+    /// によって定義される。
+    /// たとえば $\\alpha = 1/2$ とすると、$g(x)^2 \\equiv f(x) \\pmod{x^n}$
+    /// かつ $\[x\^0] g(x) = 1$ なる $g$ が得られる。
+    ///
+    /// 自明なケースは [`pow`](#method.pow) と同様で、$\\alpha = 0$ のときは $1$、
+    /// $f(x) = 0$ のときは $0$ である。
+    ///
+    /// # Panics
+    /// 上記以外で、$f(x) = a\_l x^l \\cdot (1+g(x))$ と書いたときに $l \\ne 0$
+    /// または $a\_l \\ne 1$ の場合。$x^{l\\alpha}$ や $a\_l^{\\alpha}$
+    /// が一般には定まらないため。
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, ModIntBase, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// # type Mi = nekolib::math::ModInt998244353;
+    /// let f: Poly = [1, 1].into();
+    /// let g: Poly = [1, 499122177, 124780544, 935854081, 38993920].into();
+    /// // (1+x)^{1/2} = 1 + 1/2 x - 1/8 x^2 + 1/16 x^3 - 5/128 x^4 + ...
+    /// assert_eq!(f.pow_frac(Mi::new(2).recip(), 5), g);
+    /// ```
+    pub fn pow_frac(&self, alpha: StaticModInt<M>, len: usize) -> Self {
+        // 0^0 = 1
+        if alpha.get() == 0 {
+            return Self::from([1]).truncated(len);
+        } else if self.is_zero() {
+            return Self::new();
+        }
+
+        // f(x) = (a_l x^l) (1+g(x))
+        let l = (0..).find(|&i| self.0[i].get() != 0).unwrap();
+        assert_eq!(l, 0, "the lowest term must be the constant term");
+        assert_eq!(self.0[0].get(), 1, "the constant term must be 1");
+        if len == 0 {
+            return Self::new();
+        }
+
+        (self.log(len) * alpha).exp(len)
+    }
+
+    /// $g(x)^2 \\equiv f(x) \\pmod{x^n}$ なる $g(x) \\bmod x^n$ を返す。
+    ///
+    /// $f(x)$ は $f(x) \\bmod x^n$ とみなす。$f(x) \\equiv 0$ のときは $0$ を返す。
+    /// それ以外のとき、$f(x) = a\_l x^l \\cdot (1+h(x))$ と書いて、$l$
+    /// が奇数または $a\_l$ が平方非剰余であれば `None` を返す。
+    /// $g(x)$ は $\\pm$ の分だけ自由度があるので、$\[x^{l/2}] g(x)$
+    /// として $a\_l$ の平方根のうち小さい方を選ぶ。
+    ///
+    /// # Ideas
+    /// $l \\gt 0$ のときは $f(x) / x^l$ の平方根を求めて $x^{l/2}$ を掛ければよい。
+    ///
+    /// $l = 0$ のとき、$g\_k(x)^2 \\equiv f(x) \\pmod{x^k}$ なる $g\_k$ に対して
+    /// $$
+    /// g\_{2k}(x) = \\frac{1}{2}\\left(g\_k(x) + \\frac{f(x)}{g\_k(x)}\\right)
+    /// = g\_k(x) + \\frac{f(x)-g\_k(x)^2}{2\\cdot g\_k(x)}
+    /// $$
+    /// とすると $g\_{2k}(x)^2 \\equiv f(x) \\pmod{x^{2k}}$ となる。
+    /// $f(x)-g\_k(x)^2 \\equiv 0 \\pmod{x^k}$ なので、右辺の割り算には
+    /// $g\_k(x)^{-1} \\bmod x^k$ があれば十分である。これも
+    /// [`recip`](#method.recip) と同様の Newton 法で並行して更新する。
+    ///
+    /// # Complexity
+    /// $O(n\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [4, 4, 1].into();
+    /// assert_eq!(f.sqrt(5), Some([2, 1].into()));
+    ///
+    /// let f: Poly = [0, 0, 1, 1].into();
+    /// let g: Poly = [0, 1, 499122177, 124780544, 935854081].into();
+    /// // sqrt(x^2 + x^3) = x (1+x)^{1/2} = x + 1/2 x^2 - 1/8 x^3 + 1/16 x^4 + ...
+    /// assert_eq!(f.sqrt(5), Some(g));
+    ///
+    /// // 3 は mod 998244353 で平方非剰余
+    /// assert_eq!(Poly::from([3, 1]).sqrt(5), None);
+    /// // 先頭の非零項の次数が奇数
+    /// assert_eq!(Poly::from([0, 1]).sqrt(5), None);
+    /// ```
+    pub fn sqrt(&self, len: usize) -> Option<Self> {
+        let f = self.ref_truncated(len);
+        if f.is_zero() {
+            return Some(Self::new());
+        }
+
+        // f(x) = (a_l x^l) (1+h(x))
+        let l = (0..).find(|&i| f.0[i].get() != 0).unwrap();
+        if l % 2 != 0 {
+            return None;
+        }
+        if l > 0 {
+            return (f >> l).sqrt(len - l / 2).map(|g| g << (l / 2));
+        }
+
+        let g0 = Self::sqrt_const(f.0[0])?;
+        Some(f.sqrt_newton(g0, len))
+    }
+
+    // a の平方根のうち小さい方を返す。
+    fn sqrt_const(a: StaticModInt<M>) -> Option<StaticModInt<M>> {
+        let p = M::VALUE as u64;
+        mod_sqrt(a.get() as u64, p).map(StaticModInt::new)
+    }
+
+    // g0^2 == [x^0] f(x) のとき、f(x)^{1/2} mod x^len を返す。
+    fn sqrt_newton(&self, g0: StaticModInt<M>, len: usize) -> Self {
+        let zero = StaticModInt::new(0);
+        let half = StaticModInt::new(2).recip();
+        let mut g = vec![g0];
+        let mut h = Self(vec![g0.recip()]);
+        let mut k = 1;
+        while k < len {
+            let m = 2 * k;
+            let iz = StaticModInt::new(m).recip();
+
+            // f(x) - g(x)^2 の [k, 2k) の部分。長さ k の g(x) の二乗は回り込まない。
+            let mut sq = g.clone();
+            sq.resize(m, zero);
+            butterfly(&mut sq);
+            for x in &mut sq {
+                *x *= *x;
+            }
+            butterfly_inv(&mut sq);
+            let mut d: Vec<_> =
+                (k..m).map(|i| self.get(i) - sq[i] * iz).collect();
+
+            d.resize(m, zero);
+            let mut hh = h.0.clone();
+            hh.resize(m, zero);
+            butterfly(&mut d);
+            butterfly(&mut hh);
+            for i in 0..m {
+                d[i] *= hh[i];
+            }
+            butterfly_inv(&mut d);
+            let c = iz * half;
+            g.extend(d[..k].iter().map(|&x| x * c));
+
+            k = m;
+            if k < len {
+                h = Self(g.clone()).recip_newton(h, k);
+            }
+        }
+        Self(g).truncated(len)
+    }
+
     #[allow(dead_code)]
     fn circular_naive(&self, im: &Self, len: usize) -> (Self, Self) {
         let re = self;
@@ -704,6 +1024,143 @@ impl<M: NttFriendly> Polynomial<M> {
         (sin * cos.recip(len)).truncated(len)
     }
 
+    /// $f(g(x)) \\bmod x^n$ を返す。
+    ///
+    /// $f$ は多項式なので、$\[x^0] g(x) = c \\ne 0$ の場合も $f(g(x))$
+    /// は定まる。ただしこの場合は $f(g(x)) = f(c + (g(x)-c))$ の各係数に
+    /// $f$ の全ての係数が寄与するので、$f$ を $\\bmod x^n$ で切り捨てることはしない。
+    /// $c = 0$ の場合は $f(x) \\bmod x^n$ のみを用いる。
+    ///
+    /// # Ideas
+    /// Kinoshita--Li の方法による。
+    ///
+    /// $m = \\deg(f)+1$ とし、合成を $m\\times n$ 行列 $C\_{k, i} = \[x^k] g(x)^i$
+    /// と $f$ の積とみなす。この転置は、$w(x)$ に対して
+    /// $$
+    /// \\sum\_{i=0}^{m-1} y^i\\cdot \[x^{n-1}] w(x)\\cdot g(x)^i
+    /// = \[x^{n-1}] \\frac{w(x)}{1-y\\,g(x)} \\bmod y^m
+    /// $$
+    /// を求める問題（power projection）であり、これは $x$ についての
+    /// Bostan--Mori 法で求められる。
+    /// すなわち、$P(x, y)/Q(x, y)$ の分母分子に $Q(-x, y)$ を掛けて分母を
+    /// $x$ について偶関数にし、必要な偶奇の係数のみを残すことを
+    /// $x$ の次数が $1$ になるまで繰り返す。$x$ の次数が半分になるごとに
+    /// $y$ の次数は倍になるので、各段階での計算量は $O(n\\log(n))$ である。
+    /// 最後に $P(0, y)/Q(0, y) \\bmod y^m$ を求める。
+    ///
+    /// これらの操作はいずれも $w$ について線形なので、各操作を転置して逆順に行うと
+    /// $f \\mapsto C f$ が得られる。積 $P\\mapsto P\\cdot R$ の転置は
+    /// $R$ との middle product である。$Q(1-yg(x))$ の $x$ の定数項は $1-cy$
+    /// であり、$c$ によらず $y$ について可逆となる。
+    ///
+    /// # Complexity
+    /// $O(n\\log(n)^2 + m\\log(m))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [1, 2, 3].into();
+    /// let g: Poly = [0, 1, 1].into();
+    /// // 1 + 2(x+x^2) + 3(x+x^2)^2 = 1 + 2x + 5x^2 + 6x^3 + 3x^4
+    /// assert_eq!(f.compose(&g, 4), Poly::from([1, 2, 5, 6]));
+    ///
+    /// let g: Poly = [1, 1].into();
+    /// // 1 + 2(1+x) + 3(1+x)^2 = 6 + 8x + 3x^2
+    /// assert_eq!(f.compose(&g, 10), Poly::from([6, 8, 3]));
+    /// ```
+    pub fn compose(&self, g: &Self, len: usize) -> Self {
+        if len == 0 || self.is_zero() {
+            return Self::new();
+        }
+
+        let m = if g.get(0).get() == 0 {
+            self.0.len().min(len)
+        } else {
+            self.0.len()
+        };
+
+        // Q(x, y) = 1 - y g(x)
+        let mut q = vec![StaticModInt::new(0); 2 * len];
+        for a in 0..len {
+            q[2 * a + 1] = -g.get(a);
+        }
+        q[0] = StaticModInt::new(1);
+
+        let p = Self::compose_rec(q, len, 1, &self.0[..m]);
+        let res: Vec<_> = (0..len).map(|k| p[len - 1 - k]).collect();
+        res.into()
+    }
+
+    // q は x について長さ l、y について d 次の二変数多項式で、q[a * (d + 1) + b]
+    // が x^a y^b の係数。[x^{l-1}] P/q mod y^{f.len()} を P の関数とみなしたときの
+    // 転置を f に適用したものを、x について長さ l、y について長さ d で返す。
+    fn compose_rec(
+        q: Vec<StaticModInt<M>>,
+        l: usize,
+        d: usize,
+        f: &[StaticModInt<M>],
+    ) -> Vec<StaticModInt<M>> {
+        let zero = StaticModInt::new(0);
+        let w = d + 1;
+        if l == 1 {
+            // P(0, y) / Q(0, y) mod y^m の転置
+            let m = f.len();
+            let s = Self::from(&q[..w]).recip(m);
+            let rev: Vec<_> = f.iter().rev().copied().collect();
+            let c = convolve(rev, s.0);
+            return (0..d)
+                .map(|b| if b < m { c[m - 1 - b] } else { zero })
+                .collect();
+        }
+
+        // 行の幅を広げて、一変数の畳み込みで二変数の積を計算する。
+        let embed = |a: &[StaticModInt<M>], w: usize, to_w: usize| {
+            let mut res = vec![zero; a.len() / w * to_w];
+            for (i, row) in a.chunks(w).enumerate() {
+                res[i * to_w..][..w].copy_from_slice(row);
+            }
+            res
+        };
+
+        let mut qm = q.clone();
+        for row in qm.chunks_mut(w).skip(1).step_by(2) {
+            for c in row {
+                *c = -*c;
+            }
+        }
+
+        // Q(x, y) Q(-x, y) は x について偶関数
+        let w2 = 2 * d + 1;
+        let v = convolve(embed(&q, w, w2), embed(&qm, w, w2));
+        let l2 = l.div_ceil(2);
+        let mut q2 = vec![zero; l2 * w2];
+        for a in 0..l2 {
+            q2[a * w2..][..w2].copy_from_slice(&v[2 * a * w2..][..w2]);
+        }
+        let u2 = Self::compose_rec(q2, l2, 2 * d, f);
+
+        // 偶奇の係数を取り出す操作の転置
+        let wu = 2 * d;
+        let mut u = vec![zero; l * wu];
+        for (j, row) in u2.chunks(wu).enumerate() {
+            let a = (l - 1) % 2 + 2 * j;
+            u[a * wu..][..wu].copy_from_slice(row);
+        }
+
+        // Q(-x, y) を掛ける操作の転置
+        let w3 = 3 * d;
+        let mut rm = qm;
+        rm.reverse();
+        let c = convolve(embed(&u, wu, w3), embed(&rm, w, w3));
+        let mut res = vec![zero; l * d];
+        for a in 0..l {
+            let off = (a + l - 1) * w3 + d;
+            res[a * d..][..d].copy_from_slice(&c[off..][..d]);
+        }
+        res
+    }
+
     // f(y) = f(y0) + (y-y0) f'(y0) = 0
     // y = y0 - f(y0)/f'(y0)
     /// `self` を初期解とし、$f(y) = 0$ を満たす $y$ を求める。
@@ -923,6 +1380,216 @@ impl<M: NttFriendly> Polynomial<M> {
         ft
     }
 
+    /// $(f\_i(t))\_{i=0}^{k-1}$ を返す。
+    ///
+    /// $t$ の冪を $\\max\_i \\deg(f\_i)$ 次まで前計算しておき、各 $f\_i$
+    /// との内積を取る。
+    ///
+    /// # Complexity
+    /// $O(\\sum\_i \\deg(f\_i))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, ModIntBase, Polynomial, StaticModInt};
+    /// # type Poly = Polynomial::<Mod998244353>;
+    /// let fs: Vec<Poly> = vec![[1, 2].into(), [3, 0, 1].into(), Poly::new()];
+    /// let t = StaticModInt::new(2);
+    /// let res = Poly::eval_all_at(&fs, t);
+    /// assert_eq!(res.iter().map(|x| x.get()).collect::<Vec<_>>(), [5, 7, 0]);
+    /// ```
+    pub fn eval_all_at(
+        polys: &[Self],
+        t: StaticModInt<M>,
+    ) -> Vec<StaticModInt<M>> {
+        let len = polys.iter().map(|f| f.0.len()).max().unwrap_or(0);
+        let mut pow = vec![StaticModInt::new(1); len];
+        for i in 1..len {
+            pow[i] = pow[i - 1] * t;
+        }
+        polys
+            .iter()
+            .map(|f| f.0.iter().zip(&pow).map(|(&a, &p)| a * p).sum())
+            .collect()
+    }
+
+    /// $(f(x\_i))\_{i=0}^{m-1}$ を返す。
+    ///
+    /// # Ideas
+    /// $f(x\_i) = f(x) \\bmod (x-x\_i)$ であることを用いる。
+    /// $\\prod\_{i\\in I} (x-x\_i)$ をセグメント木状に持つ（subproduct tree）。
+    /// 根から順に、親での剰余をさらに子の多項式で割った剰余を求めていくと、
+    /// 葉では $f(x) \\bmod (x-x\_i) = f(x\_i)$ が得られる。
+    ///
+    /// # Complexity
+    /// $O(n\\log(n) + m\\log(m)^2)$ time, where $n = \\deg(f)+1$.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, ModIntBase, Polynomial, StaticModInt};
+    /// # type Poly = Polynomial::<Mod998244353>;
+    /// let f: Poly = [1, 2, 3].into();
+    /// let xs: Vec<_> = [0, 1, 2, -1].iter().map(|&x| StaticModInt::new(x)).collect();
+    /// let ys = f.eval_multi(&xs);
+    /// assert_eq!(ys.iter().map(|y| y.get()).collect::<Vec<_>>(), [1, 6, 17, 2]);
+    /// ```
+    pub fn eval_multi(&self, xs: &[StaticModInt<M>]) -> Vec<StaticModInt<M>> {
+        if xs.is_empty() {
+            return vec![];
+        }
+        let tree = Self::subproduct_tree(xs);
+        self.eval_subproduct(&tree, xs.len())
+    }
+
+    /// $f(x\_i) = y\_i$ ($0\\le i\\lt n$) なる $n-1$ 次以下の多項式 $f(x)$ を返す。
+    ///
+    /// $n = 0$ のときは $0$ を返す。
+    ///
+    /// # Ideas
+    /// $g(x) = \\prod\_{i=0}^{n-1} (x-x\_i)$ とおくと、Lagrange 補間により
+    /// $$
+    /// f(x) = \\sum\_{i=0}^{n-1} \\frac{y\_i}{g\'(x\_i)}\\cdot\\frac{g(x)}{x-x\_i}
+    /// $$
+    /// となる。$g\'(x\_i) = \\prod\_{j\\ne i} (x\_i-x\_j)$ は [`eval_multi`]
+    /// と同じ subproduct tree で多点評価して求める。
+    /// 和の部分は、葉を $y\_i/g\'(x\_i)$ とし、子 $(l, r)$ の値
+    /// $f\_l, f\_r$ と積 $g\_l, g\_r$ から $f\_l g\_r + f\_r g\_l$ を親の値とすることで、
+    /// 同じ木の上で下から求まる。
+    ///
+    /// [`eval_multi`]: #method.eval_multi
+    ///
+    /// # Panics
+    /// $x\_i$ に重複がある場合。
+    ///
+    /// # Complexity
+    /// $O(n\\log(n)^2)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, ModIntBase, Polynomial, StaticModInt};
+    /// # type Poly = Polynomial::<Mod998244353>;
+    /// let points: Vec<_> = [(0, 1), (1, 6), (2, 17)]
+    ///     .iter()
+    ///     .map(|&(x, y)| (StaticModInt::new(x), StaticModInt::new(y)))
+    ///     .collect();
+    /// assert_eq!(Poly::from_points(&points), Poly::from([1, 2, 3]));
+    /// ```
+    pub fn from_points(points: &[(StaticModInt<M>, StaticModInt<M>)]) -> Self {
+        let n = points.len();
+        if n == 0 {
+            return Self::new();
+        }
+
+        let xs: Vec<_> = points.iter().map(|&(x, _)| x).collect();
+        let tree = Self::subproduct_tree(&xs);
+        let w = tree[1].clone().differential().eval_subproduct(&tree, n);
+
+        assert!(w.iter().all(|wi| wi.get() != 0), "`x` must be distinct");
+        let w_recip = batch_recip(&w);
+
+        let n2 = tree.len() / 2;
+        let mut res = vec![Self::new(); 2 * n2];
+        for i in 0..n {
+            res[n2 + i] = Self::from(vec![points[i].1 * w_recip[i]]);
+        }
+        for i in (1..n2).rev() {
+            res[i] =
+                &res[2 * i] * &tree[2 * i + 1] + &res[2 * i + 1] * &tree[2 * i];
+        }
+        res.swap_remove(1)
+    }
+
+    /// $f(i) = y\_i$ ($0\\le i\\lt n$) なる $n-1$ 次以下の多項式 $f(x)$ に対して、$f(t)$ を返す。
+    ///
+    /// `Poly::from_points(&[(0, y_0), ..., (n-1, y_{n-1})]).eval(t)` と同じ値を、
+    /// 多項式を復元せずに求める。$x\_i = a+id$ の形の等間隔点に対しては、
+    /// $t$ の代わりに $(t-a)/d$ を渡せばよい。$n = 0$ のときは $0$ を返す。
+    ///
+    /// # Ideas
+    /// $$
+    /// f(t) = \\sum\_{i=0}^{n-1} y\_i \\prod\_{j\\ne i} \\frac{t-j}{i-j}
+    /// = \\sum\_{i=0}^{n-1} y\_i\\cdot
+    /// \\frac{\\prod\_{j\\lt i} (t-j)\\cdot \\prod\_{j\\gt i} (t-j)}{(-1)^{n-1-i}\\cdot i!\\,(n-1-i)!}
+    /// $$
+    /// であり、$t$ の累積積と階乗の逆元を前計算すればよい。
+    ///
+    /// # Requirements
+    /// $n$ は法より小さい。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, ModIntBase, Polynomial, StaticModInt};
+    /// # type Poly = Polynomial::<Mod998244353>;
+    /// // f(x) = 1 + 2x + 3x^2
+    /// let ys: Vec<_> = [1, 6, 17].iter().map(|&y| StaticModInt::new(y)).collect();
+    /// assert_eq!(Poly::interpolate_iota(&ys, StaticModInt::new(1)).get(), 6);
+    /// assert_eq!(Poly::interpolate_iota(&ys, StaticModInt::new(10)).get(), 321);
+    /// assert_eq!(Poly::interpolate_iota(&ys, StaticModInt::new(-1)).get(), 2);
+    /// ```
+    pub fn interpolate_iota(
+        ys: &[StaticModInt<M>],
+        t: StaticModInt<M>,
+    ) -> StaticModInt<M> {
+        let n = ys.len();
+        if n == 0 {
+            return StaticModInt::new(0);
+        }
+        if (t.get() as usize) < n {
+            return ys[t.get() as usize];
+        }
+
+        let one = StaticModInt::new(1);
+        let mut suf = vec![one; n + 1];
+        for i in (0..n).rev() {
+            suf[i] = suf[i + 1] * (t - StaticModInt::new(i));
+        }
+        let mut fact_recip = vec![one; n];
+        fact_recip[n - 1] =
+            (1..n).map(StaticModInt::new).fold(one, |x, y| x * y).recip();
+        for i in (1..n).rev() {
+            fact_recip[i - 1] = fact_recip[i] * StaticModInt::new(i);
+        }
+
+        let mut res = StaticModInt::new(0);
+        let mut pre = one;
+        for i in 0..n {
+            let w = fact_recip[i] * fact_recip[n - 1 - i];
+            let term = ys[i] * pre * suf[i + 1] * w;
+            if (n - 1 - i) % 2 == 1 {
+                res -= term;
+            } else {
+                res += term;
+            }
+            pre *= t - StaticModInt::new(i);
+        }
+        res
+    }
+
+    // 葉が x-x_i の積のセグメント木。長さが足りない部分の葉は 1 とする。
+    fn subproduct_tree(xs: &[StaticModInt<M>]) -> Vec<Self> {
+        let n = xs.len().next_power_of_two();
+        let mut tree = vec![Self::from([1]); 2 * n];
+        for (i, &x) in xs.iter().enumerate() {
+            tree[n + i] = Self(vec![-x, StaticModInt::new(1)]);
+        }
+        for i in (1..n).rev() {
+            tree[i] = &tree[2 * i] * &tree[2 * i + 1];
+        }
+        tree
+    }
+
+    fn eval_subproduct(&self, tree: &[Self], m: usize) -> Vec<StaticModInt<M>> {
+        let n = tree.len() / 2;
+        let mut rem = vec![Self::new(); 2 * n];
+        rem[1] = self % &tree[1];
+        for i in 2..n + m {
+            rem[i] = &rem[i / 2] % &tree[i];
+        }
+        rem[n..n + m].iter().map(|r| r.get(0)).collect()
+    }
+
     /// $(\[x^i] f(x))\_{i=0}^{\\deg(f)}$ を返す。
     pub fn into_inner(self) -> Vec<StaticModInt<M>> { self.0 }
 
@@ -977,6 +1644,26 @@ impl<M: NttFriendly> Polynomial<M> {
     /// $\\deg(f)-1$ を返す。ただし $f(x) = 0$ のときは $0$ を返す。
     pub fn len(&self) -> usize { self.0.len() }
 
+    /// 各係数の代表値が $0$ 以上 $k$ 未満であるかを返す。
+    ///
+    /// 係数が $0$ 以上 $k$ 未満の整数になるはずの計算において、
+    /// 法を取らなくても値が収まっているかの検算に使う。
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [1, 2, 1].into();
+    /// assert!((&f * &f).is_integral_upto(7));
+    /// assert!(!(&f * &f).is_integral_upto(6));
+    ///
+    /// let g: Poly = [0, 1].into();
+    /// assert!(!g.exp(4).is_integral_upto(1 << 20));
+    /// ```
+    pub fn is_integral_upto(&self, k: u64) -> bool {
+        self.0.iter().all(|c| (c.get() as u64) < k)
+    }
+
     /// $(f(x) / g(x), f(x) \\bmod g(x))$ を返す。
     ///
     /// $f(x) / g(x)$ は $f(x)\\cdot g(x)^{-1}$ ではなく多項式としての除算である。
@@ -986,33 +1673,233 @@ impl<M: NttFriendly> Polynomial<M> {
         (q, r)
     }
 
-    // [x^n] self/other
-    /// $\[x^n] f(x) \\cdot g(x)^{-1}$ を返す。
-    pub fn div_nth(
-        &self,
-        other: &Polynomial<M>,
-        mut n: usize,
-    ) -> StaticModInt<M> {
-        let mut p = self.clone();
-        let mut q = other.clone();
-        while n > 0 {
-            let d = (2 * q.0.len() - 1).next_power_of_two();
-            p.fft_butterfly(d);
-            q.fft_butterfly(d);
-            let pq_: Vec<_> = (0..d).map(|i| p.get(i) * q.get(i ^ 1)).collect();
-            let qq_: Vec<_> =
-                (0..d).step_by(2).map(|i| q.get(i) * q.get(i + 1)).collect();
-            let (mut pq_, mut qq_): (Self, Self) = (pq_.into(), qq_.into());
-            pq_.fft_inv_butterfly(d);
-            qq_.fft_inv_butterfly(d / 2);
-            let u: Vec<_> = (n % 2..d).step_by(2).map(|i| pq_.get(i)).collect();
-            p = u.into();
-            q = qq_.into();
-            n /= 2;
+    /// $\\gcd(f(x), g(x))$ を返す。
+    ///
+    /// 結果は最高次の係数が $1$ になるように（モニックに）正規化する。
+    /// 片方が $0$ のときは他方をモニックにしたもの、両方が $0$ のときは $0$ を返す。
+    ///
+    /// # Complexity
+    /// $n = \\deg(f)+1$, $m = \\deg(g)+1$ とする。Euclid の互除法により、
+    /// [`div_mod`](#method.div_mod) による除算を $O(\\min\\{n, m\\})$ 回行うので、
+    /// $O(\\max\\{n, m\\}\\cdot\\min\\{n, m\\}\\log(\\max\\{n, m\\}))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [2, 6, 4].into(); // 2(x+1)(2x+1)
+    /// let g: Poly = [-3, 0, 3].into(); // 3(x+1)(x-1)
+    /// assert_eq!(f.gcd(&g), Poly::from([1, 1]));
+    ///
+    /// assert_eq!(f.gcd(&Poly::new()), Poly::from([499122177, 499122178, 1]));
+    /// assert_eq!(Poly::new().gcd(&Poly::new()), Poly::new());
+    /// ```
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut res = Self::gcd_euclid(self.clone(), other.clone());
+        if let Some(&lead) = res.0.last() {
+            res /= lead;
+        }
+        res
+    }
+
+    // 正規化する前の gcd を返す。half-GCD に差し替えるならここ。
+    fn gcd_euclid(mut f: Self, mut g: Self) -> Self {
+        while !g.is_zero() {
+            let (_, r) = f.div_mod(&g);
+            f = std::mem::replace(&mut g, r);
+        }
+        f
+    }
+
+    /// $f(x)/(1-x) \\bmod x^n$ を返す。
+    ///
+    /// すなわち、係数の累積和 $\\sum\_{j=0}^i f\_j$ を $i$ 次の係数とする。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [1, 2, 3].into();
+    /// assert_eq!(f.prefix_sum(5), Poly::from([1, 3, 6, 6, 6]));
+    /// assert_eq!(f.prefix_sum(2), Poly::from([1, 3]));
+    /// ```
+    pub fn prefix_sum(&self, len: usize) -> Self {
+        let mut res = vec![StaticModInt::new(0); len];
+        let mut acc = StaticModInt::new(0);
+        for (i, ri) in res.iter_mut().enumerate() {
+            acc += self.get(i);
+            *ri = acc;
+        }
+        res.into()
+    }
+
+    /// $f(x)\\cdot (1-x)$ を返す。
+    ///
+    /// すなわち、係数の階差 $f\_i - f\_{i-1}$ を $i$ 次の係数とする。
+    /// ただし $f\_{-1} = 0$ とする。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [1, 3, 6].into();
+    /// assert_eq!(f.difference(), Poly::from([1, 2, 3, -6]));
+    /// ```
+    pub fn difference(&self) -> Self {
+        let mut res = self.0.clone();
+        res.push(StaticModInt::new(0));
+        for i in (1..res.len()).rev() {
+            let prev = res[i - 1];
+            res[i] -= prev;
+        }
+        res.into()
+    }
+
+    /// $f(x) \\bmod (x^m-1)$ を返す。
+    ///
+    /// $x^m \\equiv 1$ より、$i$ 次の係数を $i \\bmod m$ 次に足し込めばよい。
+    /// 長さ $m$ の循環畳み込みは `(&f * &g).mod_cyclic(m)` で得られる。
+    ///
+    /// # Requirements
+    /// $m \\gt 0$.
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [1, 2, 3, 4, 5].into();
+    /// assert_eq!(f.mod_cyclic(2), Poly::from([9, 6]));
+    /// assert_eq!(f.mod_cyclic(3), Poly::from([5, 7, 3]));
+    /// assert_eq!(f.mod_cyclic(6), f);
+    /// ```
+    pub fn mod_cyclic(&self, m: usize) -> Self {
+        assert!(m > 0, "`m` must be positive");
+        let mut res = vec![StaticModInt::new(0); m.min(self.0.len())];
+        for (i, &c) in self.0.iter().enumerate() {
+            res[i % m] += c;
+        }
+        res.into()
+    }
+
+    // [x^n] self/other
+    /// $\[x^n] f(x) \\cdot g(x)^{-1}$ を返す。
+    pub fn div_nth(
+        &self,
+        other: &Polynomial<M>,
+        mut n: usize,
+    ) -> StaticModInt<M> {
+        let mut p = self.clone();
+        let mut q = other.clone();
+        while n > 0 {
+            let (pq_, qq_) = Self::bostan_mori_step(&p, &q);
+            let u: Vec<_> =
+                (n % 2..pq_.0.len()).step_by(2).map(|i| pq_.get(i)).collect();
+            p = u.into();
+            q = qq_;
+            n /= 2;
         }
         p.get(0)
     }
 
+    /// $(\[x^i] f(x) \\cdot g(x)^{-1})\_{i=n}^{n+k-1}$ を返す。
+    ///
+    /// [`div_nth`](#method.div_nth) を $k$ 回呼ぶ代わりに、
+    /// $g(x)^{-1}$ の係数のうち必要な範囲をまとめて求める。
+    ///
+    /// # Ideas
+    /// $f(x) = \\sum\_{j=0}^{m} f\_j x^j$ とすると、
+    /// $\[x^i] f(x)\\cdot g(x)^{-1} = \\sum\_j f\_j\\cdot\[x^{i-j}] g(x)^{-1}$
+    /// なので、$g(x)^{-1}$ の $n-m$ 次から $n+k-1$ 次までの係数があればよい。
+    ///
+    /// $g(x)^{-1}$ の $l$ 次から $r-1$ 次までの係数を求めることを考える。
+    /// [`div_nth`](#method.div_nth) と同様に
+    /// $$
+    /// \\frac{1}{g(x)} = \\frac{g(-x)}{g(x)g(-x)} = \\frac{g(-x)}{h(x^2)}
+    /// $$
+    /// と変形すると、$d = \\deg(g)$ として、$h(y)^{-1}$ の
+    /// $\\lfloor (l-d)/2\\rfloor$ 次から $\\lfloor (r-1)/2\\rfloor$
+    /// 次までの係数から $g(-x)$ を掛けて求まる。
+    /// 区間の長さは $r-l$ からおよそ $(r-l+d)/2$ に減り、左端はおよそ半分になる。
+    /// 左端が十分小さくなったら、$g(x)^{-1}$ を直接計算する。
+    ///
+    /// # Requirements
+    /// $\[x^0] g(x) \\ne 0$.
+    ///
+    /// # Complexity
+    /// $O((m+k+d)\\log(m+k+d)\\log(n))$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use nekolib::math::{Mod998244353, ModIntBase, Polynomial};
+    /// # type Poly = Polynomial::<nekolib::math::Mod998244353>;
+    /// let f: Poly = [1].into();
+    /// let g: Poly = [1, -1, -1].into();
+    /// // 1 / (1 - x - x^2) = 1 + x + 2x^2 + 3x^3 + 5x^4 + 8x^5 + ...
+    /// let actual = f.div_nth_range(&g, 3, 4);
+    /// assert_eq!(actual.iter().map(|c| c.get()).collect::<Vec<_>>(), [3, 5, 8, 13]);
+    /// ```
+    pub fn div_nth_range(
+        &self,
+        other: &Polynomial<M>,
+        n: usize,
+        k: usize,
+    ) -> Vec<StaticModInt<M>> {
+        assert_ne!(other.get(0).get(), 0, "the constant term must be non-zero");
+
+        if k == 0 || self.is_zero() {
+            return vec![StaticModInt::new(0); k];
+        }
+        let lo = n.saturating_sub(self.0.len() - 1);
+        let w: Self = other.recip_range(lo, n + k).into();
+        let fw = self * &w;
+        (n..n + k).map(|i| fw.get(i - lo)).collect()
+    }
+
+    // ([x^i] 1/self)_{i=lo}^{hi-1}
+    fn recip_range(&self, lo: usize, hi: usize) -> Vec<StaticModInt<M>> {
+        let d = self.0.len() - 1;
+        if lo <= hi - lo + d {
+            let r = self.recip(hi);
+            return (lo..hi).map(|i| r.get(i)).collect();
+        }
+
+        let (q_neg, qq_) = Self::bostan_mori_step(&Self::from([1]), self);
+        let lo2 = (lo - d) / 2;
+        let hi2 = (hi - 1) / 2 + 1;
+        let mut w = vec![StaticModInt::new(0); 2 * (hi2 - lo2)];
+        for (wi, ci) in w.iter_mut().step_by(2).zip(qq_.recip_range(lo2, hi2)) {
+            *wi = ci;
+        }
+        let w: Self = w.into();
+        let qw = &q_neg * &w;
+        (lo..hi).map(|i| qw.get(i - 2 * lo2)).collect()
+    }
+
+    // (p(x) q(-x), q(x) q(-x)); the latter is represented as a polynomial of x^2
+    fn bostan_mori_step(p: &Self, q: &Self) -> (Self, Self) {
+        let d = (p.0.len().max(q.0.len()) + q.0.len()).next_power_of_two();
+        let mut p = p.clone();
+        let mut q = q.clone();
+        p.fft_butterfly(d);
+        q.fft_butterfly(d);
+        let pq_: Vec<_> = (0..d).map(|i| p.get(i) * q.get(i ^ 1)).collect();
+        let qq_: Vec<_> =
+            (0..d).step_by(2).map(|i| q.get(i) * q.get(i + 1)).collect();
+        let (mut pq_, mut qq_): (Self, Self) = (pq_.into(), qq_.into());
+        pq_.fft_inv_butterfly(d);
+        qq_.fft_inv_butterfly(d / 2);
+        (pq_, qq_)
+    }
+
     #[allow(dead_code)]
     fn sparse(&self, thresh: usize) -> Option<Vec<(usize, StaticModInt<M>)>> {
         let nz: Vec<_> = self
@@ -1139,13 +2026,16 @@ impl<'a, M: NttFriendly> DivAssign<&'a Polynomial<M>> for Polynomial<M> {
 }
 
 impl<M: NttFriendly> DivAssign for Polynomial<M> {
-    fn div_assign(&mut self, mut other: Polynomial<M>) {
-        let deg = self.0.len() - other.0.len();
-        self.reverse();
-        other.reverse();
-        *self *= other.recip(deg + 1);
-        self.0.resize(deg + 1, StaticModInt::new(0));
-        self.reverse();
+    fn div_assign(&mut self, other: Polynomial<M>) {
+        if self.0.len() < other.0.len() {
+            self.0.clear();
+            return;
+        }
+        let (n, m) = (self.0.len() - 1, other.0.len() - 1);
+        let deg = n - m;
+        let rf = self.reversed_at(n);
+        let rg = other.reversed_at(m);
+        *self = (rf * rg.recip(deg + 1)).truncated(deg + 1).reversed_at(deg);
     }
 }
 
@@ -1428,6 +2318,300 @@ impl<'a, M: NttFriendly> Shr<usize> for &'a Polynomial<M> {
     fn shr(self, sh: usize) -> Self::Output { self.clone().shr(sh) }
 }
 
+/// 任意 mod の多項式。
+///
+/// 法が NTT に適した素数でない場合のためのもの。畳み込みは
+/// [`convolve_u32_mod`] によって、3 つの NTT 素数での畳み込みの結果を
+/// Garner のアルゴリズムで復元して行う。
+/// 乗算以外の演算は [`Polynomial`] のように butterfly を直接使うことはせず、
+/// 畳み込みのみを用いて実装しているので、畳み込みを差し替えるだけで動く。
+///
+/// 法は [`DynamicModInt`] のものを用いる。
+///
+/// # Examples
+/// ```
+/// use nekolib::math::{ArbitraryPolynomial, DefaultId, DynamicModInt};
+/// type Poly = ArbitraryPolynomial<DefaultId>;
+///
+/// DynamicModInt::<DefaultId>::set_modulus(1_000_000_007);
+/// let f: Poly = [1, 2, 3].into();
+/// let g: Poly = [4, 5].into();
+/// assert_eq!(&f * &g, Poly::from([4, 13, 22, 15]));
+///
+/// let f: Poly = [1, -1].into();
+/// assert_eq!(f.recip(5), Poly::from([1; 5]));
+/// ```
+#[derive(Clone, Eq, PartialEq)]
+pub struct ArbitraryPolynomial<I: DynamicModIntId>(Vec<DynamicModInt<I>>);
+
+impl<I: DynamicModIntId> Debug for ArbitraryPolynomial<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArbitraryPolynomial")
+            .field("f", &self.0.iter().map(|x| x.get()).collect::<Vec<_>>())
+            .field("mod", &DynamicModInt::<I>::modulus())
+            .finish()
+    }
+}
+
+impl<I: DynamicModIntId> ArbitraryPolynomial<I> {
+    /// $f(x) = 0$ を返す。
+    pub fn new() -> Self { Self(vec![]) }
+
+    fn normalize(&mut self) {
+        while let Some(c) = self.0.last() {
+            if c.get() != 0 {
+                break;
+            }
+            self.0.pop();
+        }
+    }
+
+    // 任意 mod での演算はすべてここを経由する。
+    fn convolve(
+        a: &[DynamicModInt<I>],
+        b: &[DynamicModInt<I>],
+    ) -> Vec<DynamicModInt<I>> {
+        let a: Vec<_> = a.iter().map(|x| x.get()).collect();
+        let b: Vec<_> = b.iter().map(|x| x.get()).collect();
+        let m = DynamicModInt::<I>::modulus();
+        convolve_u32_mod(&a, &b, m)
+            .into_iter()
+            .map(DynamicModInt::new)
+            .collect()
+    }
+
+    /// $\[x^i] f(x)$ を返す。
+    pub fn get(&self, i: usize) -> DynamicModInt<I> {
+        self.0.get(i).copied().unwrap_or(DynamicModInt::new(0))
+    }
+
+    /// $f(x) = 0$ であれば `true` を返す。
+    pub fn is_zero(&self) -> bool { self.0.is_empty() }
+
+    /// $(\[x^i] f(x))\_{i=0}^{\\deg(f)}$ を返す。
+    pub fn into_inner(self) -> Vec<DynamicModInt<I>> { self.0 }
+
+    /// $f(x)\\bmod x^n$ を返す。
+    pub fn truncated(mut self, len: usize) -> Self {
+        self.0.truncate(len);
+        self.normalize();
+        self
+    }
+
+    fn ref_truncated(&self, len: usize) -> Self {
+        self.0[..len.min(self.0.len())].to_vec().into()
+    }
+
+    /// $f\'(x)$ を返す。
+    pub fn differential(&self) -> Self {
+        let res: Vec<_> = (1..self.0.len())
+            .map(|i| self.0[i] * DynamicModInt::new(i))
+            .collect();
+        res.into()
+    }
+
+    /// $\\int\_0^x f(t)\\, \\mathrm{d}t$ を返す。
+    ///
+    /// # Requirements
+    /// $1, 2, \\dots, \\deg(f)+1$ が法と互いに素。
+    pub fn integral(&self) -> Self {
+        let res: Vec<_> = Some(DynamicModInt::new(0))
+            .into_iter()
+            .chain(
+                (0..self.0.len())
+                    .map(|i| self.0[i] * DynamicModInt::new(i + 1).recip()),
+            )
+            .collect();
+        res.into()
+    }
+
+    /// $f(x)\\cdot g(x) \\equiv 1\\pmod{x^n}$ なる $g(x) \\bmod x^n$ を返す。
+    ///
+    /// Newton 法 $g \\gets g\\cdot(2-f\\cdot g)$ による。
+    ///
+    /// # Requirements
+    /// $\[x^0] f(x)$ が法と互いに素。
+    ///
+    /// # Complexity
+    /// $O(n\\log(n))$ time.
+    pub fn recip(&self, len: usize) -> Self {
+        if len == 0 {
+            return Self::new();
+        }
+        let mut g = vec![self.get(0).recip()];
+        let mut k = 1;
+        while k < len {
+            k *= 2;
+            let f = &self.0[..self.0.len().min(k)];
+            let mut e = Self::convolve(f, &g);
+            e.truncate(k);
+            for c in &mut e {
+                *c = -*c;
+            }
+            e[0] += DynamicModInt::new(2);
+            g = Self::convolve(&g, &e);
+            g.truncate(k);
+        }
+        Self::from(g).truncated(len)
+    }
+
+    /// $\[x^0] f(x) = 1$ なる $f$ に対し、$\\log(f(x)) \\bmod x^n$ を返す。
+    ///
+    /// [`Polynomial::log`] と同様に $\\int f\'(x)\\cdot f(x)^{-1}\\, \\mathrm{d}x$
+    /// によって求める。
+    ///
+    /// # Requirements
+    /// $1, 2, \\dots, n-1$ が法と互いに素。
+    pub fn log(&self, len: usize) -> Self {
+        assert_eq!(self.get(0).get(), 1);
+        if len == 0 {
+            return Self::new();
+        }
+        let diff = self.differential().truncated(len - 1);
+        (diff * self.recip(len - 1)).truncated(len - 1).integral()
+    }
+
+    /// $\[x^0] f(x) = 0$ なる $f$ に対し、$\\exp(f(x)) \\bmod x^n$ を返す。
+    ///
+    /// Newton 法 $g \\gets g\\cdot(1-\\log(g)+f)$ による。
+    ///
+    /// # Requirements
+    /// $1, 2, \\dots, n-1$ が法と互いに素。
+    pub fn exp(&self, len: usize) -> Self {
+        assert_eq!(self.get(0).get(), 0);
+        if len == 0 {
+            return Self::new();
+        }
+        let mut g = Self::from(vec![DynamicModInt::new(1)]);
+        let mut k = 1;
+        while k < len {
+            k *= 2;
+            let mut e = self.ref_truncated(k) - g.log(k);
+            e += &Self::from(vec![DynamicModInt::new(1)]);
+            g = (g * e).truncated(k);
+        }
+        g.truncated(len)
+    }
+}
+
+impl<I: DynamicModIntId> Default for ArbitraryPolynomial<I> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<I: DynamicModIntId> From<Vec<DynamicModInt<I>>>
+    for ArbitraryPolynomial<I>
+{
+    fn from(buf: Vec<DynamicModInt<I>>) -> Self {
+        let mut res = Self(buf);
+        res.normalize();
+        res
+    }
+}
+
+macro_rules! impl_from_arbitrary {
+    ( $($ty:ty) * ) => { $(
+        impl<I: DynamicModIntId> From<Vec<$ty>> for ArbitraryPolynomial<I> {
+            fn from(buf: Vec<$ty>) -> Self {
+                let mut res =
+                    Self(buf.into_iter().map(DynamicModInt::new).collect());
+                res.normalize();
+                res
+            }
+        }
+        impl<I: DynamicModIntId, const N: usize> From<[$ty; N]>
+            for ArbitraryPolynomial<I>
+        {
+            fn from(buf: [$ty; N]) -> Self {
+                let mut res =
+                    Self(buf.iter().map(|&x| DynamicModInt::new(x)).collect());
+                res.normalize();
+                res
+            }
+        }
+    )* }
+}
+
+impl_from_arbitrary! {
+    i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize
+}
+
+impl<'a, I: DynamicModIntId> AddAssign<&'a ArbitraryPolynomial<I>>
+    for ArbitraryPolynomial<I>
+{
+    fn add_assign(&mut self, other: &'a ArbitraryPolynomial<I>) {
+        let n = self.0.len().max(other.0.len());
+        self.0.resize(n, DynamicModInt::new(0));
+        for (a, &b) in self.0.iter_mut().zip(&other.0) {
+            *a += b;
+        }
+        self.normalize();
+    }
+}
+
+impl<'a, I: DynamicModIntId> SubAssign<&'a ArbitraryPolynomial<I>>
+    for ArbitraryPolynomial<I>
+{
+    fn sub_assign(&mut self, other: &'a ArbitraryPolynomial<I>) {
+        let n = self.0.len().max(other.0.len());
+        self.0.resize(n, DynamicModInt::new(0));
+        for (a, &b) in self.0.iter_mut().zip(&other.0) {
+            *a -= b;
+        }
+        self.normalize();
+    }
+}
+
+impl<'a, I: DynamicModIntId> MulAssign<&'a ArbitraryPolynomial<I>>
+    for ArbitraryPolynomial<I>
+{
+    fn mul_assign(&mut self, other: &'a ArbitraryPolynomial<I>) {
+        self.0 = Self::convolve(&self.0, &other.0);
+        self.normalize();
+    }
+}
+
+macro_rules! impl_binop_arbitrary {
+    ( $( ($op:ident, $op_assign:ident, $op_trait:ident, $op_assign_trait:ident), )* ) => {
+        $(
+            impl<I: DynamicModIntId> $op_assign_trait for ArbitraryPolynomial<I> {
+                fn $op_assign(&mut self, other: ArbitraryPolynomial<I>) {
+                    self.$op_assign(&other);
+                }
+            }
+            impl<I: DynamicModIntId> $op_trait for ArbitraryPolynomial<I> {
+                type Output = ArbitraryPolynomial<I>;
+                fn $op(mut self, other: ArbitraryPolynomial<I>) -> Self::Output {
+                    self.$op_assign(&other);
+                    self
+                }
+            }
+            impl<'a, I: DynamicModIntId> $op_trait<&'a ArbitraryPolynomial<I>>
+                for ArbitraryPolynomial<I>
+            {
+                type Output = ArbitraryPolynomial<I>;
+                fn $op(mut self, other: &'a ArbitraryPolynomial<I>) -> Self::Output {
+                    self.$op_assign(other);
+                    self
+                }
+            }
+            impl<'a, I: DynamicModIntId> $op_trait<&'a ArbitraryPolynomial<I>>
+                for &'a ArbitraryPolynomial<I>
+            {
+                type Output = ArbitraryPolynomial<I>;
+                fn $op(self, other: &'a ArbitraryPolynomial<I>) -> Self::Output {
+                    self.clone().$op(other)
+                }
+            }
+        )*
+    }
+}
+
+impl_binop_arbitrary! {
+    (add, add_assign, Add, AddAssign),
+    (sub, sub_assign, Sub, SubAssign),
+    (mul, mul_assign, Mul, MulAssign),
+}
+
 #[test]
 fn sanity_check() {
     type Poly = Polynomial<modint::Mod998244353>;
@@ -1509,6 +2693,38 @@ fn recip() {
     }
 }
 
+#[test]
+fn recip_from() {
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let f: Poly = (1..=30).map(|i| i * i % 17).collect::<Vec<_>>().into();
+    let n = 100;
+    let g = f.recip(n);
+    for k in 0..=n + 5 {
+        let seed = g.ref_truncated(k);
+        for len in 0..=n {
+            assert_eq!(f.recip_from(&seed, len), g.ref_truncated(len));
+        }
+    }
+
+    // g(x) mod x^k の末尾が 0 の場合
+    let f: Poly = [1, 0, 0, 0, 1].into();
+    let g = f.recip(20);
+    assert_eq!(g.ref_truncated(4), Poly::from([1]));
+    assert_eq!(f.recip_from(&g.ref_truncated(4), 20), g);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "`seed` must be a prefix of the reciprocal")]
+fn recip_from_wrong_seed() {
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let f: Poly = [1, -1].into();
+    let seed: Poly = [1, 1, 2].into();
+    f.recip_from(&seed, 10);
+}
+
 #[test]
 fn pow() {
     type Poly = Polynomial<modint::Mod998244353>;
@@ -1526,6 +2742,349 @@ fn pow() {
     }
 }
 
+#[test]
+fn pow_frac() {
+    type Poly = Polynomial<modint::Mod998244353>;
+    type Mi = modint::ModInt998244353;
+
+    let f: Poly = [1, 3, 0, 2, 1, 5].into();
+    let half = Mi::new(2).recip();
+    for len in 0..50 {
+        let g = f.pow_frac(half, len);
+        assert_eq!(g.get(0).get(), if len == 0 { 0 } else { 1 });
+        assert_eq!((&g * &g).truncated(len), f.ref_truncated(len));
+
+        assert_eq!(f.pow_frac(Mi::new(-1), len), f.recip(len));
+        for k in 0..=5 {
+            assert_eq!(f.pow_frac(Mi::new(k), len), f.pow(k, len));
+        }
+    }
+
+    let zero = Poly::new();
+    assert_eq!(zero.pow_frac(Mi::new(0), 3), Poly::from([1]));
+    assert_eq!(zero.pow_frac(half, 3), Poly::new());
+}
+
+#[test]
+fn sqrt() {
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let mut f =
+        std::iter::successors(Some(296_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    for len in 0..40 {
+        for _ in 0..10 {
+            let f: Poly =
+                (0..len).map(|_| rand() % 998244353).collect::<Vec<_>>().into();
+            let g = f.sqrt(len);
+            let res = f.get(0).pow((998244353 - 1) / 2).get() <= 1;
+            assert_eq!(g.is_some(), res, "sqrt({})", f);
+            if let Some(g) = g {
+                assert_eq!((&g * &g).truncated(len), f);
+            }
+
+            let g2 = (&f * &f).truncated(len);
+            let g = g2.sqrt(len).unwrap();
+            assert_eq!((&g * &g).truncated(len), g2);
+            assert!(g == f || g == -&f);
+        }
+    }
+
+    // 先頭が 0 の場合
+    for l in 0..6 {
+        let f: Poly = [1, 3, 0, 2, 1, 5].into();
+        let f = f << l;
+        for len in 0..20 {
+            let g = f.sqrt(len);
+            if l % 2 != 0 && l < len {
+                assert_eq!(g, None);
+            } else {
+                let g = g.unwrap();
+                assert_eq!((&g * &g).truncated(len), f.ref_truncated(len));
+            }
+        }
+    }
+
+    assert_eq!(Poly::new().sqrt(5), Some(Poly::new()));
+    assert_eq!(Poly::from([0, 0, 0, 1]).sqrt(3), Some(Poly::new()));
+}
+
+#[test]
+fn eval_multi() {
+    type Poly = Polynomial<modint::Mod998244353>;
+    type Mi = modint::ModInt998244353;
+
+    let mut f =
+        std::iter::successors(Some(185_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    for n in 0..20 {
+        let f: Poly =
+            (0..n).map(|_| rand() % 998244353).collect::<Vec<_>>().into();
+        for m in 0..20 {
+            // 重複する点も含める
+            let xs: Vec<_> = (0..m).map(|_| Mi::new(rand() % 8)).collect();
+            let expected: Vec<_> = xs.iter().map(|&x| f.eval(x)).collect();
+            assert_eq!(f.eval_multi(&xs), expected);
+        }
+    }
+
+    let f: Poly = (1..=1000).collect::<Vec<_>>().into();
+    let xs: Vec<_> = (0..300).map(|_| Mi::new(rand())).collect();
+    let expected: Vec<_> = xs.iter().map(|&x| f.eval(x)).collect();
+    assert_eq!(f.eval_multi(&xs), expected);
+    assert!(f.eval_multi(&[]).is_empty());
+}
+
+#[test]
+fn from_points() {
+    type Poly = Polynomial<modint::Mod998244353>;
+    type Mi = modint::ModInt998244353;
+
+    let mut f =
+        std::iter::successors(Some(748_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    assert_eq!(Poly::from_points(&[]), Poly::new());
+
+    for n in 1..40 {
+        let mut xs: Vec<_> = vec![];
+        while xs.len() < n {
+            let x = Mi::new(rand() % 100);
+            if !xs.contains(&x) {
+                xs.push(x);
+            }
+        }
+        let ys: Vec<_> = (0..n).map(|_| Mi::new(rand())).collect();
+        let points: Vec<_> =
+            xs.iter().copied().zip(ys.iter().copied()).collect();
+        let f = Poly::from_points(&points);
+        assert!(f.len() <= n);
+        assert_eq!(f.eval_multi(&xs), ys);
+
+        let g: Poly =
+            (0..n).map(|_| rand() % 998244353).collect::<Vec<_>>().into();
+        let points: Vec<_> =
+            xs.iter().copied().zip(g.eval_multi(&xs)).collect();
+        assert_eq!(Poly::from_points(&points), g);
+
+        let iota: Vec<_> = (0..n).map(|i| (Mi::new(i), ys[i])).collect();
+        let f = Poly::from_points(&iota);
+        for t in (0..n + 5).map(Mi::new).chain((0..5).map(|_| Mi::new(rand())))
+        {
+            assert_eq!(Poly::interpolate_iota(&ys, t), f.eval(t));
+        }
+    }
+    assert_eq!(Poly::interpolate_iota(&[], Mi::new(3)).get(), 0);
+}
+
+#[test]
+#[should_panic(expected = "`x` must be distinct")]
+fn from_points_dup() {
+    type Poly = Polynomial<modint::Mod998244353>;
+    type Mi = modint::ModInt998244353;
+
+    let points: Vec<_> = [(1, 2), (3, 4), (1, 5)]
+        .iter()
+        .map(|&(x, y)| (Mi::new(x), Mi::new(y)))
+        .collect();
+    Poly::from_points(&points);
+}
+
+#[test]
+fn compose() {
+    type Poly = Polynomial<modint::Mod998244353>;
+    type Mi = modint::ModInt998244353;
+
+    let mut f =
+        std::iter::successors(Some(397_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    let naive = |f: &Poly, g: &Poly, len: usize| {
+        let mut res = Poly::new();
+        for i in (0..f.len()).rev() {
+            res = (res * g + Poly::from(vec![f.get(i)])).truncated(len);
+        }
+        res
+    };
+
+    let x: Poly = [0, 1].into();
+    for len in 0..30 {
+        for flen in 0..35 {
+            let f: Poly = (0..flen)
+                .map(|_| rand() % 998244353)
+                .collect::<Vec<_>>()
+                .into();
+            assert_eq!(f.compose(&x, len), f.ref_truncated(len));
+
+            for &c in &[0, 1, 5] {
+                let g: Poly = (0..rand() % 35)
+                    .map(|i| if i == 0 { c } else { rand() % 998244353 })
+                    .collect::<Vec<_>>()
+                    .into();
+                assert_eq!(f.compose(&g, len), naive(&f, &g, len));
+            }
+        }
+    }
+
+    // exp(g) と log(1+g)
+    let n = 50;
+    let e: Poly = (0..n)
+        .scan(Mi::new(1), |fact, i| {
+            if i > 0 {
+                *fact *= Mi::new(i);
+            }
+            Some(fact.recip())
+        })
+        .collect::<Vec<_>>()
+        .into();
+    let l: Poly = (0..n)
+        .map(|i| match i {
+            0 => Mi::new(0),
+            _ if i % 2 == 1 => Mi::new(i).recip(),
+            _ => -Mi::new(i).recip(),
+        })
+        .collect::<Vec<_>>()
+        .into();
+    let g: Poly = (0..n).map(|i| i * i % 7).collect::<Vec<_>>().into();
+    assert_eq!(e.compose(&g, n), g.exp(n));
+    assert_eq!(l.compose(&g, n), (&g + Poly::from([1])).log(n));
+}
+
+#[test]
+fn taylor_shift() {
+    type Poly = Polynomial<modint::Mod998244353>;
+    type Mi = modint::ModInt998244353;
+
+    let mut f =
+        std::iter::successors(Some(258_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+
+    for n in 0..40 {
+        let f: Poly =
+            (0..n).map(|_| rand() % 998244353).collect::<Vec<_>>().into();
+        assert_eq!(f.taylor_shift(0), f);
+        for _ in 0..5 {
+            let c = Mi::new(rand());
+            let g = f.taylor_shift(c);
+            assert_eq!(g.taylor_shift(-c), f);
+            for _ in 0..5 {
+                let t = Mi::new(rand());
+                assert_eq!(f.eval(t), g.eval(t - c));
+            }
+        }
+    }
+}
+
+#[test]
+fn gcd() {
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let mut f =
+        std::iter::successors(Some(291_u64), |&x| Some(x * 48271 % 2147483647));
+    let mut rand = || f.next().unwrap();
+    let mut rand_poly = move |n: u64| -> Poly {
+        (0..n).map(|_| rand() % 998244353).collect::<Vec<_>>().into()
+    };
+
+    for _ in 0..100 {
+        let h = rand_poly(5);
+        let h = h.clone() / h.get(h.len() - 1);
+        let f = &h * rand_poly(10);
+        let g = &h * rand_poly(7);
+        // ランダムな多項式同士はほぼ互いに素
+        let d = f.gcd(&g);
+        assert_eq!(d, h);
+        assert_eq!(g.gcd(&f), h);
+        assert!((&f % &d).is_zero());
+        assert!((&g % &d).is_zero());
+
+        assert_eq!(f.gcd(&Poly::new()), f.gcd(&f));
+        assert_eq!(Poly::new().gcd(&g), g.gcd(&g));
+        assert_eq!(f.gcd(&Poly::from([3])), Poly::from([1]));
+    }
+    assert_eq!(Poly::new().gcd(&Poly::new()), Poly::new());
+}
+
+#[test]
+fn prefix_sum_difference() {
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let fs: Vec<Poly> = vec![
+        Poly::new(),
+        [1].into(),
+        [0, 0, 5].into(),
+        [3, 1, 4, 1, 5, 9, 2, 6].into(),
+        [-1, 2, -3, 4].into(),
+    ];
+    let one_minus_x: Poly = [1, -1].into();
+    for f in &fs {
+        assert_eq!(f.difference(), f * &one_minus_x);
+        for len in 0..12 {
+            let g = f.prefix_sum(len);
+            assert_eq!(g, (f * one_minus_x.recip(len)).truncated(len));
+            assert_eq!(g.difference().truncated(len), f.ref_truncated(len));
+        }
+        assert_eq!(f.prefix_sum(f.len()).difference().truncated(f.len()), *f);
+    }
+}
+
+#[test]
+fn mod_cyclic() {
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let f: Poly = (1..=20).collect::<Vec<_>>().into();
+    for m in 1..=25 {
+        let x_m_1 = (Poly::from([1]) << m) - Poly::from([1]);
+        assert_eq!(f.mod_cyclic(m), &f % &x_m_1);
+    }
+    assert!(Poly::new().mod_cyclic(3).is_zero());
+    assert!(Poly::from([1, 2, -1, -2]).mod_cyclic(2).is_zero());
+
+    let a = [3, 1, 4, 1, 5, 9, 2];
+    let b = [2, 7, 1, 8, 2, 8, 1];
+    let m = a.len();
+    let naive: Vec<_> = (0..m)
+        .map(|k| (0..m).map(|i| a[i] * b[(m + k - i) % m]).sum::<i32>())
+        .collect();
+    let (fa, fb): (Poly, Poly) = (a.into(), b.into());
+    assert_eq!((&fa * &fb).mod_cyclic(m), Poly::from(naive));
+}
+
+#[test]
+fn hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let hash = |f: &Poly| {
+        let mut hasher = DefaultHasher::new();
+        f.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    let f: Poly = [1, 2, 3].into();
+    let g = Poly::from([1, 2, 3, 4]) - Poly::from([0, 0, 0, 4]);
+    assert_eq!(f, g);
+    assert_eq!(hash(&f), hash(&g));
+    assert_eq!(hash(&Poly::new()), hash(&Poly::from([0, 0])));
+
+    // (1+x)^k を二通りで計算して同じキーとみなされることを確かめる
+    let x1: Poly = [1, 1].into();
+    let mut memo = HashMap::new();
+    let mut cur = Poly::from([1]);
+    for k in 0..10 {
+        memo.insert(cur.clone(), k);
+        cur *= &x1;
+    }
+    for k in 0..10 {
+        assert_eq!(memo.get(&x1.pow(k, 20)), Some(&k));
+    }
+    assert_eq!(memo.get(&x1.pow(10, 20)), None);
+}
+
 #[test]
 fn polyeqn() {
     type Poly = Polynomial<modint::Mod998244353>;
@@ -1605,6 +3164,57 @@ fn fibonacci() {
     let actual: Poly = actual.into();
 
     assert_eq!(actual, expected);
+
+    let n = 60;
+    let expected = (&p * q.recip(n)).truncated(n);
+    for i in 0..n {
+        for k in 0..n - i {
+            let actual = p.div_nth_range(&q, i, k);
+            let expected: Vec<_> =
+                (i..i + k).map(|i| expected.get(i)).collect();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    let i = 1_000_000_000_000;
+    let actual = p.div_nth_range(&q, i, 10);
+    let expected: Vec<_> = (i..i + 10).map(|i| p.div_nth(&q, i)).collect();
+    assert_eq!(actual, expected);
+
+    // 分子の次数が分母以上の場合
+    let p: Poly = [3, 1, 4, 1, 5, 9, 2, 6].into();
+    let n = 20;
+    let expected = (&p * q.recip(n)).truncated(n);
+    for i in 0..n {
+        let actual = p.div_nth_range(&q, i, n - i);
+        let expected: Vec<_> = (i..n).map(|i| expected.get(i)).collect();
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn div_nth_range() {
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let mut it =
+        std::iter::successors(Some(296), |&x| Some((x * 258 + 185) % 397));
+    for &(m, d) in &[(1, 1), (3, 1), (5, 5), (10, 3), (3, 10)] {
+        let p: Poly = it.by_ref().take(m).collect::<Vec<_>>().into();
+        let mut q: Vec<_> = it.by_ref().take(d).collect();
+        q[0] = 3;
+        let q: Poly = q.into();
+
+        let n = 300;
+        let expected = (&p * q.recip(n)).truncated(n);
+        for i in (0..n).step_by(7) {
+            for k in (0..n - i).step_by(11) {
+                let actual = p.div_nth_range(&q, i, k);
+                let expected: Vec<_> =
+                    (i..i + k).map(|i| expected.get(i)).collect();
+                assert_eq!(actual, expected);
+            }
+        }
+    }
 }
 
 #[test]
@@ -1667,3 +3277,146 @@ fn sin_cos() {
     assert_eq!(z.0, (&cos * &cos2 - &sin * &sin2).truncated(n));
     assert_eq!(z.1, (&sin * &cos2 + &cos * &sin2).truncated(n));
 }
+
+#[test]
+fn reversed_at() {
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let f: Poly = [0, 0, 1, 2, 3].into();
+    for deg in 4..10 {
+        let g = f.reversed_at(deg);
+        assert_eq!(g.len(), deg - 1);
+        assert_eq!(g.reversed_at(deg), f);
+    }
+    assert_eq!(f.reversed_at(2), Poly::from([1]));
+    assert_eq!(f.reversed_at(3), Poly::from([2, 1]));
+    assert_eq!(Poly::new().reversed_at(3), Poly::new());
+}
+
+#[test]
+fn div_mod() {
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let fs: Vec<Poly> = vec![
+        [1].into(),
+        [0, 1].into(),
+        [0, 0, 0, 1].into(),
+        [3, 1, 4, 1, 5, 9, 2, 6].into(),
+        [0, 0, 2, 7, 1, 8, 0, 0, 0, 2].into(),
+    ];
+    for f in &fs {
+        for g in &fs {
+            let (q, r) = f.div_mod(g);
+            assert_eq!(&q * g + &r, *f);
+            assert!(r.len() < g.len());
+            if f.len() < g.len() {
+                assert!(q.is_zero());
+            } else {
+                assert_eq!(q.len(), f.len() - g.len() + 1);
+            }
+        }
+    }
+}
+
+#[test]
+fn is_integral_upto() {
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    assert!(Poly::new().is_integral_upto(0));
+
+    // (1 + x)^10
+    let f = Poly::from([1, 1]).pow(10, 11);
+    assert!(f.is_integral_upto(253));
+    assert!(!f.is_integral_upto(252));
+
+    // sum_i x^i / i!
+    let g = Poly::from([0, 1]).exp(10);
+    assert_eq!(g.get(0).get(), 1);
+    assert_eq!(g.get(1).get(), 1);
+    assert!(!g.is_integral_upto(1 << 20));
+    assert!(!g.ref_truncated(3).is_integral_upto(1 << 20));
+    assert!(g.ref_truncated(2).is_integral_upto(2));
+}
+
+#[test]
+fn eval_all_at() {
+    type Mi = modint::ModInt998244353;
+    type Poly = Polynomial<modint::Mod998244353>;
+
+    let fs: Vec<Poly> = vec![
+        [1, 2, 3].into(),
+        Poly::new(),
+        [5].into(),
+        Poly::from([1, 1]).pow(20, 21),
+        Poly::from([0, 1]).exp(30),
+        [0, 0, 0, 0, 7].into(),
+    ];
+    for t in [0, 1, 2, 3, 998244352] {
+        let t = Mi::new(t);
+        let expected: Vec<_> = fs.iter().map(|f| f.eval(t)).collect();
+        assert_eq!(Poly::eval_all_at(&fs, t), expected);
+    }
+    assert!(Poly::eval_all_at(&[], Mi::new(1)).is_empty());
+}
+
+#[cfg(test)]
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Mod1000000007Id {}
+
+#[cfg(test)]
+impl DynamicModIntId for Mod1000000007Id {
+    fn barrett() -> &'static modint::Barrett {
+        static BARRETT: modint::Barrett = modint::Barrett::new(1_000_000_007);
+        &BARRETT
+    }
+}
+
+#[test]
+fn arbitrary() {
+    type Poly = ArbitraryPolynomial<Mod1000000007Id>;
+    type Mi = DynamicModInt<Mod1000000007Id>;
+
+    let mut f = std::iter::successors(Some(93739_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = || f.next().unwrap();
+
+    for n in 0..30 {
+        for m in 0..30 {
+            let f: Vec<_> = (0..n).map(|_| Mi::new(rand())).collect();
+            let g: Vec<_> = (0..m).map(|_| Mi::new(rand())).collect();
+            let mut expected = vec![Mi::new(0); (n + m).max(1) - 1];
+            for i in 0..n {
+                for j in 0..m {
+                    expected[i + j] += f[i] * g[j];
+                }
+            }
+            assert_eq!(Poly::from(f) * Poly::from(g), Poly::from(expected));
+        }
+    }
+
+    let n = 100;
+    let f: Poly = (0..n)
+        .map(|i| if i == 0 { 1 } else { rand() })
+        .collect::<Vec<_>>()
+        .into();
+    assert_eq!((&f * &f.recip(n)).truncated(n), Poly::from([1]));
+    assert_eq!(f.log(n).exp(n), f);
+
+    // exp(x) = sum_i x^i / i!
+    let e = Poly::from([0, 1]).exp(n);
+    let mut fact = Mi::new(1);
+    for i in 0..n {
+        if i > 0 {
+            fact *= Mi::new(i);
+        }
+        assert_eq!(e.get(i) * fact, Mi::new(1));
+    }
+
+    // log(1/(1-x)) = sum_{i>0} x^i / i
+    let l = Poly::from([1; 100]).log(n);
+    assert_eq!(l.get(0).get(), 0);
+    for i in 1..n {
+        assert_eq!(l.get(i) * Mi::new(i), Mi::new(1));
+    }
+}
@@ -2,8 +2,9 @@
 
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
+use std::convert::TryFrom;
 use std::fmt::Debug;
-use std::ops::{Add, AddAssign, Bound, RangeInclusive};
+use std::ops::{Add, AddAssign, Bound, Neg, RangeInclusive, Sub};
 
 /// 区分線形凸関数。
 ///
@@ -21,9 +22,19 @@ use std::ops::{Add, AddAssign, Bound, RangeInclusive};
 /// |`new`|$O(1)$|
 /// |`add_const`|$O(1)$|
 /// |`add_left`, `add_right`, `add_abs`|$O(\\log(\|L\|) + \\log(\|R\|))$|
+/// |`add_left_slope`, `add_right_slope`|$O((m+1)(\\log(\|L\|) + \\log(\|R\|)))$|
 /// |`min_left`, `min_right`|amortized $O(1)$|
 /// |`shift`, `window`|$O(1)$|
 /// |`min`, `argmin`|$O(1)$|
+/// |`eval`|$O(\|L\| + \|R\|)$|
+/// |`x_at_slope`|$O((\|L\| + \|R\|)\\log(\|L\| + \|R\|))$|
+/// |`+=`|$O((k+m)\\log(n))$|
+///
+/// $L$, $R$ は値とその個数の組で持つため、ここでの $\|L\|$, $\|R\|$
+/// は相異なる組の個数とする。`add_left_slope`, `add_right_slope`
+/// の $m$ は、もう一方の多重集合に移される組の個数である。
+/// `+=` について、$k$ と $n$ はそれぞれ二つの関数の $\|L\|+\|R\|$ の小さい方と大きい方とし、
+/// $m$ は内部で呼ぶ `add_left_slope`, `add_right_slope` で移される組の個数の合計とする。
 ///
 /// # Examples
 /// ```
@@ -41,6 +52,18 @@ use std::ops::{Add, AddAssign, Bound, RangeInclusive};
 /// // f(x) |  2  0 -2 -4 -6 -7 -8 -9 -9 -8 -7
 /// assert_eq!(sf.min(), -9);
 /// assert_eq!(sf.argmin(), (Included(2), Included(3)));
+///
+/// // 別々に構築したものを足す
+/// let mut g = SlopeFunction::new();
+/// g.add_abs(0);
+/// g.shift(-2);
+/// // g(x) = |x+2|
+/// sf += g;
+/// //      x      | -5 -4 -3 -2 -1  0  1  2  3  4  5
+/// // f(x) + g(x) |  5  2 -1 -4 -5 -5 -5 -5 -4 -2  0
+/// assert_eq!(sf.min(), -5);
+/// assert_eq!(sf.argmin(), (Included(-1), Included(2)));
+/// assert_eq!(sf.eval(-5), 5);
 /// ```
 ///
 /// # Notes
@@ -84,8 +107,8 @@ use std::ops::{Add, AddAssign, Bound, RangeInclusive};
 /// - <https://maspypy.com/slope-trick-1-%E8%A7%A3%E8%AA%AC%E7%B7%A8>
 #[derive(Clone, Debug, Default)]
 pub struct SlopeFunction<I: Ord> {
-    left: BinaryHeap<I>,
-    right: BinaryHeap<Reverse<I>>,
+    left: BinaryHeap<(I, usize)>,
+    right: BinaryHeap<Reverse<(I, usize)>>,
     min: I,
     shl: I,
     shr: I,
@@ -131,15 +154,54 @@ impl<I: SlopeTrickInt> SlopeFunction<I> {
     /// sf.add_left(4);
     /// assert_eq!(sf.argmin(), (Included(4), Unbounded));
     /// ```
-    pub fn add_left(&mut self, l: I) {
+    pub fn add_left(&mut self, l: I) { self.add_left_slope(l, 1); }
+    /// $f(x) \\xleftarrow{+} k\\cdot (l-x)\_+$ で更新する。
+    ///
+    /// `add_left(l)` を $k$ 回行うのと同じだが、$L$ には $l$ を個数つきで入れる。
+    ///
+    /// # Examples
+    /// ```
+    /// use std::ops::Bound::{Included, Unbounded};
+    ///
+    /// use nekolib::math::SlopeFunction;
+    ///
+    /// let mut sf = SlopeFunction::new();
+    /// sf.add_right_slope(0, 2);
+    /// sf.add_left_slope(4, 3);
+    /// // f(x) = 2 * 0.max(x-0) + 3 * 0.max(4-x)
+    /// //   x  | -1  0  1  2  3  4  5
+    /// // f(x) | 15 12 11 10  9  8 10
+    /// assert_eq!(sf.min(), 8);
+    /// assert_eq!(sf.argmin(), (Included(4), Included(4)));
+    /// assert_eq!(sf.x_at_slope(-1), (Included(0), Included(4)));
+    /// assert_eq!(sf.x_at_slope(-3), (Unbounded, Included(0)));
+    ///
+    /// let mut sf = SlopeFunction::<i64>::new();
+    /// sf.add_left_slope(0, 1 << 40);
+    /// assert_eq!(sf.eval(-3), 3 << 40);
+    /// ```
+    pub fn add_left_slope(&mut self, l: I, k: usize) {
+        if k == 0 {
+            return;
+        }
         if self.right.is_empty() {
-            self.left.push(l);
+            self.left.push((l - self.shl, k));
             return;
         }
-        self.min += l.doz(self.right.peek().unwrap().0);
-        self.right.push(Reverse(l));
-        let l = self.right.pop().unwrap().0;
-        self.left.push(l);
+        // R に l を k 個入れてから、小さい方の k 個を L に移す。
+        self.right.push(Reverse((l - self.shr, k)));
+        let mut rem = k;
+        while rem > 0 {
+            let Reverse((r, c)) = self.right.pop().unwrap();
+            let r = r + self.shr;
+            let t = c.min(rem);
+            self.min += l.doz(r).mul_count(t);
+            self.left.push((r - self.shl, t));
+            if t < c {
+                self.right.push(Reverse((r - self.shr, c - t)));
+            }
+            rem -= t;
+        }
     }
     /// $f(x) \\xleftarrow{+} (x-r)\_+$ で更新する。
     ///
@@ -153,15 +215,49 @@ impl<I: SlopeTrickInt> SlopeFunction<I> {
     /// sf.add_right(4);
     /// assert_eq!(sf.argmin(), (Unbounded, Included(4)));
     /// ```
-    pub fn add_right(&mut self, r: I) {
+    pub fn add_right(&mut self, r: I) { self.add_right_slope(r, 1); }
+    /// $f(x) \\xleftarrow{+} k\\cdot (x-r)\_+$ で更新する。
+    ///
+    /// `add_right(r)` を $k$ 回行うのと同じだが、$R$ には $r$ を個数つきで入れる。
+    ///
+    /// # Examples
+    /// ```
+    /// use std::ops::Bound::{Included, Unbounded};
+    ///
+    /// use nekolib::math::SlopeFunction;
+    ///
+    /// let mut sf = SlopeFunction::new();
+    /// sf.add_left_slope(0, 2);
+    /// sf.add_right_slope(-4, 3);
+    /// // f(x) = 2 * 0.max(0-x) + 3 * 0.max(x+4)
+    /// //   x  | -5 -4 -3 -2 -1  0  1
+    /// // f(x) | 10  8  9 10 11 12 15
+    /// assert_eq!(sf.min(), 8);
+    /// assert_eq!(sf.argmin(), (Included(-4), Included(-4)));
+    /// assert_eq!(sf.x_at_slope(3), (Included(0), Unbounded));
+    /// ```
+    pub fn add_right_slope(&mut self, r: I, k: usize) {
+        if k == 0 {
+            return;
+        }
         if self.left.is_empty() {
-            self.right.push(Reverse(r));
+            self.right.push(Reverse((r - self.shr, k)));
             return;
         }
-        self.min += self.left.peek().unwrap().doz(r);
-        self.left.push(r);
-        let r = self.left.pop().unwrap();
-        self.right.push(Reverse(r));
+        // L に r を k 個入れてから、大きい方の k 個を R に移す。
+        self.left.push((r - self.shl, k));
+        let mut rem = k;
+        while rem > 0 {
+            let (l, c) = self.left.pop().unwrap();
+            let l = l + self.shl;
+            let t = c.min(rem);
+            self.min += l.doz(r).mul_count(t);
+            self.right.push(Reverse((l - self.shr, t)));
+            if t < c {
+                self.left.push((l - self.shl, c - t));
+            }
+            rem -= t;
+        }
     }
     /// $f(x) \\xleftarrow{+} |x-a|$ で更新する。
     ///
@@ -271,22 +367,299 @@ impl<I: SlopeTrickInt> SlopeFunction<I> {
     /// ```
     pub fn argmin(&self) -> (Bound<I>, Bound<I>) {
         let left = match self.left.peek() {
-            Some(&x) => Bound::Included(x + self.shl),
+            Some(&(x, _)) => Bound::Included(x + self.shl),
             None => Bound::Unbounded,
         };
         let right = match self.right.peek() {
-            Some(&Reverse(x)) => Bound::Included(x + self.shr),
+            Some(&Reverse((x, _))) => Bound::Included(x + self.shr),
             None => Bound::Unbounded,
         };
         (left, right)
     }
+    /// $\\argmin\_{x\\in\\mathbb{R}} f(x)$ に含まれる有限な値を一つ返す。
+    ///
+    /// [`argmin`] の左端が有限であればそれを、そうでなく右端が有限であればそれを返す。
+    /// 両端とも有限でない、すなわち $f$ が定数関数のときは `None` を返す。
+    ///
+    /// [`argmin`]: #method.argmin
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::math::SlopeFunction;
+    ///
+    /// let mut sf = SlopeFunction::new();
+    /// assert_eq!(sf.any_argmin(), None);
+    ///
+    /// sf.add_right(3);
+    /// assert_eq!(sf.any_argmin(), Some(3));
+    ///
+    /// // 中央値の一つ
+    /// let mut sf = SlopeFunction::new();
+    /// for &a in &[5, 1, 4, 2] {
+    ///     sf.add_abs(a);
+    /// }
+    /// assert_eq!(sf.any_argmin(), Some(2));
+    /// ```
+    pub fn any_argmin(&self) -> Option<I> {
+        match self.argmin() {
+            (Bound::Included(x), _) | (_, Bound::Included(x)) => Some(x),
+            _ => None,
+        }
+    }
+    /// $f(x)$ を返す。
+    ///
+    /// $\\argmin f$ の左端より左では $L$ の各要素 $l$ が $(l-x)\_+$ を、
+    /// 右端より右では $R$ の各要素 $r$ が $(x-r)\_+$ を積み上げる。
+    /// これらを [`min`] に足したものを返す。
+    ///
+    /// [`min`]: #method.min
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::math::SlopeFunction;
+    ///
+    /// let mut sf = SlopeFunction::new();
+    /// sf.add_left(-1);
+    /// sf.add_left(3);
+    /// sf.add_right(2);
+    /// //   x  | -5 -4 -3 -2 -1  0  1  2  3  4  5
+    /// // f(x) | 12 10  8  6  4  3  2  1  1  2  3
+    /// assert_eq!(sf.eval(-5), 12);
+    /// assert_eq!(sf.eval(0), 3);
+    /// assert_eq!(sf.eval(3), 1);
+    /// assert_eq!(sf.eval(5), 3);
+    ///
+    /// sf.window(-1..=2);
+    /// // g(x) = min {f(y) | x-2 <= y <= x+1}
+    /// //   x  | -5 -4 -3 -2 -1  0  1  2  3  4  5  6  7
+    /// // g(x) | 10  8  6  4  3  2  1  1  1  1  1  2  3
+    /// assert_eq!(sf.eval(-5), 10);
+    /// assert_eq!(sf.eval(1), 1);
+    /// assert_eq!(sf.eval(6), 2);
+    /// ```
+    pub fn eval(&self, x: I) -> I {
+        let mut res = self.min;
+        for &(l, c) in &self.left {
+            res += (l + self.shl).doz(x).mul_count(c);
+        }
+        for &Reverse((r, c)) in &self.right {
+            res += x.doz(r + self.shr).mul_count(c);
+        }
+        res
+    }
+    /// $\\{x\\mid k\\in\\partial f(x)\\}$ を返す。
+    ///
+    /// $\\partial f(x)$ は $f$ の $x$ における劣微分であり、
+    /// $k = 0$ のときは [`argmin`] と一致する。
+    /// $k \\gt 0$ のときは $R$ の小さい方から $k$ 番目と $k+1$ 番目の値、
+    /// $k \\lt 0$ のときは $L$ の大きい方から $-k+1$ 番目と $-k$ 番目の値が両端となる。
+    ///
+    /// 言い換えると、$f(x) - kx$ を最小にする $x$ の範囲を返す。
+    ///
+    /// [`argmin`]: #method.argmin
+    ///
+    /// # Panics
+    /// $k \\lt -\|L\|$ または $k \\gt \|R\|$ のとき、すなわち傾きが $k$
+    /// となる点が存在しないとき。
+    ///
+    /// # Examples
+    /// ```
+    /// use std::ops::Bound::{Included, Unbounded};
+    ///
+    /// use nekolib::math::SlopeFunction;
+    ///
+    /// let mut sf = SlopeFunction::new();
+    /// sf.add_left(-1);
+    /// sf.add_left(3);
+    /// sf.add_right(2);
+    /// //   x  | -5 -4 -3 -2 -1  0  1  2  3  4  5
+    /// // f(x) | 12 10  8  6  4  3  2  1  1  2  3
+    /// assert_eq!(sf.x_at_slope(-2), (Unbounded, Included(-1)));
+    /// assert_eq!(sf.x_at_slope(-1), (Included(-1), Included(2)));
+    /// assert_eq!(sf.x_at_slope(0), (Included(2), Included(3)));
+    /// assert_eq!(sf.x_at_slope(0), sf.argmin());
+    /// assert_eq!(sf.x_at_slope(1), (Included(3), Unbounded));
+    ///
+    /// sf.shift(10);
+    /// assert_eq!(sf.x_at_slope(-1), (Included(9), Included(12)));
+    /// assert_eq!(sf.x_at_slope(0), sf.argmin());
+    /// ```
+    pub fn x_at_slope(&self, k: i64) -> (Bound<I>, Bound<I>) {
+        if k == 0 {
+            return self.argmin();
+        }
+        let j = k.unsigned_abs() as usize;
+        if k < 0 {
+            let left: Vec<_> =
+                self.left.iter().map(|&(x, c)| (Reverse(x), c)).collect();
+            let len: usize = left.iter().map(|&(_, c)| c).sum();
+            assert!(j <= len, "no point has slope {}", k);
+            let (Reverse(hi), lo) = nth_and_next(left, j);
+            let lo = match lo {
+                Some(Reverse(x)) => Bound::Included(x + self.shl),
+                None => Bound::Unbounded,
+            };
+            (lo, Bound::Included(hi + self.shl))
+        } else {
+            let right: Vec<_> = self.right.iter().map(|x| x.0).collect();
+            let len: usize = right.iter().map(|&(_, c)| c).sum();
+            assert!(j <= len, "no point has slope {}", k);
+            let (lo, hi) = nth_and_next(right, j);
+            let hi = match hi {
+                Some(x) => Bound::Included(x + self.shr),
+                None => Bound::Unbounded,
+            };
+            (Bound::Included(lo + self.shr), hi)
+        }
+    }
+}
+
+// (値, 個数) の組の列を多重集合とみなし、小さい方から j 番目と j+1 番目を返す。
+fn nth_and_next<T: Copy + Ord>(
+    mut xs: Vec<(T, usize)>,
+    j: usize,
+) -> (T, Option<T>) {
+    xs.sort_unstable();
+    let mut acc = 0;
+    for i in 0..xs.len() {
+        acc += xs[i].1;
+        if acc > j {
+            return (xs[i].0, Some(xs[i].0));
+        }
+        if acc == j {
+            return (xs[i].0, xs.get(i + 1).map(|&(x, _)| x));
+        }
+    }
+    unreachable!()
+}
+
+// 要素数の小さい方を大きい方に足し込む。
+impl<I: SlopeTrickInt> AddAssign for SlopeFunction<I> {
+    fn add_assign(&mut self, mut other: Self) {
+        let len = |sf: &Self| sf.left.len() + sf.right.len();
+        if len(self) < len(&other) {
+            std::mem::swap(self, &mut other);
+        }
+        self.min += other.min;
+        for (l, c) in other.left {
+            self.add_left_slope(l + other.shl, c);
+        }
+        for Reverse((r, c)) in other.right {
+            self.add_right_slope(r + other.shr, c);
+        }
+    }
+}
+
+/// 上に凸な区分線形関数。
+///
+/// 整数の多重集合 $L$, $R$ に対して、次の形で表せる関数を管理する：
+/// $$ g(x) = c - \\sum\_{l\\in L} (l-x)\_+ - \\sum\_{r\\in R} (x-r)\_+. $$
+/// 最大化問題に対する slope trick で用いる。
+///
+/// # Idea
+/// $f(x) = -g(x)$ は下に凸なので、[`SlopeFunction`] で $f$ を管理する。
+/// 各操作は $f$ に対する操作に読み替えられる。たとえば、$\\max$ は $\\min$
+/// に、$\\max\_{y\\le x}$ は $\\min\_{y\\le x}$ になる。
+///
+/// # Complexity
+/// [`SlopeFunction`] の対応する操作と同じ。
+///
+/// # Examples
+/// ```
+/// use std::ops::Bound::Included;
+///
+/// use nekolib::math::ConcaveSlopeFunction;
+///
+/// let mut csf = ConcaveSlopeFunction::new();
+/// csf.add_left(-1);
+/// csf.add_left(3);
+/// csf.add_right(2);
+/// csf.add_const(10);
+/// // g(x) = 10 - 0.max(-1-x) - 0.max(3-x) - 0.max(x-2)
+/// //   x  | -2 -1  0  1  2  3  4  5
+/// // g(x) |  4  6  7  8  9  9  8  7
+/// assert_eq!(csf.max(), 9);
+/// assert_eq!(csf.argmax(), (Included(2), Included(3)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ConcaveSlopeFunction<I: Ord>(SlopeFunction<I>);
+
+impl<I: SlopeTrickInt + Neg<Output = I>> ConcaveSlopeFunction<I> {
+    /// $g(x) = 0$ で初期化する。
+    pub fn new() -> Self { Self::default() }
+    /// $g(x) \\xleftarrow{+} c$ で更新する。
+    pub fn add_const(&mut self, c: I) { self.0.add_const(-c); }
+    /// $g(x) \\xleftarrow{+} -(l-x)\_+$ で更新する。
+    pub fn add_left(&mut self, l: I) { self.0.add_left(l); }
+    /// $g(x) \\xleftarrow{+} -(x-r)\_+$ で更新する。
+    pub fn add_right(&mut self, r: I) { self.0.add_right(r); }
+    /// $g(x) \\xleftarrow{+} -k\\cdot (l-x)\_+$ で更新する。
+    pub fn add_left_slope(&mut self, l: I, k: usize) {
+        self.0.add_left_slope(l, k);
+    }
+    /// $g(x) \\xleftarrow{+} -k\\cdot (x-r)\_+$ で更新する。
+    pub fn add_right_slope(&mut self, r: I, k: usize) {
+        self.0.add_right_slope(r, k);
+    }
+    /// $g(x) \\xleftarrow{+} -|x-a|$ で更新する。
+    pub fn add_abs(&mut self, a: I) { self.0.add_abs(a); }
+    /// $h(x) = \\max\_{y\\le x} g(y)$ として、$g\\gets h$ で更新する。
+    pub fn max_left(&mut self) { self.0.min_left(); }
+    /// $h(x) = \\max\_{y\\ge x} g(y)$ として、$g\\gets h$ で更新する。
+    pub fn max_right(&mut self) { self.0.min_right(); }
+    /// $h(x) = g(x-a)$ として、$g\\gets h$ で更新する。
+    pub fn shift(&mut self, s: I) { self.0.shift(s); }
+    /// $[a, b]$ に対して $h(x) = \\max\_{y\\in[x-b, x-a]} g(y)$ として、$g\\gets h$ で更新する。
+    pub fn window(&mut self, window: RangeInclusive<I>) {
+        self.0.window(window);
+    }
+    /// $\\max\_{x\\in\\mathbb{R}} g(x)$ を返す。
+    pub fn max(&self) -> I { -self.0.min() }
+    /// $\\argmax\_{x\\in\\mathbb{R}} g(x)$ を返す。
+    pub fn argmax(&self) -> (Bound<I>, Bound<I>) { self.0.argmin() }
+    /// $\\argmax\_{x\\in\\mathbb{R}} g(x)$ に含まれる有限な値を一つ返す。
+    pub fn any_argmax(&self) -> Option<I> { self.0.any_argmin() }
+}
+
+/// 数列を非減少にするための最小コスト。
+///
+/// $\\sum\_{i=0}^{n-1} |a\_i-b\_i|$ を、$b\_0\\le b\_1\\le \\dots\\le b\_{n-1}$
+/// なる整数列 $b$ について最小化した値を返す。
+///
+/// # Idea
+/// $f\_i(x)$ を、$b\_i = x$ としたときの $i$ 項目までのコストの最小値とすると、
+/// $$ f\_i(x) = |a\_i-x| + \\min\_{y\\le x} f\_{i-1}(y) $$
+/// が成り立つ。これは [`SlopeFunction`] の `min_left` と `add_abs`
+/// で更新できる。
+///
+/// # Complexity
+/// $O(n\\log(n))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::math::min_cost_nondecreasing;
+///
+/// assert_eq!(min_cost_nondecreasing(&[3, 1, 2]), 2); // [2, 2, 2] など
+/// assert_eq!(min_cost_nondecreasing(&[1, 2, 2, 5]), 0);
+/// assert_eq!(min_cost_nondecreasing(&[5, 4, 3, 2, 1]), 6);
+/// assert_eq!(min_cost_nondecreasing(&[]), 0);
+/// ```
+pub fn min_cost_nondecreasing(a: &[i64]) -> i64 {
+    let mut sf = SlopeFunction::new();
+    for &ai in a {
+        sf.min_left();
+        sf.add_abs(ai);
+    }
+    sf.min()
 }
 
 pub trait SlopeTrickInt:
-    Copy + Add<Output = Self> + AddAssign + Default + Ord
+    Copy + Add<Output = Self> + AddAssign + Sub<Output = Self> + Default + Ord
 {
     // unsigned でいうところの saturating_sub
     fn doz(self, rhs: Self) -> Self;
+    // 個数 k 倍
+    fn mul_count(self, k: usize) -> Self;
 }
 
 macro_rules! impl_slope_trick_int {
@@ -295,8 +668,395 @@ macro_rules! impl_slope_trick_int {
             fn doz(self, rhs: Self) -> Self {
                 0.max(self - rhs)
             }
+            fn mul_count(self, k: usize) -> Self {
+                if self == 0 {
+                    return 0;
+                }
+                self * <$ty>::try_from(k).expect("count overflows")
+            }
         }
     )* }
 }
 
 impl_slope_trick_int! { i8 i16 i32 i64 i128 isize }
+
+#[test]
+fn test_min_cost_nondecreasing() {
+    fn naive(a: &[i64]) -> i64 {
+        // b_i は a の値のいずれかとしてよい
+        let mut xs = a.to_vec();
+        xs.sort_unstable();
+        xs.dedup();
+        let mut dp = vec![0; xs.len()];
+        for &ai in a {
+            let mut cur = i64::MAX;
+            for (dj, &x) in dp.iter_mut().zip(&xs) {
+                cur = cur.min(*dj);
+                *dj = cur + (ai - x).abs();
+            }
+        }
+        dp.into_iter().min().unwrap_or(0)
+    }
+
+    for n in 0..=6 {
+        let mut a = vec![-2; n];
+        loop {
+            assert_eq!(min_cost_nondecreasing(&a), naive(&a), "{:?}", a);
+            match a.iter().position(|&ai| ai < 4) {
+                Some(i) => {
+                    a[i] += 1;
+                    a[..i].iter_mut().for_each(|aj| *aj = -2);
+                }
+                None => break,
+            }
+        }
+        // 大きい値
+        let a: Vec<_> = (0..n as i64).map(|i| (i * 7 % 5 - 2) << 40).collect();
+        assert_eq!(min_cost_nondecreasing(&a), naive(&a));
+    }
+}
+
+#[test]
+fn test_concave() {
+    use std::ops::Bound::{Included, Unbounded};
+
+    let mut f = std::iter::successors(Some(2468_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = |k: u64| f.next().unwrap() % k;
+
+    // [-40, 40] 上での値を愚直に管理する
+    let xs: Vec<i64> = (-40..=40).collect();
+    for _ in 0..300 {
+        let mut csf = ConcaveSlopeFunction::new();
+        let mut sf = SlopeFunction::new();
+        let mut naive = vec![0; xs.len()];
+        for _ in 0..rand(10) {
+            let a = rand(21) as i64 - 10;
+            match rand(6) {
+                0 => {
+                    csf.add_const(a);
+                    sf.add_const(-a);
+                    naive.iter_mut().for_each(|y| *y += a);
+                }
+                1 => {
+                    csf.add_left(a);
+                    sf.add_left(a);
+                    for (y, &x) in naive.iter_mut().zip(&xs) {
+                        *y -= 0.max(a - x);
+                    }
+                }
+                2 => {
+                    csf.add_right(a);
+                    sf.add_right(a);
+                    for (y, &x) in naive.iter_mut().zip(&xs) {
+                        *y -= 0.max(x - a);
+                    }
+                }
+                3 => {
+                    csf.add_abs(a);
+                    sf.add_abs(a);
+                    for (y, &x) in naive.iter_mut().zip(&xs) {
+                        *y -= (x - a).abs();
+                    }
+                }
+                4 => {
+                    csf.max_left();
+                    sf.min_left();
+                    for i in 1..naive.len() {
+                        naive[i] = naive[i].max(naive[i - 1]);
+                    }
+                }
+                _ => {
+                    csf.max_right();
+                    sf.min_right();
+                    for i in (1..naive.len()).rev() {
+                        naive[i - 1] = naive[i - 1].max(naive[i]);
+                    }
+                }
+            }
+        }
+
+        let max = *naive.iter().max().unwrap();
+        let lo = naive.iter().position(|&y| y == max).unwrap();
+        let hi = naive.iter().rposition(|&y| y == max).unwrap();
+        let lo = if lo == 0 { Unbounded } else { Included(xs[lo]) };
+        let hi = if hi == xs.len() - 1 { Unbounded } else { Included(xs[hi]) };
+
+        assert_eq!(csf.max(), max);
+        assert_eq!(csf.argmax(), (lo, hi));
+        assert_eq!(csf.max(), -sf.min());
+        assert_eq!(csf.argmax(), sf.argmin());
+
+        match csf.any_argmax() {
+            Some(x) => {
+                assert_eq!(naive[(x + 40) as usize], max);
+                assert_eq!(sf.any_argmin(), Some(x));
+            }
+            None => {
+                assert_eq!((lo, hi), (Unbounded, Unbounded));
+                assert!(naive.iter().all(|&y| y == max));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_any_argmin() {
+    use std::ops::Bound::{Included, Unbounded};
+
+    let contains = |(lo, hi): (Bound<i64>, Bound<i64>), x: i64| {
+        let lo_ok = match lo {
+            Included(l) => l <= x,
+            _ => true,
+        };
+        let hi_ok = match hi {
+            Included(h) => x <= h,
+            _ => true,
+        };
+        lo_ok && hi_ok
+    };
+
+    let mut sf = SlopeFunction::new();
+    assert_eq!(sf.any_argmin(), None);
+    sf.add_const(5);
+    sf.min_left();
+    assert_eq!(sf.any_argmin(), None);
+    sf.add_left(-3);
+    assert_eq!(sf.argmin(), (Included(-3), Unbounded));
+    assert_eq!(sf.any_argmin(), Some(-3));
+    sf.shift(10);
+    assert_eq!(sf.any_argmin(), Some(7));
+
+    // 中央値の追跡
+    let a = [8_i64, -3, 5, 5, 0, 12, -7, 3, 3, 9, -1];
+    let mut sf = SlopeFunction::new();
+    for i in 0..a.len() {
+        sf.add_abs(a[i]);
+        let mut sorted = a[..=i].to_vec();
+        sorted.sort_unstable();
+        let median = sf.any_argmin().unwrap();
+        assert!(contains(sf.argmin(), median));
+        assert_eq!(median, sorted[i / 2]);
+    }
+}
+
+#[test]
+fn test_eval() {
+    use std::ops::Bound::Included;
+
+    let mut f = std::iter::successors(Some(3141_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = |k: u64| f.next().unwrap() % k;
+
+    // 平行移動の後に追加したもの
+    let mut sf = SlopeFunction::new();
+    sf.add_abs(4);
+    sf.shift(2);
+    sf.add_abs(0);
+    assert_eq!(sf.argmin(), (Included(0), Included(6)));
+    assert_eq!(sf.eval(-1), 8);
+    assert_eq!(sf.eval(3), 6);
+
+    // [-200, 200] 上での値を愚直に管理し、[-100, 100] で比較する
+    let n = 401;
+    let xs: Vec<i64> = (-200..=200).collect();
+    for _ in 0..300 {
+        let mut sf = SlopeFunction::new();
+        let mut naive = vec![0; n];
+        for _ in 0..rand(10) {
+            let a = rand(21) as i64 - 10;
+            match rand(7) {
+                0 => {
+                    sf.add_const(a);
+                    naive.iter_mut().for_each(|y| *y += a);
+                }
+                1 => {
+                    sf.add_left(a);
+                    for (y, &x) in naive.iter_mut().zip(&xs) {
+                        *y += 0.max(a - x);
+                    }
+                }
+                2 => {
+                    sf.add_right(a);
+                    for (y, &x) in naive.iter_mut().zip(&xs) {
+                        *y += 0.max(x - a);
+                    }
+                }
+                3 => {
+                    sf.add_abs(a);
+                    for (y, &x) in naive.iter_mut().zip(&xs) {
+                        *y += (x - a).abs();
+                    }
+                }
+                4 => {
+                    sf.min_left();
+                    for i in 1..n {
+                        naive[i] = naive[i].min(naive[i - 1]);
+                    }
+                }
+                5 => {
+                    let s = a / 2;
+                    sf.shift(s);
+                    let m = n as i64 - 1;
+                    naive = (0..=m)
+                        .map(|i| naive[(i - s).clamp(0, m) as usize])
+                        .collect();
+                }
+                _ => {
+                    let (lo, hi) = (a / 4, a / 4 + rand(4) as i64);
+                    sf.window(lo..=hi);
+                    let m = n as i64 - 1;
+                    naive = (0..=m)
+                        .map(|i| {
+                            let j0 = (i - hi).clamp(0, m) as usize;
+                            let j1 = (i - lo).clamp(0, m) as usize;
+                            *naive[j0..=j1].iter().min().unwrap()
+                        })
+                        .collect();
+                }
+            }
+        }
+
+        for i in 100..=300 {
+            assert_eq!(sf.eval(xs[i]), naive[i], "x = {}", xs[i]);
+        }
+        assert_eq!(sf.min(), *naive[100..=300].iter().min().unwrap());
+    }
+}
+
+#[test]
+fn test_add_assign() {
+    use std::ops::Bound::{Included, Unbounded};
+
+    let mut f = std::iter::successors(Some(2718_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = |k: u64| f.next().unwrap() % k;
+
+    // [-100, 100] 上での値を愚直に計算する
+    let xs: Vec<i64> = (-100..=100).collect();
+    let random_sf = |rand: &mut dyn FnMut(u64) -> u64| {
+        let mut sf = SlopeFunction::new();
+        for _ in 0..rand(8) {
+            let a = rand(21) as i64 - 10;
+            match rand(5) {
+                0 => sf.add_const(a),
+                1 => sf.add_left(a),
+                2 => sf.add_right(a),
+                3 => sf.add_abs(a),
+                _ => sf.shift(a / 2),
+            }
+        }
+        sf
+    };
+    for _ in 0..300 {
+        let f = random_sf(&mut rand);
+        let g = random_sf(&mut rand);
+        let naive: Vec<_> = xs.iter().map(|&x| f.eval(x) + g.eval(x)).collect();
+
+        let mut h = f.clone();
+        h += g.clone();
+        let mut h_rev = g;
+        h_rev += f;
+
+        let min = *naive.iter().min().unwrap();
+        let lo = naive.iter().position(|&y| y == min).unwrap();
+        let hi = naive.iter().rposition(|&y| y == min).unwrap();
+        let lo = if lo == 0 { Unbounded } else { Included(xs[lo]) };
+        let hi = if hi == xs.len() - 1 { Unbounded } else { Included(xs[hi]) };
+
+        for h in &[h, h_rev] {
+            assert_eq!(h.min(), min);
+            assert_eq!(h.argmin(), (lo, hi));
+            for (&x, &y) in xs.iter().zip(&naive) {
+                assert_eq!(h.eval(x), y);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_slope() {
+    let mut f = std::iter::successors(Some(1414_u64), |&x| {
+        Some(x * 48271 % 2147483647)
+    });
+    let mut rand = |k: u64| f.next().unwrap() % k;
+
+    // 個数つきで入れたものと 1 個ずつ入れたものを比べる
+    for _ in 0..300 {
+        let mut sf = SlopeFunction::new();
+        let mut sf1 = SlopeFunction::new();
+        for _ in 0..rand(10) {
+            let a = rand(21) as i64 - 10;
+            let k = rand(5) as usize;
+            match rand(5) {
+                0 => {
+                    sf.add_left_slope(a, k);
+                    (0..k).for_each(|_| sf1.add_left(a));
+                }
+                1 => {
+                    sf.add_right_slope(a, k);
+                    (0..k).for_each(|_| sf1.add_right(a));
+                }
+                2 => {
+                    sf.min_left();
+                    sf1.min_left();
+                }
+                3 => {
+                    sf.min_right();
+                    sf1.min_right();
+                }
+                _ => {
+                    sf.shift(a / 2);
+                    sf1.shift(a / 2);
+                }
+            }
+        }
+
+        assert_eq!(sf.min(), sf1.min());
+        assert_eq!(sf.argmin(), sf1.argmin());
+        for x in -30..=30 {
+            assert_eq!(sf.eval(x), sf1.eval(x));
+        }
+        let len = |sf: &SlopeFunction<i64>| {
+            let left: usize = sf.left.iter().map(|&(_, c)| c).sum();
+            let right: usize = sf.right.iter().map(|x| (x.0).1).sum();
+            (left, right)
+        };
+        let (left, right) = len(&sf1);
+        assert_eq!(len(&sf), (left, right));
+        for k in -(left as i64)..=right as i64 {
+            assert_eq!(sf.x_at_slope(k), sf1.x_at_slope(k));
+        }
+    }
+
+    // 大きい個数
+    let mut sf = SlopeFunction::<i64>::new();
+    let k = 1 << 40;
+    sf.add_left_slope(10, k);
+    sf.add_right_slope(-10, k);
+    sf.add_right_slope(0, 1);
+    assert_eq!(sf.min(), 20 << 40);
+    assert_eq!(sf.eval(-10), 20 << 40);
+    assert_eq!(sf.eval(10), (20 << 40) + 10);
+    assert_eq!(sf.left.len() + sf.right.len(), 3);
+}
+
+#[test]
+fn test_count_overflow_zero() {
+    // 値が 0 なら個数が型に収まらなくてもよい
+    let mut sf = SlopeFunction::<i32>::new();
+    sf.add_left_slope(0, 1 << 40);
+    assert_eq!(sf.eval(0), 0);
+    assert_eq!(sf.eval(5), 0);
+}
+
+#[test]
+#[should_panic(expected = "count overflows")]
+fn test_count_overflow() {
+    let mut sf = SlopeFunction::<i32>::new();
+    sf.add_left_slope(0, 1 << 40);
+    sf.eval(-1);
+}
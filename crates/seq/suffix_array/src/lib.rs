@@ -1,9 +1,12 @@
 //! 接尾辞配列。
 
-use std::cmp::Ordering::{Equal, Greater, Less};
+use std::cmp::Ordering::{self, Equal, Greater, Less};
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
-use std::ops::Index;
+use std::ops::{Index, Range};
+
+use n1_rmq::N1Rmq;
+use permutation::inverse;
 
 /// 接尾辞配列。
 ///
@@ -465,18 +468,6 @@ fn count_freq(buf: &[usize]) -> Vec<usize> {
     res
 }
 
-/// 逆順列を返す。
-///
-/// `res[x]` が `buf` における `x` の出現位置となる配列 `res` を返す。
-///
-/// # Requirements
-/// `buf` の要素は `0..buf.len()` に含まれ、かつ distinct である。
-fn inv_perm(buf: &[usize]) -> Vec<usize> {
-    let mut res = vec![0; buf.len()];
-    buf.iter().enumerate().for_each(|(i, &x)| res[x] = i);
-    res
-}
-
 /// LS type を求める。
 ///
 /// `res[i]` が `buf[i]` の LS type である配列 `res` を返す。
@@ -596,7 +587,7 @@ fn sa_is(buf: &[usize]) -> Vec<usize> {
     let len = buf.len();
     let count = count_freq(buf);
     if count.iter().all(|&x| x == 1) {
-        return inv_perm(buf);
+        return inverse(buf);
     }
 
     let ls = ls_classify(buf);
@@ -654,6 +645,34 @@ impl<T: Ord> SuffixArray<T> {
     /// assert_eq!(sa.search(&['a', 'e']).next(), None);
     /// ```
     pub fn search(&self, pat: &[T]) -> impl Iterator<Item = usize> + '_ {
+        self.sa[self.search_range(pat)].iter().cloned()
+    }
+
+    /// パターンの出現回数を返す。
+    ///
+    /// [`search`] で得られる出現位置の個数と等しい。
+    /// 空のパターンに対しては、空の接尾辞も含めて $|S|+1$ を返す。
+    ///
+    /// [`search`]: #method.search
+    ///
+    /// # Complexity
+    /// $O(|T|\\log(|S|))$ 時間。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::seq::SuffixArray;
+    ///
+    /// let s: Vec<_> = "abracadabra".chars().collect();
+    /// let sa: SuffixArray<_> = s.into();
+    ///
+    /// assert_eq!(sa.count(&['a']), 5);
+    /// assert_eq!(sa.count(&['a', 'b', 'r', 'a']), 2);
+    /// assert_eq!(sa.count(&['a', 'e']), 0);
+    /// assert_eq!(sa.count(&[]), 12);
+    /// ```
+    pub fn count(&self, pat: &[T]) -> usize { self.search_range(pat).len() }
+
+    fn search_range(&self, pat: &[T]) -> Range<usize> {
         let lo = {
             let mut lt = 1_usize.wrapping_neg();
             let mut ge = self.sa.len();
@@ -668,7 +687,7 @@ impl<T: Ord> SuffixArray<T> {
             ge
         };
         if lo >= self.sa.len() {
-            return self.sa[lo..lo].iter().cloned();
+            return lo..lo;
         }
         let hi = {
             let mut le = lo.wrapping_sub(1);
@@ -684,7 +703,67 @@ impl<T: Ord> SuffixArray<T> {
             }
             gt
         };
-        self.sa[lo..hi].iter().cloned()
+        lo..hi
+    }
+
+    /// 固定長のギャップを挟んだパターンの検索を行う。
+    ///
+    /// 断片 $P\_0, P\_1, \\dots, P\_{m-1}$ とギャップ長 $g\_0, g\_1, \\dots, g\_{m-2}$
+    /// に対して、パターン $P\_0\\,{?}^{g\_0}\\,P\_1\\,{?}^{g\_1}\\cdots P\_{m-1}$
+    /// の出現位置を昇順に返す。ここで ${?}$ は任意の一文字にマッチする。
+    /// パターン全体がテキストに収まる位置のみを返す。
+    ///
+    /// # Requirements
+    /// `parts.len() == gaps.len() + 1`.
+    ///
+    /// # Idea
+    /// 各断片の出現位置を [`search`] で求め、断片の先頭からのオフセットを引いて
+    /// パターンの先頭位置に揃える。すべての断片で揃った位置が答えとなる。
+    ///
+    /// [`search`]: #method.search
+    ///
+    /// # Complexity
+    /// 断片 $P\_k$ の出現回数を $\\mathrm{occ}\_k$ として、
+    /// $O(\\sum\_k (|P\_k|\\log(|S|) + \\mathrm{occ}\_k) + |S|)$ 時間。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::seq::SuffixArray;
+    ///
+    /// let s: Vec<_> = "abracadabra".chars().collect();
+    /// let sa: SuffixArray<_> = s.into();
+    ///
+    /// // "a?a"
+    /// assert_eq!(sa.search_with_gaps(&[&['a'], &['a']], &[1]), [3, 5]);
+    /// // "ab??c"
+    /// assert_eq!(sa.search_with_gaps(&[&['a', 'b'], &['c']], &[2]), [0]);
+    /// // "a??"
+    /// assert_eq!(sa.search_with_gaps(&[&['a'], &[]], &[2]), [0, 3, 5, 7]);
+    /// ```
+    pub fn search_with_gaps(
+        &self,
+        parts: &[&[T]],
+        gaps: &[usize],
+    ) -> Vec<usize> {
+        assert_eq!(parts.len(), gaps.len() + 1);
+        let n = self.buf.len();
+        let len = parts.iter().map(|p| p.len()).sum::<usize>()
+            + gaps.iter().sum::<usize>();
+        if len > n {
+            return vec![];
+        }
+
+        let mut count = vec![0; n - len + 1];
+        let mut offset = 0;
+        for (i, part) in parts.iter().enumerate() {
+            for pos in self.search(part) {
+                if (offset..=offset + n - len).contains(&pos) {
+                    count[pos - offset] += 1;
+                }
+            }
+            offset += part.len() + gaps.get(i).unwrap_or(&0);
+        }
+        (0..=n - len).filter(|&i| count[i] == parts.len()).collect()
     }
 
     /// 高さ配列を返す。
@@ -721,6 +800,123 @@ impl<T: Ord> SuffixArray<T> {
         res
     }
 
+    /// 相異なる空でない部分文字列の個数を返す。
+    ///
+    /// 高さ配列を $h$ として、$\\sum\_{r=1}^n (n-\\mathrm{sa}\_r-h\_r)$ で求める。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::seq::SuffixArray;
+    ///
+    /// let sa: SuffixArray<_> = "abab".to_string().into();
+    /// // a, b, ab, ba, aba, bab, abab
+    /// assert_eq!(sa.distinct_substrings(), 7);
+    /// ```
+    pub fn distinct_substrings(&self) -> usize {
+        let n = self.buf.len();
+        n * (n + 1) / 2 - self.lcpa().into_iter().sum::<usize>()
+    }
+
+    /// 各位置から始まる部分文字列のうち、新規なものの個数を返す。
+    ///
+    /// 位置 $i$ から始まる空でない部分文字列 $S[i\\dots j]$ であって、$i$
+    /// より前の位置から始まる部分文字列のいずれとも異なるものの個数を、各 $i$
+    /// について求める。
+    /// これは $(n-i) - \\max\_{j\\lt i} \\mathrm{lcp}(S[i\\dots], S[j\\dots])$
+    /// に等しく、総和は [`distinct_substrings`] と一致する。
+    ///
+    /// [`distinct_substrings`]: #method.distinct_substrings
+    ///
+    /// # Idea
+    /// 接尾辞を辞書順に並べた連結リストを考え、位置の大きい方から順に削除していく。
+    /// $i$ を削除する直前、リストには位置 $i$ 以下の接尾辞のみが残っているので、
+    /// $\\max\_{j\\lt i} \\mathrm{lcp}$ はリスト上で隣接する二つとの lcp の大きい方となる。
+    /// 隣接する要素間の lcp を持っておけば、削除時は二つの min を取って更新できる。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::seq::SuffixArray;
+    ///
+    /// let sa: SuffixArray<_> = "abab".to_string().into();
+    /// // [a, ab, aba, abab], [b, ba, bab], [], []
+    /// assert_eq!(sa.new_substrings_per_position(), [4, 3, 0, 0]);
+    /// ```
+    pub fn new_substrings_per_position(&self) -> Vec<usize> {
+        let n = self.buf.len();
+        let mut rank = vec![0; n + 1];
+        for (r, &i) in self.sa.iter().enumerate() {
+            rank[i] = r;
+        }
+
+        // 順位 0 は空の接尾辞、順位 n + 1 は番兵で、いずれも lcp は 0。
+        let mut lcp = self.lcpa();
+        lcp.push(0);
+        let mut prev: Vec<_> = (0..n + 2).map(|r| r.wrapping_sub(1)).collect();
+        let mut next: Vec<_> = (1..n + 3).collect();
+
+        let mut res = vec![0; n];
+        for i in (0..n).rev() {
+            let r = rank[i];
+            let (p, q) = (prev[r], next[r]);
+            res[i] = n - i - lcp[r].max(lcp[q]);
+            lcp[q] = lcp[q].min(lcp[r]);
+            next[p] = q;
+            prev[q] = p;
+        }
+        res
+    }
+
+    /// Burrows--Wheeler 変換をした列を返す。
+    ///
+    /// 末尾に番兵 $\\$$（どの文字よりも小さい）を付加した $S\\$$ の BWT から、
+    /// 番兵を除いた長さ $|S|$ の列を返す。すなわち、接尾辞配列を $A$
+    /// として、$A\_i \\gt 0$ なる $i$ について $S\_{A\_i-1}$ を $i$
+    /// の昇順に並べたものである。
+    /// 番兵があった位置（$A\_i = 0$ なる $i$）は [`bwt_sentinel`]
+    /// で得られ、逆変換 [`inverse_bwt`] にはこれも渡す必要がある。
+    ///
+    /// [`bwt_sentinel`]: #method.bwt_sentinel
+    /// [`inverse_bwt`]: fn.inverse_bwt.html
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::seq::SuffixArray;
+    ///
+    /// let s: Vec<_> = "abracadabra".chars().collect();
+    /// let sa: SuffixArray<_> = s.into();
+    /// // BWT of "abracadabra$" is "ard$rcaaaabb"
+    /// let bwt: String = sa.bwt().into_iter().collect();
+    /// assert_eq!(bwt, "ardrcaaaabb");
+    /// assert_eq!(sa.bwt_sentinel(), 3);
+    /// ```
+    pub fn bwt(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.sa
+            .iter()
+            .filter(|&&i| i > 0)
+            .map(|&i| self.buf[i - 1].clone())
+            .collect()
+    }
+
+    /// BWT において番兵があった位置を返す。
+    ///
+    /// # Complexity
+    /// $O(n)$ time.
+    pub fn bwt_sentinel(&self) -> usize {
+        self.sa.iter().position(|&i| i == 0).unwrap()
+    }
+
     /// 自身を消費し、内部表現を返す。
     ///
     /// # Examples
@@ -754,6 +950,79 @@ impl SuffixArray<char> {
         let pat: Vec<_> = pat.chars().collect();
         self.search(&pat)
     }
+
+    /// Burrows--Wheeler 変換をした文字列を返す。
+    ///
+    /// [`bwt`](#method.bwt) と同様に、番兵は含まない。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::seq::SuffixArray;
+    ///
+    /// let sa: SuffixArray<_> = "banana".to_string().into();
+    /// // BWT of "banana$" is "annb$aa"
+    /// assert_eq!(sa.bwt_string(), "annbaa");
+    /// assert_eq!(sa.bwt_sentinel(), 4);
+    /// ```
+    pub fn bwt_string(&self) -> String { self.bwt().into_iter().collect() }
+}
+
+/// Burrows--Wheeler 変換の逆変換。
+///
+/// [`SuffixArray::bwt`] の返す列 $L$ と [`SuffixArray::bwt_sentinel`]
+/// の返す番兵の位置 $p$ から、元の列を復元する。
+///
+/// # Idea
+/// 番兵を戻した BWT 列を $L'$ とし、それを安定ソートした列を $F$ とする。
+/// $L'\_i$ は $F$ においてソート後の位置 $\\mathrm{LF}(i)$ に移るとすると、
+/// 行 $i$ の接尾辞が $S\_j$ から始まるとき行 $\\mathrm{LF}(i)$ の接尾辞は
+/// $S\_{j-1}$ から始まる。
+/// 空の接尾辞（行 $0$）から始めて $L'$ を読みながら $\\mathrm{LF}$
+/// を辿れば、$S$ が後ろから得られる。
+///
+/// # Panics
+/// $p \\gt |L|$ のとき。
+///
+/// # Complexity
+/// $O(n\\log(n))$ time.
+///
+/// # Examples
+/// ```
+/// use nekolib::seq::{inverse_bwt, SuffixArray};
+///
+/// let s: Vec<_> = "abracadabra".chars().collect();
+/// let sa: SuffixArray<_> = s.clone().into();
+/// assert_eq!(inverse_bwt(&sa.bwt(), sa.bwt_sentinel()), s);
+///
+/// let l: Vec<_> = "ardrcaaaabb".chars().collect();
+/// assert_eq!(inverse_bwt(&l, 3), s);
+/// ```
+pub fn inverse_bwt<T: Ord + Clone>(bwt: &[T], sentinel: usize) -> Vec<T> {
+    let n = bwt.len();
+    assert!(sentinel <= n, "sentinel position out of bounds");
+
+    // 番兵を None として戻す
+    let l: Vec<_> = bwt[..sentinel]
+        .iter()
+        .map(Some)
+        .chain(std::iter::once(None))
+        .chain(bwt[sentinel..].iter().map(Some))
+        .collect();
+    let mut ord: Vec<_> = (0..=n).collect();
+    ord.sort_by_key(|&i| l[i]);
+    let mut lf = vec![0; n + 1];
+    for (k, &i) in ord.iter().enumerate() {
+        lf[i] = k;
+    }
+
+    let mut res = Vec::with_capacity(n);
+    let mut r = 0;
+    for _ in 0..n {
+        res.push(l[r].unwrap().clone());
+        r = lf[r];
+    }
+    res.reverse();
+    res
 }
 
 impl<T: Ord> Index<usize> for SuffixArray<T> {
@@ -765,6 +1034,115 @@ impl<T: Ord> From<SuffixArray<T>> for Vec<usize> {
     fn from(sa: SuffixArray<T>) -> Self { sa.sa }
 }
 
+/// LCP の RMQ つき接尾辞配列。
+///
+/// 接尾辞配列 $A$ に加え、その逆順列 (rank) と、高さ配列の RMQ を持つ。
+/// 接尾辞 $i$, $j$ の最長共通接頭辞長は、rank を $r\_i \\lt r\_j$
+/// として高さ配列の $(r\_i, r\_j]$ における最小値となるので、$O(1)$
+/// 時間で求められる。
+///
+/// # Complexity
+/// 前処理は、接尾辞配列の構築に加えて $O(n)$ 時間。
+///
+/// # Examples
+/// ```
+/// use nekolib::seq::{LcpSuffixArray, SuffixArray};
+///
+/// let sa: SuffixArray<_> = "abracadabra".to_string().into();
+/// let sa: LcpSuffixArray<_> = sa.into();
+/// assert_eq!(sa.lcp(0, 7), 4);
+/// assert_eq!(sa.lcp(3, 5), 1);
+/// assert_eq!(sa.lcp(2, 2), 9);
+/// ```
+pub struct LcpSuffixArray<T: Ord> {
+    sa: SuffixArray<T>,
+    rank: Vec<usize>,
+    lcpa: N1Rmq<usize>,
+}
+
+impl<T: Ord> From<SuffixArray<T>> for LcpSuffixArray<T> {
+    fn from(sa: SuffixArray<T>) -> Self {
+        let mut rank = vec![0; sa.sa.len()];
+        for (i, &si) in sa.sa.iter().enumerate() {
+            rank[si] = i;
+        }
+        let lcpa = sa.lcpa().into();
+        Self { sa, rank, lcpa }
+    }
+}
+
+impl<T: Ord> LcpSuffixArray<T> {
+    /// 接尾辞 $i$ と接尾辞 $j$ の最長共通接頭辞長を返す。
+    ///
+    /// $i = j$ のときは接尾辞の長さ $|S|-i$ を返す。
+    /// $i = |S|$ や $j = |S|$ は空の接尾辞を表し、$i = j = |S|$ を含めて
+    /// $0$ を返す。
+    ///
+    /// # Panics
+    /// $i \\gt |S|$ または $j \\gt |S|$ のとき。
+    ///
+    /// # Complexity
+    /// $O(1)$ 時間。
+    ///
+    /// # Examples
+    /// ```
+    /// use nekolib::seq::{LcpSuffixArray, SuffixArray};
+    ///
+    /// let sa: SuffixArray<_> = "abab".to_string().into();
+    /// let sa: LcpSuffixArray<_> = sa.into();
+    /// assert_eq!(sa.lcp(0, 2), 2); // abab, ab
+    /// assert_eq!(sa.lcp(1, 1), 3); // bab, bab
+    /// assert_eq!(sa.lcp(4, 0), 0); // (empty), abab
+    /// assert_eq!(sa.lcp(4, 4), 0); // (empty), (empty)
+    /// ```
+    pub fn lcp(&self, i: usize, j: usize) -> usize {
+        let n = self.sa.buf.len();
+        assert!(i <= n && j <= n, "index out of bounds");
+        if i == j {
+            return n - i;
+        }
+        let (ri, rj) = (self.rank[i], self.rank[j]);
+        let (l, r) = if ri < rj { (ri, rj) } else { (rj, ri) };
+        *self.lcpa.min(l + 1, r + 1)
+    }
+
+    /// $S\[i\\dots i+l\_1\]$ と $S\[j\\dots j+l\_2\]$ を辞書順で比較する。
+    ///
+    /// # Complexity
+    /// $O(1)$ 時間。
+    ///
+    /// # Examples
+    /// ```
+    /// use std::cmp::Ordering::{Equal, Greater, Less};
+    ///
+    /// use nekolib::seq::{LcpSuffixArray, SuffixArray};
+    ///
+    /// let sa: SuffixArray<_> = "abracadabra".to_string().into();
+    /// let sa: LcpSuffixArray<_> = sa.into();
+    /// assert_eq!(sa.compare_substr(0, 4, 7, 4), Equal); // abra, abra
+    /// assert_eq!(sa.compare_substr(0, 4, 7, 3), Greater); // abra, abr
+    /// assert_eq!(sa.compare_substr(0, 2, 3, 2), Less); // ab, ac
+    /// assert_eq!(sa.compare_substr(4, 0, 5, 0), Equal); // (empty), (empty)
+    /// ```
+    pub fn compare_substr(
+        &self,
+        i: usize,
+        len1: usize,
+        j: usize,
+        len2: usize,
+    ) -> Ordering {
+        let buf = &self.sa.buf;
+        assert!(i + len1 <= buf.len());
+        assert!(j + len2 <= buf.len());
+        let len = len1.min(len2);
+        let l = self.lcp(i, j).min(len);
+        if l == len { len1.cmp(&len2) } else { buf[i + l].cmp(&buf[j + l]) }
+    }
+
+    /// 接尾辞配列を返す。
+    pub fn suffix_array(&self) -> &SuffixArray<T> { &self.sa }
+}
+
 #[test]
 fn test_simple() {
     let buf = "abracadabra".to_string();
@@ -786,6 +1164,31 @@ fn test_empty_pat() {
     let sa: SuffixArray<_> = "empty".to_string().into();
     let occ: Vec<_> = sa.search_str("").collect();
     assert_eq!(occ, [5, 0, 1, 2, 3, 4]);
+    assert_eq!(sa.count(&[]), 6);
+
+    let sa: SuffixArray<_> = "".to_string().into();
+    assert_eq!(sa.count(&[]), 1);
+    assert_eq!(sa.count(&['x']), 0);
+}
+
+#[test]
+fn test_count() {
+    let s: Vec<_> = "abaababaabaababaababa".chars().collect();
+    let n = s.len();
+    let sa: SuffixArray<_> = s.clone().into();
+    for i in 0..=n {
+        for j in i..=n.min(i + 6) {
+            let pat = &s[i..j];
+            let naive = (0..=n - pat.len())
+                .filter(|&k| &s[k..k + pat.len()] == pat)
+                .count();
+            assert_eq!(sa.count(pat), naive);
+            assert_eq!(sa.count(pat), sa.search(pat).count());
+        }
+    }
+    assert_eq!(sa.count(&['c']), 0);
+    assert_eq!(sa.count(&['b', 'b']), 0);
+    assert_eq!(sa.count(&s[..]), 1);
 }
 
 #[test]
@@ -802,3 +1205,192 @@ fn test_naive() {
     let sa: Vec<_> = sa.into();
     assert_eq!(sa, naive_sa);
 }
+
+#[test]
+fn test_compare_substr() {
+    fn naive_lcp<T: Eq>(a: &[T], b: &[T]) -> usize {
+        a.iter().zip(b).take_while(|(x, y)| x == y).count()
+    }
+
+    for s in ["abracadabra", "aaaaaaa", "abababab", "mississippi", "", "x"] {
+        let buf: Vec<_> = s.chars().collect();
+        let n = buf.len();
+        let sa: LcpSuffixArray<_> = SuffixArray::from(buf.clone()).into();
+        for i in 0..=n {
+            for j in 0..=n {
+                assert_eq!(sa.lcp(i, j), naive_lcp(&buf[i..], &buf[j..]));
+                for len1 in 0..=n - i {
+                    for len2 in 0..=n - j {
+                        let expected = buf[i..i + len1].cmp(&buf[j..j + len2]);
+                        let actual = sa.compare_substr(i, len1, j, len2);
+                        assert_eq!(actual, expected);
+                    }
+                }
+            }
+        }
+    }
+
+    let n = 1000;
+    let f = |x: &i32| Some((x * 29 + 71) % 143);
+    let buf: Vec<_> = std::iter::successors(Some(2_i32), f)
+        .map(|x| x % 3)
+        .take(n)
+        .collect();
+    let sa: LcpSuffixArray<_> = SuffixArray::from(buf.clone()).into();
+    for i in (0..=n).step_by(7) {
+        for j in (0..=n).step_by(11) {
+            let lcp = naive_lcp(&buf[i..], &buf[j..]);
+            assert_eq!(sa.lcp(i, j), lcp);
+            for len1 in [0, lcp, lcp + 1, n - i] {
+                for len2 in [0, lcp, lcp + 1, n - j] {
+                    let len1 = len1.min(n - i);
+                    let len2 = len2.min(n - j);
+                    let expected = buf[i..i + len1].cmp(&buf[j..j + len2]);
+                    let actual = sa.compare_substr(i, len1, j, len2);
+                    assert_eq!(actual, expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_new_substrings() {
+    use std::collections::BTreeSet;
+
+    fn naive<T: Ord>(buf: &[T]) -> Vec<usize> {
+        let n = buf.len();
+        let mut seen = BTreeSet::new();
+        (0..n)
+            .map(|i| (i + 1..=n).filter(|&j| seen.insert(&buf[i..j])).count())
+            .collect()
+    }
+
+    for s in ["abracadabra", "aaaaaaa", "abababab", "mississippi", "", "x"] {
+        let buf: Vec<_> = s.chars().collect();
+        let expected = naive(&buf);
+        let sa: SuffixArray<_> = buf.into();
+        assert_eq!(sa.new_substrings_per_position(), expected);
+        assert_eq!(sa.distinct_substrings(), expected.iter().sum());
+    }
+
+    let n = 300;
+    let f = |x: &i32| Some((x * 29 + 71) % 143);
+    for k in 1..=4 {
+        let buf: Vec<_> = std::iter::successors(Some(2_i32), f)
+            .map(|x| x % k)
+            .take(n)
+            .collect();
+        let expected = naive(&buf);
+        let sa: SuffixArray<_> = buf.into();
+        let actual = sa.new_substrings_per_position();
+        assert_eq!(actual, expected);
+        assert_eq!(sa.distinct_substrings(), actual.iter().sum());
+    }
+}
+
+#[test]
+fn test_search_with_gaps() {
+    fn naive<T: Ord>(buf: &[T], parts: &[&[T]], gaps: &[usize]) -> Vec<usize> {
+        // None をワイルドカードとしたパターンを作って全位置で照合する
+        let mut pat = vec![];
+        for (i, part) in parts.iter().enumerate() {
+            pat.extend(part.iter().map(Some));
+            pat.extend((0..*gaps.get(i).unwrap_or(&0)).map(|_| None));
+        }
+        if pat.len() > buf.len() {
+            return vec![];
+        }
+        (0..=buf.len() - pat.len())
+            .filter(|&i| {
+                pat.iter().enumerate().all(|(j, &c)| match c {
+                    Some(c) => *c == buf[i + j],
+                    None => true,
+                })
+            })
+            .collect()
+    }
+
+    for s in ["abracadabra", "aaaaaaa", "abababab", "mississippi", ""] {
+        let buf: Vec<_> = s.chars().collect();
+        let sa: SuffixArray<_> = buf.clone().into();
+
+        // 断片を "" / "a" / "b" / "s" / "ab" / "ss" / "ssi" から選ぶ
+        let cand: Vec<Vec<_>> = ["", "a", "b", "s", "ab", "ss", "ssi"]
+            .iter()
+            .map(|p| p.chars().collect())
+            .collect();
+        for k in 1..=3_u32 {
+            let m = cand.len().pow(k);
+            for mut x in 0..m {
+                let mut parts = vec![];
+                for _ in 0..k {
+                    parts.push(&cand[x % cand.len()][..]);
+                    x /= cand.len();
+                }
+                for g in 0..3_usize.pow(k - 1) {
+                    let gaps: Vec<_> =
+                        (0..k - 1).map(|j| g / 3_usize.pow(j) % 3).collect();
+                    assert_eq!(
+                        sa.search_with_gaps(&parts, &gaps),
+                        naive(&buf, &parts, &gaps)
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_lcp_empty_suffix() {
+    for s in ["", "a", "abab", "mississippi"] {
+        let buf: Vec<_> = s.chars().collect();
+        let n = buf.len();
+        let sa: LcpSuffixArray<_> = SuffixArray::from(buf).into();
+        for i in 0..=n {
+            assert_eq!(sa.lcp(i, i), n - i);
+            assert_eq!(sa.lcp(i, n), 0);
+            assert_eq!(sa.lcp(n, i), 0);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_lcp_out_of_bounds() {
+    let sa: LcpSuffixArray<_> = SuffixArray::from("abab".to_string()).into();
+    sa.lcp(0, 5);
+}
+
+#[test]
+fn test_bwt() {
+    for s in ["", "a", "banana", "abracadabra", "mississippi", "aaaa"] {
+        let buf: Vec<_> = s.chars().collect();
+        let n = buf.len();
+        let sa: SuffixArray<_> = buf.clone().into();
+
+        // 巡回シフトを素朴にソートしたものと比較する
+        let mut ext: Vec<_> = buf.iter().map(|&c| Some(c)).collect();
+        ext.push(None);
+        let mut rot: Vec<_> =
+            (0..=n).map(|i| [&ext[i..], &ext[..i]].concat()).collect();
+        rot.sort();
+        let naive: Vec<_> = rot.iter().map(|r| r[n]).collect();
+        let sentinel = naive.iter().position(|c| c.is_none()).unwrap();
+        let naive: Vec<_> = naive.into_iter().flatten().collect();
+
+        assert_eq!(sa.bwt(), naive);
+        assert_eq!(sa.bwt_sentinel(), sentinel);
+        assert_eq!(sa.bwt_string(), naive.iter().collect::<String>());
+        assert_eq!(inverse_bwt(&sa.bwt(), sa.bwt_sentinel()), buf);
+    }
+
+    let n = 1000;
+    let f = |x: &i32| Some((x * 29 + 71) % 143);
+    let buf: Vec<_> = std::iter::successors(Some(2_i32), f)
+        .map(|x| x % 3)
+        .take(n)
+        .collect();
+    let sa: SuffixArray<_> = buf.clone().into();
+    assert_eq!(inverse_bwt(&sa.bwt(), sa.bwt_sentinel()), buf);
+}
@@ -20,6 +20,16 @@ pub trait Magma {
     type Set: Eq;
     /// $x \\circ y$ を返す。
     fn op(&self, x: Self::Set, y: Self::Set) -> Self::Set;
+    /// $x \\circ y$ を参照から計算して返す。
+    ///
+    /// デフォルト実装は `x`, `y` を clone して [`op`](#tymethod.op) を呼ぶ。
+    /// clone が重い型では、オーバーライドすることでコピーを減らせる。
+    fn op_ref(&self, x: &Self::Set, y: &Self::Set) -> Self::Set
+    where
+        Self::Set: Clone,
+    {
+        self.op(x.clone(), y.clone())
+    }
 }
 
 /// 結合法則を満たす。
@@ -223,6 +233,15 @@ where
             self.multiplicative().partial_recip(x).unwrap()
         }
     }
+    /// $x \\ne 0$ であれば乗法 $\\ast$ に関する逆元 $x^{-1}$ を返す。
+    /// $x = 0$ であれば `None` を返す。
+    fn try_recip(&self, x: Self::Set) -> Option<Self::Set> {
+        if x == self.additive().id() {
+            None
+        } else {
+            self.multiplicative().partial_recip(x)
+        }
+    }
 }
 
 #[macro_export]
@@ -312,3 +331,32 @@ fn sanity_check() {
     assert_eq!(monoid.op(2, 3), 1);
     assert_eq!(monoid.recip(4), 4);
 }
+
+#[test]
+fn op_ref() {
+    struct Concat;
+    impl Magma for Concat {
+        type Set = String;
+        fn op(&self, x: String, y: String) -> String { x + &y }
+    }
+    struct ConcatRef;
+    impl Magma for ConcatRef {
+        type Set = String;
+        fn op(&self, x: String, y: String) -> String { x + &y }
+        fn op_ref(&self, x: &String, y: &String) -> String {
+            let mut res = String::with_capacity(x.len() + y.len());
+            res.push_str(x);
+            res.push_str(y);
+            res
+        }
+    }
+
+    let words = ["", "a", "bc", "def"];
+    for x in words.iter().map(|w| w.to_string()) {
+        for y in words.iter().map(|w| w.to_string()) {
+            let expected = Concat.op(x.clone(), y.clone());
+            assert_eq!(Concat.op_ref(&x, &y), expected);
+            assert_eq!(ConcatRef.op_ref(&x, &y), expected);
+        }
+    }
+}
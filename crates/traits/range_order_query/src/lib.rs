@@ -0,0 +1,46 @@
+//! 区間の順序統計クエリ。
+
+use count::{Count, Count3way};
+use find_nth::FindNth;
+use quantile::Quantile;
+
+/// 区間の順序統計クエリ。
+///
+/// [`Count`], [`Count3way`], [`Quantile`], [`FindNth`] をまとめたもの。
+/// wavelet matrix のように、これらをすべて処理できる型を一つの境界で受け取るために用いる。
+/// 各トレイトを実装している型には自動で実装される。
+///
+/// [`Count`]: ../count/trait.Count.html
+/// [`Count3way`]: ../count/trait.Count3way.html
+/// [`Quantile`]: ../quantile/trait.Quantile.html
+/// [`FindNth`]: ../find_nth/trait.FindNth.html
+///
+/// # Examples
+/// ```
+/// use nekolib::ds::WaveletMatrix;
+/// use nekolib::traits::RangeOrderQuery;
+///
+/// // 区間 [l, r) の中央値（小さい方）と、それと等しい値の個数・最初の位置
+/// fn median<Q: RangeOrderQuery<u32>>(
+///     q: &Q,
+///     l: usize,
+///     r: usize,
+/// ) -> Option<(u32, usize, Option<usize>)> {
+///     let x = q.quantile(l..r, (r - l).saturating_sub(1) / 2)?;
+///     Some((x, q.count(l..r, x), q.find_nth(l..r, x, 0)))
+/// }
+///
+/// let wm: WaveletMatrix<u32> = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3].into();
+/// assert_eq!(median(&wm, 0, 10), Some((3, 2, Some(0))));
+/// assert_eq!(median(&wm, 4, 9), Some((5, 2, Some(4))));
+/// assert_eq!(median(&wm, 3, 3), None);
+/// ```
+pub trait RangeOrderQuery<I>:
+    Count<I> + Count3way<I> + Quantile<Output = I> + FindNth<I>
+{
+}
+
+impl<I, T> RangeOrderQuery<I> for T where
+    T: Count<I> + Count3way<I> + Quantile<Output = I> + FindNth<I>
+{
+}
@@ -1,4 +1,4 @@
-//! 加法に関する wrapper クラス。
+//! アフィン変換の合成に関する wrapper クラス。
 
 use std::fmt::Debug;
 
@@ -6,6 +6,35 @@ use additive::{AddAssoc, Zero};
 use binop::{Associative, Identity, Magma};
 use multiplicative::One;
 
+/// アフィン変換の合成を返す演算を持つ。
+///
+/// 組 $(a, b)$ で $\\lambda x.\\; ax+b$ を表す。
+/// 演算は左側の変換を先に適用するものとして定義する。
+/// $$ (a, b) \\circ (c, d) = (ac, bc+d). $$
+/// すなわち $f\\circ g$ は $\\lambda x.\\; g(f(x))$ に対応し、通常の関数合成とは順序が逆である。
+/// たとえばセグ木で区間 $[l, r)$ を畳み込むと、$f\_l, f\_{l+1}, \\dots, f\_{r-1}$
+/// の順に適用する変換が得られる。
+///
+/// 単位元は $(1, 0)$ であり、[`One`] と [`Zero`] で定義する。
+/// 交換法則は満たさない。
+///
+/// # Examples
+/// ```
+/// use nekolib::traits::Magma;
+/// use nekolib::utils::OpAffine;
+///
+/// let op_affine = OpAffine::<i64>::default();
+/// let f = (2, 1); // 2x + 1
+/// let g = (3, 4); // 3x + 4
+///
+/// // 3 * (2x + 1) + 4 = 6x + 7
+/// assert_eq!(op_affine.op(f, g), (6, 7));
+/// // 2 * (3x + 4) + 1 = 6x + 9
+/// assert_eq!(op_affine.op(g, f), (6, 9));
+/// ```
+///
+/// [`One`]: ../../traits/multiplicative/trait.One.html
+/// [`Zero`]: ../../traits/additive/trait.Zero.html
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OpAffine<T> {
     OpAffineV,
@@ -41,3 +70,29 @@ impl<T> Associative for OpAffine<T> where
     T: Add<Output = T> + Mul<Output = T> + Eq + Clone + Sized + AddAssoc
 {
 }
+
+#[test]
+fn assoc_id() {
+    let op_affine = OpAffine::<i64>::default();
+    let e = op_affine.id();
+    let mut f =
+        std::iter::successors(Some(296_u64), |&x| Some(x * 48271 % 2147483647))
+            .map(|x| (x % 21) as i64 - 10);
+    let mut rand = || f.next().unwrap();
+    for _ in 0..1000 {
+        let f = (rand(), rand());
+        let g = (rand(), rand());
+        let h = (rand(), rand());
+        assert_eq!(op_affine.op(f, e), f);
+        assert_eq!(op_affine.op(e, f), f);
+        assert_eq!(
+            op_affine.op(op_affine.op(f, g), h),
+            op_affine.op(f, op_affine.op(g, h)),
+        );
+
+        // 左側の変換を先に適用する
+        let apply = |(a, b): (i64, i64), x: i64| a * x + b;
+        let z = rand();
+        assert_eq!(apply(op_affine.op(f, g), z), apply(g, apply(f, z)));
+    }
+}
@@ -1,9 +1,30 @@
+//! 最大公約数に関する wrapper クラス。
+
 use std::fmt::Debug;
 
 use additive::Zero;
 use binop::{Associative, Commutative, Identity, Magma};
 use gcd::Gcd;
 
+/// 最大公約数を返す演算を持つ。
+///
+/// [`Gcd`] により定義される。単位元は [`Zero`] で定義する。
+///
+/// 符号つき整数に対しては [`Gcd`] が非負の値を返すため、
+/// 単位元の性質 $x\\circ 0 = 0\\circ x = x$ は非負の $x$ に対してのみ成り立つ。
+///
+/// # Examples
+/// ```
+/// use nekolib::traits::{Identity, Magma};
+/// use nekolib::utils::OpGcd;
+///
+/// let op_gcd = OpGcd::<u32>::default();
+/// assert_eq!(op_gcd.op(12, 18), 6);
+/// assert_eq!(op_gcd.op(op_gcd.id(), 18), 18);
+/// ```
+///
+/// [`Gcd`]: ../../math/gcd/trait.Gcd.html
+/// [`Zero`]: ../../traits/additive/trait.Zero.html
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OpGcd<T> {
     OpGcdV,
@@ -31,3 +52,22 @@ where
 
 impl<T> Associative for OpGcd<T> where T: Gcd + Eq + Sized {}
 impl<T> Commutative for OpGcd<T> where T: Gcd + Eq + Sized {}
+
+#[test]
+fn assoc_id() {
+    let op_gcd = OpGcd::<u64>::default();
+    let e = op_gcd.id();
+    for x in 0..30 {
+        assert_eq!(op_gcd.op(x, e), x);
+        assert_eq!(op_gcd.op(e, x), x);
+        for y in 0..30 {
+            assert_eq!(op_gcd.op(x, y), op_gcd.op(y, x));
+            for z in 0..30 {
+                assert_eq!(
+                    op_gcd.op(op_gcd.op(x, y), z),
+                    op_gcd.op(x, op_gcd.op(y, z)),
+                );
+            }
+        }
+    }
+}
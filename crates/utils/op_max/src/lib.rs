@@ -37,3 +37,23 @@ where
 
 impl<T> Associative for OpMax<T> where T: Ord + Eq + Sized {}
 impl<T> Commutative for OpMax<T> where T: Ord + Eq + Sized {}
+
+#[test]
+fn assoc_id() {
+    let op_max = OpMax::<i32>::default();
+    let e = op_max.id();
+    let a = [i32::MIN, -3, -1, 0, 1, 2, 5, i32::MAX];
+    for &x in &a {
+        assert_eq!(op_max.op(x, e), x);
+        assert_eq!(op_max.op(e, x), x);
+        for &y in &a {
+            assert_eq!(op_max.op(x, y), op_max.op(y, x));
+            for &z in &a {
+                assert_eq!(
+                    op_max.op(op_max.op(x, y), z),
+                    op_max.op(x, op_max.op(y, z)),
+                );
+            }
+        }
+    }
+}
@@ -37,3 +37,23 @@ where
 
 impl<T> Associative for OpMin<T> where T: Ord + Eq + Sized {}
 impl<T> Commutative for OpMin<T> where T: Ord + Eq + Sized {}
+
+#[test]
+fn assoc_id() {
+    let op_min = OpMin::<i32>::default();
+    let e = op_min.id();
+    let a = [i32::MIN, -3, -1, 0, 1, 2, 5, i32::MAX];
+    for &x in &a {
+        assert_eq!(op_min.op(x, e), x);
+        assert_eq!(op_min.op(e, x), x);
+        for &y in &a {
+            assert_eq!(op_min.op(x, y), op_min.op(y, x));
+            for &z in &a {
+                assert_eq!(
+                    op_min.op(op_min.op(x, y), z),
+                    op_min.op(x, op_min.op(y, z)),
+                );
+            }
+        }
+    }
+}
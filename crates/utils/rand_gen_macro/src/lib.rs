@@ -127,6 +127,35 @@ macro_rules! rand_gen_builder {
 /// assert_eq!(x, 5);
 /// ```
 ///
+/// 範囲をタプルで並べると、各成分を順に生成したタプルが得られる。
+/// 左辺にはパターンを書くことができ、`[(0..n, 0..n); m]` のように
+/// 配列の要素として使うこともできる。
+///
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha20Rng;
+///
+/// use nekolib::rand_gen;
+/// use nekolib::utils::rand_gen_macro::*;
+///
+/// rand_gen! {
+///     rng: ChaCha20Rng;
+///
+///     (n, mut m) in (2_usize..=10, 1_usize..=20);
+///     (x, (y, z)) in (-5_i32..=5, (0_u8..10, 100_u64..=200));
+///     es in [(0..n, 0..n, 1_i64..=100); m];
+/// }
+/// m += 1;
+///
+/// assert!((2..=10).contains(&n) && (2..=21).contains(&m));
+/// assert!((-5..=5).contains(&x) && y < 10 && (100..=200).contains(&z));
+/// assert_eq!(es.len(), m - 1);
+/// let valid = |&(u, v, w): &(usize, usize, i64)| {
+///     u < n && v < n && (1..=100).contains(&w)
+/// };
+/// assert!(es.iter().all(valid));
+/// ```
+///
 /// `oj` を用いて、hack をするのに使うとよい。
 /// 以下の例は、想定解が Rust で標的が Python のプログラムのもの。
 ///
@@ -135,6 +164,14 @@ macro_rules! rand_gen_builder {
 /// ```
 #[macro_export]
 macro_rules! rand_gen {
+    ( @seed $seed:ident { ( $($pat:tt)* ) in $($r:tt)* } @rest ) => {
+        let ( $($pat)* ) =
+            $seed.generate($crate::rand_gen_builder!(@gen { $($r)* }));
+    };
+    ( @seed $seed:ident { ( $($pat:tt)* ) in $($r:tt)* } @rest ; $($rest:tt)* ) => {
+        rand_gen!(@seed $seed { ( $($pat)* ) in $($r)* } @rest);
+        rand_gen!(@seed $seed {} @rest $($rest)*);
+    };
     ( @seed $seed:ident { mut $a:ident in $($r:tt)* } @rest ) => {
         let mut $a = $seed.generate($crate::rand_gen_builder!(@gen { $($r)* }));
     };
@@ -284,7 +321,8 @@ impl RandomGenerator<AsciiGen> for ChaCha20Rng {
     }
 }
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
 use std::ops::{Range, RangeInclusive};
 
 use rand::distributions::{Distribution, Uniform};
@@ -543,9 +581,125 @@ impl RandomGenerator<VecOptionsMarker<RangeInclusive<i64>>> for ChaCha20Rng {
     }
 }
 
+macro_rules! impl_tuple {
+    ( $( ($($a:ident)*) )* ) => { $(
+        impl<$($a),*> RandomGenerator<($($a,)*)> for ChaCha20Rng
+        where
+            $( ChaCha20Rng: RandomGenerator<$a>, )*
+        {
+            type Output = ($(<ChaCha20Rng as RandomGenerator<$a>>::Output,)*);
+            #[allow(non_snake_case)]
+            fn generate(&mut self, s: ($($a,)*)) -> Self::Output {
+                let ($($a,)*) = s;
+                ($(self.generate($a),)*)
+            }
+        }
+        impl<$($a: Clone),*> RandomGenerator<VecMarker<($($a,)*)>>
+            for ChaCha20Rng
+        where
+            $( ChaCha20Rng: RandomGenerator<$a>, )*
+        {
+            type Output = Vec<<Self as RandomGenerator<($($a,)*)>>::Output>;
+            fn generate(&mut self, s: VecMarker<($($a,)*)>) -> Self::Output {
+                let VecMarker { inner, len } = s;
+                (0..len).map(|_| self.generate(inner.clone())).collect()
+            }
+        }
+        impl<$($a: Clone),*> RandomGenerator<VecMarker<VecMarker<($($a,)*)>>>
+            for ChaCha20Rng
+        where
+            $( ChaCha20Rng: RandomGenerator<$a>, )*
+        {
+            type Output =
+                Vec<Vec<<Self as RandomGenerator<($($a,)*)>>::Output>>;
+            fn generate(
+                &mut self,
+                s: VecMarker<VecMarker<($($a,)*)>>,
+            ) -> Self::Output {
+                let VecMarker { inner, len } = s;
+                (0..len).map(|_| self.generate(inner.clone())).collect()
+            }
+        }
+    )* };
+}
+
+// 各成分を左から順に生成する。
+impl_tuple! { (A B) (A B C) (A B C D) (A B C D E) }
+
+/// $(0, 1, \\dots, n-1)$ の順列を生成する。$n$ は `.0` で与える。
+///
+/// Fisher–Yates により、$O(n)$ time で一様ランダムに生成する。
+///
+/// `where { derangement }` で、不動点のない順列（完全順列）を一様ランダムに生成する。
+/// 順列を生成して不動点がなければ採用する、を繰り返す。
+/// 完全順列である確率は $n\\to\\infty$ で $1/e$ に近づくので、期待 $O(n)$ time である。
+///
+/// # Panics
+/// `derangement` を指定して $n = 1$ のとき。
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha20Rng;
+///
+/// use nekolib::rand_gen;
+/// use nekolib::utils::rand_gen_macro::*;
+///
+/// rand_gen! {
+///     rng: ChaCha20Rng;
+///
+///     p in Permutation(10);
+///     q in Permutation(10) where { derangement };
+/// }
+///
+/// let mut sorted = p.clone();
+/// sorted.sort_unstable();
+/// assert!(sorted.into_iter().eq(0..10));
+///
+/// assert!(q.iter().enumerate().all(|(i, &qi)| qi != i));
+/// ```
 #[derive(Clone, Copy)]
 pub struct Permutation(pub usize);
 
+pub struct PermutationOptionsMarker {
+    n: usize,
+    derangement: bool,
+}
+
+impl GenOptions for Permutation {
+    type OptionType = PermutationOptionsMarker;
+    fn options(self) -> PermutationOptionsMarker {
+        PermutationOptionsMarker { n: self.0, derangement: false }
+    }
+}
+
+impl PermutationOptionsMarker {
+    pub fn derangement(mut self) -> Self {
+        self.derangement = true;
+        self
+    }
+}
+
+impl RandomGenerator<PermutationOptionsMarker> for ChaCha20Rng {
+    type Output = Vec<usize>;
+    fn generate(&mut self, subject: PermutationOptionsMarker) -> Vec<usize> {
+        let PermutationOptionsMarker { n, derangement } = subject;
+        if !derangement {
+            return self.generate(Permutation(n));
+        }
+
+        if n == 1 {
+            panic!("no derangement of length 1");
+        }
+        loop {
+            let res = self.generate(Permutation(n));
+            if res.iter().enumerate().all(|(i, &x)| x != i) {
+                return res;
+            }
+        }
+    }
+}
+
 impl RandomGenerator<Permutation> for ChaCha20Rng {
     type Output = Vec<usize>;
     fn generate(&mut self, subject: Permutation) -> Vec<usize> {
@@ -558,3 +712,471 @@ impl RandomGenerator<Permutation> for ChaCha20Rng {
         res
     }
 }
+
+/// 区間 $[l, r)$ ($l < r$) を `.0` 個生成する。端点は `.1` の範囲から選ぶ。
+///
+/// `where { disjoint }` で互いに素（端点の共有は可）、
+/// `where { nested }` で任意の二つが互いに素か包含関係にある区間族になる。
+/// 両方を指定した場合は `disjoint` が優先される。
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha20Rng;
+///
+/// use nekolib::rand_gen;
+/// use nekolib::utils::rand_gen_macro::*;
+///
+/// rand_gen! {
+///     rng: ChaCha20Rng;
+///
+///     a in Intervals(10, 0..=20);
+///     b in Intervals(10, 0..=20) where { disjoint };
+///     c in Intervals(10, 0..=20) where { nested };
+/// }
+///
+/// assert_eq!(a.len(), 10);
+/// assert!(a.iter().all(|&(l, r)| 0 <= l && l < r && r <= 20));
+///
+/// let mut b = b;
+/// b.sort_unstable();
+/// assert!(b.iter().all(|&(l, r)| 0 <= l && l < r && r <= 20));
+/// assert!(b.windows(2).all(|w| w[0].1 <= w[1].0));
+///
+/// let laminar = |(l0, r0): (i64, i64), (l1, r1): (i64, i64)| {
+///     r0 <= l1 || r1 <= l0 || (l0 <= l1 && r1 <= r0) || (l1 <= l0 && r0 <= r1)
+/// };
+/// assert!(c.iter().all(|&(l, r)| 0 <= l && l < r && r <= 20));
+/// assert!(c.iter().all(|&x| c.iter().all(|&y| laminar(x, y))));
+/// ```
+///
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha20Rng;
+///
+/// use nekolib::rand_gen;
+/// use nekolib::utils::rand_gen_macro::*;
+///
+/// let gen = |seed| {
+///     rand_gen! {
+///         rng = ChaCha20Rng::from_seed(seed);
+///
+///         a in Intervals(5, -10..=10);
+///         b in Intervals(5, -10..=10) where { disjoint };
+///         c in Intervals(5, -10..=10) where { nested };
+///     }
+///     (a, b, c)
+/// };
+///
+/// assert_eq!(gen([1; 32]), gen([1; 32]));
+/// assert_ne!(gen([1; 32]), gen([2; 32]));
+/// ```
+#[derive(Clone)]
+pub struct Intervals(pub usize, pub RangeInclusive<i64>);
+
+pub struct IntervalsOptionsMarker {
+    len: usize,
+    range: RangeInclusive<i64>,
+    disjoint: bool,
+    nested: bool,
+}
+
+impl GenOptions for Intervals {
+    type OptionType = IntervalsOptionsMarker;
+    fn options(self) -> IntervalsOptionsMarker {
+        let Self(len, range) = self;
+        IntervalsOptionsMarker { len, range, disjoint: false, nested: false }
+    }
+}
+
+impl IntervalsOptionsMarker {
+    pub fn disjoint(mut self) -> Self {
+        self.disjoint = true;
+        self
+    }
+    pub fn nested(mut self) -> Self {
+        self.nested = true;
+        self
+    }
+}
+
+impl RandomGenerator<Intervals> for ChaCha20Rng {
+    type Output = Vec<(i64, i64)>;
+    fn generate(&mut self, subject: Intervals) -> Vec<(i64, i64)> {
+        self.generate(subject.options())
+    }
+}
+
+impl RandomGenerator<IntervalsOptionsMarker> for ChaCha20Rng {
+    type Output = Vec<(i64, i64)>;
+    fn generate(
+        &mut self,
+        subject: IntervalsOptionsMarker,
+    ) -> Vec<(i64, i64)> {
+        let IntervalsOptionsMarker { len, range, disjoint, nested } = subject;
+
+        if len == 0 {
+            return vec![];
+        }
+
+        let start = *range.start();
+        let end = *range.end();
+        if start >= end {
+            panic!("no interval [l, r) with l < r");
+        }
+
+        let mut res = if disjoint {
+            // l[0] < r[0] <= l[1] < r[1] <= ... を作りたいので、
+            // x[k] + floor(k/2) が狭義単調増加になるように作って戻す。
+            if ((end - start) as u128) < len as u128 {
+                panic!("by pigeonhole principle, it is infeasible");
+            }
+            let xs = self.generate(VecOptionsMarker {
+                inner: start..=end + len as i64 - 1,
+                len: 2 * len,
+                sorted: true,
+                distinct: true,
+            });
+            (0..len)
+                .map(|i| (xs[2 * i] - i as i64, xs[2 * i + 1] - i as i64))
+                .collect()
+        } else if nested {
+            // 相異なる 2n 個の座標に、ランダムな括弧列を対応させる。
+            if ((end - start) as u128) < 2 * len as u128 - 1 {
+                panic!("by pigeonhole principle, it is infeasible");
+            }
+            let xs = self.generate(VecOptionsMarker {
+                inner: range,
+                len: 2 * len,
+                sorted: true,
+                distinct: true,
+            });
+            let mut res = vec![];
+            let mut stack = vec![];
+            let mut open = 0;
+            for x in xs {
+                let push = if open == len {
+                    false
+                } else if stack.is_empty() {
+                    true
+                } else {
+                    self.generate(0..2_u8) == 0
+                };
+                if push {
+                    open += 1;
+                    stack.push(x);
+                } else {
+                    res.push((stack.pop().unwrap(), x));
+                }
+            }
+            res
+        } else {
+            let between = Uniform::from(start..=end);
+            let other = Uniform::from(start..end);
+            (0..len)
+                .map(|_| {
+                    let l = between.sample(self);
+                    let r = other.sample(self);
+                    if r < l { (r, l) } else { (l, r + 1) }
+                })
+                .collect()
+        };
+
+        for i in (1..len).rev() {
+            let j = self.generate(0..=i);
+            res.swap(j, i);
+        }
+        res
+    }
+}
+
+/// `.0` 頂点の木を生成する。辺のリスト `Vec<(usize, usize)>` として返す。
+///
+/// デフォルトでは、Prüfer 列を一様ランダムに選ぶことで、ラベルつき木を一様ランダムに生成する。
+///
+/// `where { rooted }` で、頂点 $0$ を根とする根つき木を生成する。
+/// 各 $i$ ($1\\le i\\lt n$) の親を $0, 1, \\dots, i-1$ から一様ランダムに選び、
+/// 辺は `(親, 子)` の向きで $i$ の昇順に並ぶ。これは一様ランダムな木ではなく、
+/// 深さが $O(\\log(n))$ 程度のものが得られやすい。
+///
+/// `where { shuffled }` で、頂点番号をランダムに付け替え、辺の順番もシャッフルする。
+/// `rooted` と併せて指定した場合、根は頂点 $0$ のままで、辺の向きも保たれる。
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha20Rng;
+///
+/// use nekolib::rand_gen;
+/// use nekolib::utils::rand_gen_macro::*;
+///
+/// rand_gen! {
+///     rng: ChaCha20Rng;
+///
+///     n in 1_usize..=10;
+///     a in Tree(n);
+///     b in Tree(n) where { rooted };
+///     c in Tree(n) where { rooted, shuffled };
+/// }
+///
+/// assert_eq!(a.len(), n - 1);
+/// assert!(a.iter().all(|&(u, v)| u < n && v < n));
+///
+/// assert_eq!(b.len(), n - 1);
+/// assert!(b.iter().enumerate().all(|(i, &(p, c))| p < c && c == i + 1));
+///
+/// // 根以外の各頂点はちょうど一度ずつ子として現れる
+/// let mut children: Vec<_> = c.iter().map(|&(_, c)| c).collect();
+/// children.sort_unstable();
+/// assert!(children.into_iter().eq(1..n));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Tree(pub usize);
+
+pub struct TreeOptionsMarker {
+    n: usize,
+    rooted: bool,
+    shuffled: bool,
+}
+
+impl GenOptions for Tree {
+    type OptionType = TreeOptionsMarker;
+    fn options(self) -> TreeOptionsMarker {
+        TreeOptionsMarker { n: self.0, rooted: false, shuffled: false }
+    }
+}
+
+impl TreeOptionsMarker {
+    pub fn rooted(mut self) -> Self {
+        self.rooted = true;
+        self
+    }
+    pub fn shuffled(mut self) -> Self {
+        self.shuffled = true;
+        self
+    }
+}
+
+impl RandomGenerator<Tree> for ChaCha20Rng {
+    type Output = Vec<(usize, usize)>;
+    fn generate(&mut self, subject: Tree) -> Vec<(usize, usize)> {
+        self.generate(subject.options())
+    }
+}
+
+impl RandomGenerator<TreeOptionsMarker> for ChaCha20Rng {
+    type Output = Vec<(usize, usize)>;
+    fn generate(&mut self, subject: TreeOptionsMarker) -> Vec<(usize, usize)> {
+        let TreeOptionsMarker { n, rooted, shuffled } = subject;
+
+        if n <= 1 {
+            return vec![];
+        }
+
+        let mut res: Vec<_> = if rooted {
+            (1..n).map(|i| (self.generate(0..i), i)).collect()
+        } else {
+            // Prüfer 列から復元する。
+            let seq: Vec<_> = (0..n - 2).map(|_| self.generate(0..n)).collect();
+            let mut deg = vec![1; n];
+            seq.iter().for_each(|&x| deg[x] += 1);
+            let mut leaves: BinaryHeap<_> =
+                (0..n).filter(|&i| deg[i] == 1).map(Reverse).collect();
+            let mut res = vec![];
+            for x in seq {
+                let Reverse(leaf) = leaves.pop().unwrap();
+                res.push((leaf, x));
+                deg[x] -= 1;
+                if deg[x] == 1 {
+                    leaves.push(Reverse(x));
+                }
+            }
+            let Reverse(u) = leaves.pop().unwrap();
+            let Reverse(v) = leaves.pop().unwrap();
+            res.push((u, v));
+            res
+        };
+
+        if shuffled {
+            // rooted のときは根を 0 のままにする。
+            let mut label: Vec<_> = (0..n).collect();
+            let lo = if rooted { 1 } else { 0 };
+            for i in (lo + 1..n).rev() {
+                let j = self.generate(lo..=i);
+                label.swap(j, i);
+            }
+            for e in &mut res {
+                *e = (label[e.0], label[e.1]);
+            }
+            for i in (1..n - 1).rev() {
+                let j = self.generate(0..=i);
+                res.swap(j, i);
+            }
+        }
+        res
+    }
+}
+
+#[test]
+fn test_tree() {
+    use rand::SeedableRng;
+
+    fn is_tree(n: usize, es: &[(usize, usize)]) -> bool {
+        if es.len() != n.saturating_sub(1) {
+            return false;
+        }
+        let mut g = vec![vec![]; n];
+        for &(u, v) in es {
+            if u >= n || v >= n {
+                return false;
+            }
+            g[u].push(v);
+            g[v].push(u);
+        }
+        let mut visited = vec![false; n];
+        let mut stack = vec![];
+        if n > 0 {
+            visited[0] = true;
+            stack.push(0);
+        }
+        while let Some(v) = stack.pop() {
+            for &nv in &g[v] {
+                if !visited[nv] {
+                    visited[nv] = true;
+                    stack.push(nv);
+                }
+            }
+        }
+        visited.into_iter().all(|x| x)
+    }
+
+    fn is_rooted_at_0(n: usize, es: &[(usize, usize)]) -> bool {
+        let mut children: Vec<_> = es.iter().map(|&(_, c)| c).collect();
+        children.sort_unstable();
+        children.into_iter().eq(1..n)
+    }
+
+    let gen = |seed, n| {
+        rand_gen! {
+            rng = ChaCha20Rng::from_seed(seed);
+
+            a in Tree(n);
+            b in Tree(n) where { shuffled };
+            c in Tree(n) where { rooted };
+            d in Tree(n) where { rooted, shuffled };
+        }
+        (a, b, c, d)
+    };
+
+    for n in 0..=30 {
+        for seed in 0..20 {
+            let (a, b, c, d) = gen([seed; 32], n);
+            assert!(is_tree(n, &a));
+            assert!(is_tree(n, &b));
+            assert!(is_tree(n, &c));
+            assert!(is_tree(n, &d));
+            let parent_first = |(i, &(p, c))| p < c && c == i + 1;
+            assert!(c.iter().enumerate().all(parent_first));
+            assert!(is_rooted_at_0(n, &c));
+            assert!(is_rooted_at_0(n, &d));
+            assert_eq!(gen([seed; 32], n), (a, b, c, d));
+        }
+    }
+
+    // n = 3 のラベルつき木 3 通りがすべて現れる
+    let mut seen = BTreeSet::new();
+    for seed in 0..50 {
+        let (a, ..) = gen([seed; 32], 3);
+        let center = (0..3).find(|&v| a.iter().all(|&(x, y)| x == v || y == v));
+        seen.insert(center.unwrap());
+    }
+    assert_eq!(seen.len(), 3);
+}
+
+#[test]
+fn test_permutation() {
+    use rand::SeedableRng;
+
+    let gen = |seed, n| {
+        rand_gen! {
+            rng = ChaCha20Rng::from_seed(seed);
+
+            p in Permutation(n);
+            q in Permutation(n) where { derangement };
+        }
+        (p, q)
+    };
+
+    for n in (0..=20).filter(|&n| n != 1) {
+        for seed in 0..20 {
+            let (p, q) = gen([seed; 32], n);
+            for r in &[&p, &q] {
+                let mut count = vec![0; n];
+                r.iter().for_each(|&x| count[x] += 1);
+                assert!(count.iter().all(|&c| c == 1));
+            }
+            assert!(q.iter().enumerate().all(|(i, &qi)| qi != i));
+            assert_eq!(gen([seed; 32], n), (p, q));
+        }
+    }
+
+    // n = 3 の完全順列 2 通りがどちらも現れる
+    let mut seen = BTreeSet::new();
+    for seed in 0..30 {
+        seen.insert(gen([seed; 32], 3).1);
+    }
+    assert_eq!(seen.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "no derangement of length 1")]
+fn test_derangement_1() {
+    rand_gen! {
+        rng = <ChaCha20Rng as rand::SeedableRng>::from_seed([0; 32]);
+        _q in Permutation(1) where { derangement };
+    }
+}
+
+#[test]
+fn test_tuple() {
+    use rand::SeedableRng;
+
+    let gen = |seed| {
+        rand_gen! {
+            rng = ChaCha20Rng::from_seed(seed);
+
+            (n, mut m) in (2_usize..=10, 1_usize..=20);
+            (x, (y, z)) in (-5_i32..=5, (0_u8..10, 100_u64..=200));
+            es in [(0..n, 0..n, 1_i64..=100); m];
+            ps in [[(0_u8..=3, Permutation(3)); 2]; 3];
+        }
+        m += 1;
+        (n, m, x, y, z, es, ps)
+    };
+
+    for seed in 0..100 {
+        let (n, m, x, y, z, es, ps) = gen([seed; 32]);
+        assert!((2..=10).contains(&n));
+        assert!((2..=21).contains(&m));
+        assert!((-5..=5).contains(&x));
+        assert!(y < 10);
+        assert!((100..=200).contains(&z));
+        assert_eq!(es.len(), m - 1);
+        let valid = |&(u, v, w): &(usize, usize, i64)| {
+            u < n && v < n && (1..=100).contains(&w)
+        };
+        assert!(es.iter().all(valid));
+        assert_eq!(ps.len(), 3);
+        for pi in &ps {
+            assert_eq!(pi.len(), 2);
+            for (c, p) in pi {
+                assert!(*c <= 3);
+                let mut p = p.clone();
+                p.sort_unstable();
+                assert_eq!(p, [0, 1, 2]);
+            }
+        }
+        assert_eq!(gen([seed; 32]), (n, m, x, y, z, es, ps));
+    }
+    assert_ne!(gen([1; 32]), gen([2; 32]));
+}
@@ -50,7 +50,8 @@ pub use ordered_hash_::ordered_hash;
 pub use parallel_bisect::parallel_bisect;
 #[doc(inline)]
 pub use permutation::{
-    next_permutation, prev_permutation, Backward, Forward, Permutations,
+    compose, cycles, inverse, next_permutation, nth_permutation,
+    permutation_rank, prev_permutation, Backward, Forward, Permutations,
 };
 #[doc(inline)]
 pub use rle::{Rle, RleBy, RleByKey};
@@ -15,8 +15,10 @@ pub mod disjoint_sparse_table;
 pub mod foldable_deque;
 pub mod foldable_queue;
 pub mod incremental_line_set;
+pub mod interval_core;
 pub mod interval_map;
 pub mod interval_set;
+pub mod li_chao_tree;
 pub mod n1_rmq;
 pub mod potentialized_union_find;
 pub mod removable_heap;
@@ -34,7 +36,7 @@ pub use bicremental_median_dev::BicrementalMedianDev;
 #[doc(inline)]
 pub use binary_trie::BinaryTrie;
 #[doc(inline)]
-pub use bit_set::BitSet;
+pub use bit_set::{BitArray, BitSet};
 #[doc(inline)]
 pub use btree_bimap::BTreeBimap;
 #[doc(inline)]
@@ -52,12 +54,16 @@ pub use foldable_deque::FoldableDeque;
 #[doc(inline)]
 pub use foldable_queue::FoldableQueue;
 #[doc(inline)]
-pub use incremental_line_set::IncrementalLineSet;
+pub use incremental_line_set::{
+    Envelope, IncrementalLineSet, LowerEnvelope, OrdF64, UpperEnvelope,
+};
 #[doc(inline)]
 pub use interval_map::IntervalMap;
 #[doc(inline)]
 pub use interval_set::IntervalSet;
 #[doc(inline)]
+pub use li_chao_tree::{LiChaoInt, LiChaoTree};
+#[doc(inline)]
 pub use n1_rmq::N1Rmq;
 #[doc(inline)]
 pub use potentialized_union_find::PotentializedUnionFind;
@@ -74,4 +80,4 @@ pub use vec_act_segtree::VecActSegtree;
 #[doc(inline)]
 pub use vec_segtree::VecSegtree;
 #[doc(inline)]
-pub use wavelet_matrix::WaveletMatrix;
+pub use wavelet_matrix::{WaveletMatrix, WaveletMatrixSum};
@@ -17,6 +17,8 @@ pub mod polynomial;
 pub mod convolution;
 pub mod digit_sum;
 pub mod digits;
+pub mod dirichlet_convolve;
+pub mod discrete_root;
 pub mod divisors;
 pub mod dlog;
 pub mod equiv_mod;
@@ -26,6 +28,7 @@ pub mod factors_dup;
 pub mod frac_approx;
 pub mod fraction_bisect;
 pub mod garner;
+pub mod gaussian_elimination;
 pub mod gcd;
 pub mod gcd_recip;
 pub mod harmonic_floor_sum;
@@ -35,11 +38,13 @@ pub mod lcm;
 pub mod linear_floor_sum;
 pub mod linear_sieve;
 pub mod miller_rabin;
+pub mod min_plus_convolve;
 pub mod mod_ackermann;
 pub mod mod_factorial_binom;
 pub mod mod_ord;
 pub mod mod_pow;
 pub mod mod_recip_table_;
+pub mod mod_sqrt;
 pub mod mod_tetration;
 pub mod prime_pi_;
 pub mod segmented_factor_sieve;
@@ -60,14 +65,16 @@ pub use common_quot::CommonQuot;
 #[doc(inline)]
 pub use compact_sieve::CompactSieve;
 #[doc(inline)]
-pub use const_div::{ConstDiv, ConstDiv2};
+pub use const_div::{ConstDiv, ConstDiv128, ConstDiv2};
 #[doc(inline)]
 pub use continued_fraction_::continued_fraction;
 #[doc(inline)]
 pub use convolution::{
-    butterfly, butterfly_inv, convolve, convolve_u128, convolve_u128_mod,
+    butterfly, butterfly_inv, convolve, convolve_f64, convolve_into,
+    convolve_pow, convolve_tft, convolve_u128, convolve_u128_mod,
     convolve_u32_mod, convolve_u64, convolve_u64_mod, convolve_wrapping_u128,
-    convolve_wrapping_u64, ButterflyCache, NttFriendly,
+    convolve_wrapping_u64, ntt_u32, subset_convolve, ButterflyCache,
+    NttFriendly, RelaxedConvolution,
 };
 #[doc(inline)]
 pub use digits::Digits;
@@ -78,11 +85,13 @@ pub use fraction_bisect::FractionBisect;
 #[doc(inline)]
 pub use garner::{CrtMod, CrtWrapping};
 #[doc(inline)]
+pub use gaussian_elimination::{gaussian_elimination, GaussianElimination};
+#[doc(inline)]
 pub use is_close_float::IsCloseFloat;
 #[doc(inline)]
 pub use miller_rabin::MillerRabin;
 #[doc(inline)]
-pub use polynomial::Polynomial;
+pub use polynomial::{ArbitraryPolynomial, Polynomial};
 #[doc(inline)]
 pub use segmented_factor_sieve::SegmentedFactorSieve;
 // #[doc(inline)]
@@ -90,21 +99,25 @@ pub use segmented_factor_sieve::SegmentedFactorSieve;
 #[doc(inline)]
 pub use digit_sum::DigitSum;
 #[doc(inline)]
+pub use dirichlet_convolve::dirichlet_convolve;
+#[doc(inline)]
+pub use discrete_root::discrete_root;
+#[doc(inline)]
 pub use divisors::Divisors;
 #[doc(inline)]
 pub use dlog::DLog;
 #[doc(inline)]
 pub use equiv_mod::{EquivMod, EquivModIter};
 #[doc(inline)]
-pub use euler_phi::EulerPhi;
+pub use euler_phi::{euler_phi_table, EulerPhi};
 #[doc(inline)]
-pub use factors::Factors;
+pub use factors::{factors_big, Factors};
 #[doc(inline)]
 pub use factors_dup::FactorsDup;
 #[doc(inline)]
 pub use gcd::Gcd;
 #[doc(inline)]
-pub use gcd_recip::GcdRecip;
+pub use gcd_recip::{crt, ext_gcd, GcdRecip};
 #[doc(inline)]
 pub use harmonic_floor_sum::HarmonicFloorSum;
 #[doc(inline)]
@@ -116,6 +129,8 @@ pub use linear_floor_sum::LinearFloorSum;
 #[doc(inline)]
 pub use linear_sieve::LinearSieve;
 #[doc(inline)]
+pub use min_plus_convolve::{min_plus_convolve, min_plus_convolve_convex};
+#[doc(inline)]
 pub use mod_ackermann::ModAckermann;
 #[doc(inline)]
 pub use mod_factorial_binom::ModFactorialBinom;
@@ -126,11 +141,14 @@ pub use mod_pow::ModPow;
 #[doc(inline)]
 pub use mod_recip_table_::mod_recip_table_prime;
 #[doc(inline)]
+pub use mod_sqrt::mod_sqrt;
+#[doc(inline)]
 pub use mod_tetration::ModTetration;
 #[doc(inline)]
 pub use modint::{
-    DefaultId, DynamicModInt, Mod1000000007, Mod998244353, ModInt1000000007,
-    ModInt998244353, ModIntBase, Modulus, StaticModInt,
+    batch_recip, DefaultId, DynamicModInt, Mod1000000007, Mod998244353,
+    ModInt1000000007, ModInt998244353, ModIntBase, Modulus, ParseModIntError,
+    StaticModInt,
 };
 #[doc(inline)]
 pub use prime_pi_::prime_pi;
@@ -139,7 +157,9 @@ pub use sieve_n2_plus_1::SieveN2Plus1;
 #[doc(inline)]
 pub use sieve_n2_plus_n_plus_1::SieveN2PlusNPlus1;
 #[doc(inline)]
-pub use slope_function::SlopeFunction;
+pub use slope_function::{
+    min_cost_nondecreasing, ConcaveSlopeFunction, SlopeFunction,
+};
 #[doc(inline)]
 pub use sqrt::Sqrt;
 #[doc(inline)]
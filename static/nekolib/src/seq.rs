@@ -9,6 +9,6 @@ pub mod z_algo;
 #[doc(inline)]
 pub use kmp::KmpSearcher;
 #[doc(inline)]
-pub use suffix_array::SuffixArray;
+pub use suffix_array::{inverse_bwt, LcpSuffixArray, SuffixArray};
 #[doc(inline)]
 pub use z_algo::ZSearcher;
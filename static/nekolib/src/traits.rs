@@ -21,6 +21,7 @@ pub mod potential_function;
 pub mod push_pop;
 pub mod quantile;
 pub mod range_bounds;
+pub mod range_order_query;
 pub mod set_value;
 pub mod stateful_predicate;
 pub mod usize_group_by;
@@ -75,6 +76,8 @@ pub use range_bounds::{
     StartInclusive, StartUnbounded,
 };
 #[doc(inline)]
+pub use range_order_query::RangeOrderQuery;
+#[doc(inline)]
 pub use set_value::SetValue;
 #[doc(inline)]
 pub use stateful_predicate::StatefulPred;